@@ -34,9 +34,48 @@ export const commands: { [key: string]: Command } = {
   backends: {
     hide: false,
     subcommands: {
+      audit: {
+        hide: false,
+      },
+      "check-updates": {
+        hide: false,
+      },
+      clone: {
+        hide: false,
+      },
+      graph: {
+        hide: false,
+      },
+      "health-check": {
+        hide: false,
+      },
+      import: {
+        hide: false,
+      },
+      login: {
+        hide: false,
+      },
       ls: {
         hide: false,
       },
+      prune: {
+        hide: false,
+      },
+      rename: {
+        hide: false,
+      },
+      search: {
+        hide: false,
+      },
+      show: {
+        hide: false,
+      },
+      status: {
+        hide: false,
+      },
+      "validate-config": {
+        hide: false,
+      },
     },
   },
   "bin-paths": {
@@ -48,6 +87,9 @@ export const commands: { [key: string]: Command } = {
       clear: {
         hide: false,
       },
+      info: {
+        hide: false,
+      },
       path: {
         hide: false,
       },
@@ -174,6 +216,11 @@ export const commands: { [key: string]: Command } = {
   },
   lock: {
     hide: false,
+    subcommands: {
+      diff: {
+        hide: false,
+      },
+    },
   },
   ls: {
     hide: false,
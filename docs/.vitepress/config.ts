@@ -97,6 +97,7 @@ export default withMermaid(
                 { text: "aqua", link: "/dev-tools/backends/aqua" },
                 { text: "asdf", link: "/dev-tools/backends/asdf" },
                 { text: "cargo", link: "/dev-tools/backends/cargo" },
+                { text: "codeberg", link: "/dev-tools/backends/codeberg" },
                 { text: "dotnet", link: "/dev-tools/backends/dotnet" },
                 { text: "gem", link: "/dev-tools/backends/gem" },
                 { text: "github", link: "/dev-tools/backends/github" },
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use regex::Regex;
 use std::sync::LazyLock;
 
@@ -16,6 +17,9 @@ pub enum AssetOs {
     Linux,
     Macos,
     Windows,
+    Freebsd,
+    Openbsd,
+    Netbsd,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,6 +28,8 @@ pub enum AssetArch {
     Arm64,
     X86,
     Arm,
+    Ppc64le,
+    S390x,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -39,6 +45,21 @@ impl AssetOs {
             AssetOs::Linux => target == "linux",
             AssetOs::Macos => target == "macos" || target == "darwin",
             AssetOs::Windows => target == "windows",
+            AssetOs::Freebsd => target == "freebsd",
+            AssetOs::Openbsd => target == "openbsd",
+            AssetOs::Netbsd => target == "netbsd",
+        }
+    }
+
+    /// Names of the OS matched anywhere in an asset name when nothing matches exactly
+    fn fuzzy_names(&self) -> &'static [&'static str] {
+        match self {
+            AssetOs::Linux => &["linux"],
+            AssetOs::Macos => &["darwin", "macos", "osx"],
+            AssetOs::Windows => &["windows", "win64", "win32"],
+            AssetOs::Freebsd => &["freebsd"],
+            AssetOs::Openbsd => &["openbsd"],
+            AssetOs::Netbsd => &["netbsd"],
         }
     }
 }
@@ -50,6 +71,20 @@ impl AssetArch {
             AssetArch::Arm64 => target == "aarch64" || target == "arm64",
             AssetArch::X86 => target == "x86" || target == "i386" || target == "i686",
             AssetArch::Arm => target == "arm",
+            AssetArch::Ppc64le => matches!(target, "ppc64le" | "ppc64el" | "powerpc64le"),
+            AssetArch::S390x => target == "s390x" || target == "zseries",
+        }
+    }
+
+    /// Names of the arch matched anywhere in an asset name when nothing matches exactly
+    fn fuzzy_names(&self) -> &'static [&'static str] {
+        match self {
+            AssetArch::X64 => &["x86_64", "x86-64", "amd64", "x64"],
+            AssetArch::Arm64 => &["aarch64", "arm64"],
+            AssetArch::X86 => &["i386", "i686"],
+            AssetArch::Arm => &["armv6", "armv7", "armhf", "armel"],
+            AssetArch::Ppc64le => &["ppc64le", "ppc64el", "powerpc64le"],
+            AssetArch::S390x => &["s390x"],
         }
     }
 }
@@ -80,6 +115,9 @@ impl DetectedPlatform {
             AssetOs::Linux => "linux",
             AssetOs::Macos => "macos",
             AssetOs::Windows => "windows",
+            AssetOs::Freebsd => "freebsd",
+            AssetOs::Openbsd => "openbsd",
+            AssetOs::Netbsd => "netbsd",
         };
 
         let arch_str = match self.arch {
@@ -87,6 +125,8 @@ impl DetectedPlatform {
             AssetArch::Arm64 => "arm64",
             AssetArch::X86 => "x86",
             AssetArch::Arm => "arm",
+            AssetArch::Ppc64le => "ppc64le",
+            AssetArch::S390x => "s390x",
         };
 
         format!("{os_str}-{arch_str}")
@@ -107,6 +147,18 @@ static OS_PATTERNS: LazyLock<Vec<(AssetOs, Regex)>> = LazyLock::new(|| {
             AssetOs::Windows,
             Regex::new(r"(?i)(?:\b|_)win(?:32|64|dows)?(?:\b|_)").unwrap(),
         ),
+        (
+            AssetOs::Freebsd,
+            Regex::new(r"(?i)(?:\b|_)freebsd(?:\b|_|\d)").unwrap(),
+        ),
+        (
+            AssetOs::Openbsd,
+            Regex::new(r"(?i)(?:\b|_)openbsd(?:\b|_|\d)").unwrap(),
+        ),
+        (
+            AssetOs::Netbsd,
+            Regex::new(r"(?i)(?:\b|_)netbsd(?:\b|_|\d)").unwrap(),
+        ),
     ]
 });
 
@@ -126,7 +178,15 @@ static ARCH_PATTERNS: LazyLock<Vec<(AssetArch, Regex)>> = LazyLock::new(|| {
         ),
         (
             AssetArch::Arm,
-            Regex::new(r"(?i)(?:\b|_)arm(?:v[0-7])?(?:\b|_)").unwrap(),
+            Regex::new(r"(?i)(?:\b|_)arm(?:32)?(?:v[0-7]l?|hf|el)?(?:\b|_)").unwrap(),
+        ),
+        (
+            AssetArch::Ppc64le,
+            Regex::new(r"(?i)(?:\b|_)(?:ppc64le|ppc64el|powerpc64le)(?:\b|_)").unwrap(),
+        ),
+        (
+            AssetArch::S390x,
+            Regex::new(r"(?i)(?:\b|_)(?:s390x|zseries)(?:\b|_)").unwrap(),
         ),
     ]
 });
@@ -150,7 +210,7 @@ static LIBC_PATTERNS: LazyLock<Vec<(AssetLibc, Regex)>> = LazyLock::new(|| {
 
 static ARCHIVE_EXTENSIONS: &[&str] = &[
     ".tar.gz", ".tar.bz2", ".tar.xz", ".tar.zst", ".tgz", ".tbz2", ".txz", ".tzst", ".zip", ".7z",
-    ".tar",
+    ".cab", ".tar",
 ];
 
 pub static PLATFORM_PATTERNS: LazyLock<PlatformPatterns> = LazyLock::new(|| PlatformPatterns {
@@ -160,11 +220,85 @@ pub static PLATFORM_PATTERNS: LazyLock<PlatformPatterns> = LazyLock::new(|| Plat
     archive_extensions: ARCHIVE_EXTENSIONS,
 });
 
+/// ARM32 version in asset names, e.g.: `armv7`, `arm32v6`
+static ARM_VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:\b|_)arm(?:32)?v([5-7])").unwrap());
+/// hard-float ARM32 builds, e.g.: `armhf`, `gnueabihf`
+static ARM_HARD_FLOAT_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:armhf|eabihf)(?:\b|_)").unwrap());
+/// soft-float ARM32 builds, e.g.: `armel`, `gnueabi`
+static ARM_SOFT_FLOAT_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)(?:armel|eabi)(?:\b|_)").unwrap());
+
+/// ARM version in the /proc/cpuinfo model name, e.g.: `ARMv6-compatible processor`
+static ARM_MODEL_VERSION_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"ARMv(\d)").unwrap());
+
+/// The ARM32 CPU assets are picked for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArmCpu {
+    /// the ARM architecture version, e.g.: 6 for a Raspberry Pi Zero, 7 for a Raspberry Pi 2
+    pub version: u8,
+    /// whether the CPU has a VFP unit and can run hard-float (armhf) builds
+    pub vfp: bool,
+}
+
+impl Default for ArmCpu {
+    /// armv7 with VFP, the most common ARM32 target
+    fn default() -> Self {
+        Self {
+            version: 7,
+            vfp: true,
+        }
+    }
+}
+
+impl ArmCpu {
+    /// Reads the CPU of the current system from /proc/cpuinfo, only when running on ARM32
+    /// linux as it says nothing about the target of `--platform`
+    fn detect() -> Option<Self> {
+        if !cfg!(all(target_os = "linux", target_arch = "arm")) {
+            return None;
+        }
+        let cpuinfo = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        Self::from_cpuinfo(&cpuinfo)
+    }
+
+    fn from_cpuinfo(cpuinfo: &str) -> Option<Self> {
+        let field = |name: &str| {
+            cpuinfo.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == name).then(|| value.trim())
+            })
+        };
+        // ARMv6 CPUs such as the one of the Raspberry Pi Zero report "CPU architecture: 7"
+        let model_version = field("model name")
+            .and_then(|m| ARM_MODEL_VERSION_PATTERN.captures(m))
+            .and_then(|c| c[1].parse().ok());
+        let version = match model_version {
+            Some(version) => version,
+            None => field("CPU architecture")?
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect::<String>()
+                .parse()
+                .ok()?,
+        };
+        let vfp = field("Features")
+            .unwrap_or_default()
+            .split_whitespace()
+            .any(|f| f.starts_with("vfp"));
+        Some(Self { version, vfp })
+    }
+}
+
 /// Automatically detects the best asset for the current platform
 pub struct AssetPicker {
     target_os: String,
     target_arch: String,
     target_libc: String,
+    arch_aliases: IndexMap<String, String>,
+    arm_cpu: ArmCpu,
 }
 
 impl AssetPicker {
@@ -183,21 +317,89 @@ impl AssetPicker {
             target_os,
             target_arch,
             target_libc,
+            arch_aliases: Default::default(),
+            arm_cpu: ArmCpu::detect().unwrap_or_default(),
         }
     }
 
+    /// Custom arch names used in asset names, e.g.: `arm64v8` => `aarch64`
+    pub fn with_arch_aliases(mut self, arch_aliases: IndexMap<String, String>) -> Self {
+        self.arch_aliases = arch_aliases;
+        self
+    }
+
     /// Picks the best asset from available options
     pub fn pick_best_asset(&self, assets: &[String]) -> Option<String> {
+        trace!(
+            "asset picker: target os={} arch={} libc={}",
+            self.target_os, self.target_arch, self.target_libc
+        );
         let candidates = self.filter_archive_assets(assets);
+        for asset in assets.iter().filter(|a| !candidates.contains(a)) {
+            trace!("asset picker: skipped asset={asset} reason=not_an_archive");
+        }
         let mut scored_assets = self.score_all_assets(&candidates);
 
         // Sort by score (higher is better)
         scored_assets.sort_by(|a, b| b.0.cmp(&a.0));
 
+        if !candidates.iter().any(|a| self.matches_exactly(a))
+            && let Some(asset) = self.pick_fuzzy(&scored_assets)
+        {
+            warn!(
+                "no asset matches {}-{} exactly, using {asset} which only matches loosely",
+                self.target_os, self.target_arch
+            );
+            return Some(asset);
+        }
+
         // Return the best match if it has a positive score
-        scored_assets
+        let picked = scored_assets
             .first()
             .filter(|(score, _)| *score > 0)
+            .map(|(_, asset)| asset.clone());
+        match &picked {
+            Some(asset) => trace!("asset picker: picked asset={asset}"),
+            None => trace!("asset picker: no asset with a positive score"),
+        }
+        picked
+    }
+
+    /// Whether the OS or arch in the asset name is detected as the target one
+    fn matches_exactly(&self, asset: &str) -> bool {
+        self.score_os_match(asset) > 0 || self.score_arch_match(asset) > 0
+    }
+
+    /// Picks the best scored asset whose name contains, ignoring case, a known name of both
+    /// the target OS and arch, e.g.: `tool-Darwinarm64.tar.gz` for macos-arm64
+    fn pick_fuzzy(&self, scored_assets: &[(i32, String)]) -> Option<String> {
+        let os = [
+            AssetOs::Linux,
+            AssetOs::Macos,
+            AssetOs::Windows,
+            AssetOs::Freebsd,
+            AssetOs::Openbsd,
+            AssetOs::Netbsd,
+        ]
+        .into_iter()
+        .find(|os| os.matches_target(&self.target_os))?;
+        let arch = [
+            AssetArch::X64,
+            AssetArch::Arm64,
+            AssetArch::X86,
+            AssetArch::Arm,
+            AssetArch::Ppc64le,
+            AssetArch::S390x,
+        ]
+        .into_iter()
+        .find(|arch| arch.matches_target(&self.target_arch))?;
+        scored_assets
+            .iter()
+            .find(|(_, asset)| {
+                let asset = asset.to_lowercase();
+                os.fuzzy_names().iter().any(|n| asset.contains(n))
+                    && arch.fuzzy_names().iter().any(|n| asset.contains(n))
+            })
             .map(|(_, asset)| asset.clone())
     }
 
@@ -231,25 +433,36 @@ impl AssetPicker {
 
     /// Scores a single asset based on platform compatibility
     pub fn score_asset(&self, asset: &str) -> i32 {
-        let mut score = 0;
-
         // OS scoring
-        score += self.score_os_match(asset);
+        let os = self.score_os_match(asset);
 
         // Architecture scoring
-        score += self.score_arch_match(asset);
+        let arch = self.score_arch_match(asset);
 
         // Libc variant scoring (for Linux and Windows)
-        if self.target_os == "linux" || self.target_os == "windows" {
-            score += self.score_libc_match(asset);
-        }
+        let libc = if self.target_os == "linux" || self.target_os == "windows" {
+            self.score_libc_match(asset)
+        } else {
+            0
+        };
+
+        // ARM32 version and float ABI scoring
+        let arm = if AssetArch::Arm.matches_target(&self.target_arch) {
+            self.score_arm_variant(asset)
+        } else {
+            0
+        };
 
         // Format preferences
-        score += self.score_format_preferences(asset);
+        let format = self.score_format_preferences(asset);
 
         // Penalties for unwanted builds
-        score += self.score_build_penalties(asset);
+        let penalty = self.score_build_penalties(asset);
 
+        let score = os + arch + libc + arm + format + penalty;
+        trace!(
+            "asset picker: scored asset={asset} score={score} os={os} arch={arch} libc={libc} arm={arm} format={format} penalty={penalty}"
+        );
         score
     }
 
@@ -267,8 +480,12 @@ impl AssetPicker {
     }
 
     fn score_arch_match(&self, asset: &str) -> i32 {
+        let mut asset = asset.to_string();
+        for (alias, arch) in &self.arch_aliases {
+            asset = asset.replace(alias.as_str(), arch);
+        }
         for (arch, pattern) in PLATFORM_PATTERNS.arch_patterns.iter() {
-            if pattern.is_match(asset) {
+            if pattern.is_match(&asset) {
                 return if arch.matches_target(&self.target_arch) {
                     50 // Exact arch match
                 } else {
@@ -292,6 +509,26 @@ impl AssetPicker {
         0 // No libc detected
     }
 
+    fn score_arm_variant(&self, asset: &str) -> i32 {
+        let mut score = 0;
+        if let Some(version) = ARM_VERSION_PATTERN
+            .captures(asset)
+            .and_then(|c| c[1].parse::<u8>().ok())
+        {
+            score += match version.cmp(&self.arm_cpu.version) {
+                std::cmp::Ordering::Equal => 15,    // Exact ARM version
+                std::cmp::Ordering::Less => 5,      // Older ARM version, still runs
+                std::cmp::Ordering::Greater => -40, // Newer ARM version, won't run
+            };
+        }
+        if ARM_HARD_FLOAT_PATTERN.is_match(asset) {
+            score += if self.arm_cpu.vfp { 10 } else { -40 };
+        } else if ARM_SOFT_FLOAT_PATTERN.is_match(asset) && !self.arm_cpu.vfp {
+            score += 10;
+        }
+        score
+    }
+
     fn score_format_preferences(&self, asset: &str) -> i32 {
         if PLATFORM_PATTERNS
             .archive_extensions
@@ -313,6 +550,54 @@ impl AssetPicker {
     }
 }
 
+/// x86_64 CPU features hinted at in asset names, e.g.: `tool-linux-x64-avx2.tar.gz`
+static CPU_FEATURE_PATTERNS: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    vec![
+        ("avx512", Regex::new(r"(?i)avx-?512").unwrap()),
+        ("avx2", Regex::new(r"(?i)avx2").unwrap()),
+        ("x86-64-v4", Regex::new(r"(?i)x86[_-]64[_-]v4").unwrap()),
+        ("x86-64-v3", Regex::new(r"(?i)x86[_-]64[_-]v3").unwrap()),
+    ]
+});
+
+/// CPU features hinted at in the asset name that the current CPU doesn't support
+pub fn unsupported_cpu_features(asset: &str) -> Vec<&'static str> {
+    CPU_FEATURE_PATTERNS
+        .iter()
+        .filter(|(_, pattern)| pattern.is_match(asset))
+        .map(|(feature, _)| *feature)
+        .filter(|feature| !cpu_supports(feature))
+        .collect()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn cpu_supports(feature: &str) -> bool {
+    match feature {
+        "avx2" => is_x86_feature_detected!("avx2"),
+        "avx512" => is_x86_feature_detected!("avx512f"),
+        // https://en.wikipedia.org/wiki/X86-64#Microarchitecture_levels
+        "x86-64-v3" => {
+            is_x86_feature_detected!("avx2")
+                && is_x86_feature_detected!("bmi2")
+                && is_x86_feature_detected!("fma")
+        }
+        "x86-64-v4" => {
+            is_x86_feature_detected!("avx512f")
+                && is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512cd")
+                && is_x86_feature_detected!("avx512dq")
+                && is_x86_feature_detected!("avx512vl")
+        }
+        _ => true,
+    }
+}
+
+/// these are x86_64 features, other CPUs don't pick x86_64 assets in the first place
+#[cfg(not(target_arch = "x86_64"))]
+fn cpu_supports(_feature: &str) -> bool {
+    true
+}
+
 /// Detects platform information from a URL
 pub fn detect_platform_from_url(url: &str) -> Option<DetectedPlatform> {
     let mut detected_os = None;
@@ -376,6 +661,35 @@ mod tests {
         assert_eq!(picked, "tool-1.0.0-linux-x86_64.tar.gz");
     }
 
+    #[test]
+    fn test_asset_picker_fuzzy_fallback() {
+        let assets = vec![
+            "tool-1.0.0-Linuxamd64.tar.gz".to_string(),
+            "tool-1.0.0-Darwinarm64.tar.gz".to_string(),
+            "tool-1.0.0-Darwinamd64.tar.gz".to_string(),
+        ];
+        let picker = AssetPicker::new("macos".to_string(), "arm64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-Darwinarm64.tar.gz"
+        );
+        let picker = AssetPicker::new("linux".to_string(), "x64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-Linuxamd64.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_cpu_features() {
+        assert!(unsupported_cpu_features("tool-linux-x64.tar.gz").is_empty());
+        #[cfg(target_arch = "x86_64")]
+        assert_eq!(
+            unsupported_cpu_features("tool-linux-x64-avx2.tar.gz").is_empty(),
+            is_x86_feature_detected!("avx2")
+        );
+    }
+
     #[test]
     fn test_asset_scoring() {
         let picker = AssetPicker::new("linux".to_string(), "x86_64".to_string());
@@ -608,6 +922,97 @@ mod tests {
         assert_eq!(picked, "ripgrep-14.1.1-x86_64-pc-windows-msvc.zip");
     }
 
+    #[test]
+    fn test_ibm_arch_asset_picking() {
+        let assets = vec![
+            "tool-linux-amd64.tar.gz".to_string(),
+            "tool-linux-arm64.tar.gz".to_string(),
+            "tool-linux-ppc64el.tar.gz".to_string(),
+            "tool-linux-s390x.tar.gz".to_string(),
+        ];
+        let picker = AssetPicker::new("linux".to_string(), "ppc64le".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-linux-ppc64el.tar.gz"
+        );
+        let picker = AssetPicker::new("linux".to_string(), "s390x".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-linux-s390x.tar.gz"
+        );
+
+        let platform =
+            detect_platform_from_url("https://example.com/tool_Linux_powerpc64le.tar.gz").unwrap();
+        assert_eq!(platform.arch, AssetArch::Ppc64le);
+        assert_eq!(platform.to_platform_string(), "linux-ppc64le");
+        let platform =
+            detect_platform_from_url("https://example.com/tool-zseries-linux.tgz").unwrap();
+        assert_eq!(platform.arch, AssetArch::S390x);
+    }
+
+    #[test]
+    fn test_arm32_variants() {
+        let assets = vec![
+            "tool-linux-armv6.tar.gz".to_string(),
+            "tool-linux-armv7.tar.gz".to_string(),
+            "tool-linux-arm64.tar.gz".to_string(),
+        ];
+        let mut picker = AssetPicker::new("linux".to_string(), "arm".to_string());
+        picker.arm_cpu = ArmCpu::default();
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-linux-armv7.tar.gz"
+        );
+        picker.arm_cpu = ArmCpu {
+            version: 6,
+            vfp: true,
+        };
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-linux-armv6.tar.gz"
+        );
+
+        let assets = vec![
+            "tool_linux_armel.tar.gz".to_string(),
+            "tool_linux_armhf.tar.gz".to_string(),
+        ];
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool_linux_armhf.tar.gz"
+        );
+        picker.arm_cpu.vfp = false;
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool_linux_armel.tar.gz"
+        );
+
+        let cpuinfo = "processor\t: 0\nmodel name\t: ARMv6-compatible processor rev 7 (v6l)\nFeatures\t: half thumb fastmult vfp edsp java tls\nCPU architecture: 7\n";
+        assert_eq!(
+            ArmCpu::from_cpuinfo(cpuinfo),
+            Some(ArmCpu {
+                version: 6,
+                vfp: true
+            })
+        );
+        assert_eq!(ArmCpu::from_cpuinfo("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn test_arch_aliases() {
+        let assets = vec![
+            "tool-linux-arm32v7.tar.gz".to_string(),
+            "tool-linux-arm64v8.tar.gz".to_string(),
+        ];
+        let picker =
+            AssetPicker::new("linux".to_string(), "aarch64".to_string()).with_arch_aliases(
+                IndexMap::from([("arm64v8".to_string(), "aarch64".to_string())]),
+            );
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-linux-arm64v8.tar.gz"
+        );
+    }
+
     #[test]
     fn test_various_url_formats() {
         // Test different URL formats to ensure robustness
@@ -700,4 +1105,57 @@ mod tests {
             "GNU should score lower than assets without libc info on Windows"
         );
     }
+
+    #[test]
+    fn test_bsd_asset_picking() {
+        let assets = vec![
+            "tool-1.0.0-linux-amd64.tar.gz".to_string(),
+            "tool-1.0.0-darwin-amd64.tar.gz".to_string(),
+            "tool-1.0.0-freebsd-amd64.tar.gz".to_string(),
+            "tool-1.0.0-openbsd-amd64.tar.gz".to_string(),
+            "tool-1.0.0-netbsd-amd64.tar.gz".to_string(),
+            "tool-1.0.0-freebsd-arm64.tar.gz".to_string(),
+        ];
+
+        let picker = AssetPicker::new("freebsd".to_string(), "x64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-freebsd-amd64.tar.gz"
+        );
+        let picker = AssetPicker::new("openbsd".to_string(), "x64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-openbsd-amd64.tar.gz"
+        );
+        let picker = AssetPicker::new("netbsd".to_string(), "x64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-netbsd-amd64.tar.gz"
+        );
+        let picker = AssetPicker::new("freebsd".to_string(), "arm64".to_string());
+        assert_eq!(
+            picker.pick_best_asset(&assets).unwrap(),
+            "tool-1.0.0-freebsd-arm64.tar.gz"
+        );
+
+        // BSD assets shouldn't be picked on linux
+        let picker = AssetPicker::new("linux".to_string(), "x64".to_string());
+        assert!(
+            picker.score_asset("tool-1.0.0-freebsd-amd64.tar.gz")
+                < picker.score_asset("tool-1.0.0-linux-amd64.tar.gz")
+        );
+    }
+
+    #[test]
+    fn test_bsd_platform_detection_from_url() {
+        let platform =
+            detect_platform_from_url("https://example.com/tool_1.0.0_freebsd13_x86_64.tar.gz")
+                .unwrap();
+        assert_eq!(platform.os, AssetOs::Freebsd);
+        assert_eq!(platform.to_platform_string(), "freebsd-x64");
+
+        let platform =
+            detect_platform_from_url("https://example.com/tool-openbsd-arm64.tar.gz").unwrap();
+        assert_eq!(platform.to_platform_string(), "openbsd-arm64");
+    }
 }
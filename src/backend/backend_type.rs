@@ -17,6 +17,7 @@ pub enum BackendType {
     Aqua,
     Asdf,
     Cargo,
+    Codeberg,
     Core,
     Dotnet,
     Gem,
@@ -50,6 +51,7 @@ impl BackendType {
             "aqua" => BackendType::Aqua,
             "asdf" => BackendType::Asdf,
             "cargo" => BackendType::Cargo,
+            "codeberg" => BackendType::Codeberg,
             "core" => BackendType::Core,
             "dotnet" => BackendType::Dotnet,
             "gem" => BackendType::Gem,
@@ -2,42 +2,121 @@ use crate::backend::asset_detector;
 use crate::backend::backend_type::BackendType;
 use crate::backend::static_helpers::lookup_platform_key;
 use crate::backend::static_helpers::{
-    get_filename_from_url, install_artifact, template_string, try_with_v_prefix, verify_artifact,
+    arch_aliases, asset_pattern, checksum_from_file, env_vars, extract_filter,
+    get_filename_from_url, install_artifact, resolve_bin_path_template, string_list_option,
+    template_string, template_string_with_tag, transform_version, try_with_v_prefix,
+    verify_artifact, version_blocklist, version_map, version_transform,
 };
 use crate::cli::args::BackendArg;
+use crate::cmd::{CmdLineRunner, cmd};
 use crate::config::Config;
 use crate::config::Settings;
-use crate::http::HTTP;
+use crate::forgejo::{self, ForgejoApiClient};
+use crate::http::{HTTP, error_code, is_transient_error};
 use crate::install_context::InstallContext;
 use crate::toolset::ToolVersion;
 use crate::toolset::ToolVersionOptions;
-use crate::{backend::Backend, github, gitlab};
+use crate::toolset::Toolset;
+use crate::{backend::Backend, duration, env, file, github, gitlab, gpg, hash, http};
 use async_trait::async_trait;
-use eyre::Result;
+use eyre::{Result, WrapErr, bail, eyre};
+use globset::GlobSet;
+use itertools::Itertools;
 use regex::Regex;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio_retry::RetryIf;
+use tokio_retry::strategy::{ExponentialBackoff, jitter};
+use versions::Versioning;
 
 #[derive(Debug)]
 pub struct UnifiedGitBackend {
     ba: Arc<BackendArg>,
 }
 
+#[derive(Default)]
 struct ReleaseAsset {
     name: String,
     url: String,
     url_api: String,
     digest: Option<String>,
+    /// the release the asset was found in, unknown for lockfile and direct URLs
+    tag: Option<String>,
+    published_at: Option<String>,
+    /// the API url of the repo the asset was found in, see `fallback_repos`
+    api_url: Option<String>,
+    /// the body of the release, see `include_release_notes`
+    release_notes: Option<String>,
+    /// the repo the asset was found in, see `fallback_repos`
+    repo: Option<String>,
+    /// a detached signature of the asset in the same release, see `verify_release_signature`
+    signature_url: Option<String>,
+    /// the Content-Type of the asset, only reported by github, see `interpreter`
+    content_type: Option<String>,
+}
+
+impl ReleaseAsset {
+    /// The asset recorded in the lockfile, with the metadata of the release it is in
+    fn with_release_metadata(self, release: ReleaseAsset) -> Self {
+        Self {
+            tag: release.tag,
+            published_at: release.published_at,
+            api_url: release.api_url,
+            repo: release.repo,
+            release_notes: release.release_notes,
+            signature_url: release.signature_url,
+            ..self
+        }
+    }
+}
+
+/// Written to `.mise-provenance.json` in the install path by `mise install --record-provenance`
+#[derive(Debug, Serialize)]
+struct Provenance {
+    tool: String,
+    version: String,
+    url: String,
+    /// None when the asset was extracted while downloading without a sha256 to check or lock,
+    /// see `stream_extract`
+    sha256: Option<String>,
+    tag: Option<String>,
+    published_at: Option<String>,
+    api_url: Option<String>,
+}
+
+/// A release as shown by `mise ls-remote --json`
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteRelease {
+    pub version: String,
+    pub published_at: Option<String>,
+    pub asset_count: Option<usize>,
+    pub prerelease: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimit {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// unix timestamp when the limit resets
+    pub reset: Option<u64>,
 }
 
 const DEFAULT_GITHUB_API_BASE_URL: &str = "https://api.github.com";
 const DEFAULT_GITLAB_API_BASE_URL: &str = "https://gitlab.com/api/v4";
+const DEFAULT_CODEBERG_API_BASE_URL: &str = "https://codeberg.org/api/v1";
 
 #[async_trait]
 impl Backend for UnifiedGitBackend {
     fn get_type(&self) -> BackendType {
         if self.is_gitlab() {
             BackendType::Gitlab
+        } else if self.is_codeberg() {
+            BackendType::Codeberg
         } else {
             BackendType::Github
         }
@@ -48,43 +127,91 @@ impl Backend for UnifiedGitBackend {
     }
 
     async fn _list_remote_versions(&self, _config: &Arc<Config>) -> Result<Vec<String>> {
-        let repo = self.ba.tool_name();
-        let opts = self.ba.opts();
-        let api_url = self.get_api_url(&opts);
-        if self.is_gitlab() {
-            let releases = gitlab::list_releases_from_url(api_url.as_str(), &repo).await?;
-            Ok(releases
-                .into_iter()
-                .filter(|r| {
-                    opts.get("version_prefix")
-                        .is_none_or(|p| r.tag_name.starts_with(p))
-                })
-                .map(|r| self.strip_version_prefix(&r.tag_name))
-                .rev()
-                .collect())
-        } else {
-            let releases = github::list_releases_from_url(api_url.as_str(), &repo).await?;
-            Ok(releases
-                .into_iter()
-                .filter(|r| {
-                    opts.get("version_prefix")
-                        .is_none_or(|p| r.tag_name.starts_with(p))
-                })
-                .map(|r| self.strip_version_prefix(&r.tag_name))
-                .rev()
-                .collect())
+        Ok(self
+            .list_remote_releases()
+            .await?
+            .into_iter()
+            .map(|r| r.version)
+            .collect())
+    }
+
+    async fn latest_stable_version(&self, config: &Arc<Config>) -> Result<Option<String>> {
+        // ask for the latest release directly instead of paginating through every release
+        match self.latest_release_version(config).await? {
+            Some(version) => Ok(Some(version)),
+            None => self.latest_version(config, Some("latest".into())).await,
         }
     }
 
-    async fn install_version_(
-        &self,
-        ctx: &InstallContext,
-        mut tv: ToolVersion,
-    ) -> Result<ToolVersion> {
-        let repo = self.repo();
+    async fn install_version_(&self, ctx: &InstallContext, tv: ToolVersion) -> Result<ToolVersion> {
         let opts = tv.request.options();
-        let api_url = self.get_api_url(&opts);
+        self.scoped(&opts, self.install_release(ctx, tv)).await
+    }
+
+    async fn list_bin_paths(
+        &self,
+        _config: &Arc<Config>,
+        tv: &ToolVersion,
+    ) -> Result<Vec<std::path::PathBuf>> {
+        self.bin_paths(tv, &tv.request.options())
+    }
 
+    async fn exec_env(
+        &self,
+        _config: &Arc<Config>,
+        _ts: &Toolset,
+        tv: &ToolVersion,
+    ) -> Result<BTreeMap<String, String>> {
+        let install_path = tv.install_path();
+        let install_path = install_path.to_string_lossy();
+        Ok(env_vars(&tv.request.options())
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    k,
+                    template_string(&v, tv).replace("{install_path}", &install_path),
+                )
+            })
+            .collect())
+    }
+}
+
+impl UnifiedGitBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self { ba: Arc::new(ba) }
+    }
+
+    fn is_gitlab(&self) -> bool {
+        self.ba.backend_type() == BackendType::Gitlab
+    }
+
+    /// codeberg.org runs forgejo, whose API is close enough to github's to be used the same way
+    fn is_codeberg(&self) -> bool {
+        self.ba.backend_type() == BackendType::Codeberg
+    }
+
+    fn repo(&self) -> String {
+        // Use tool_name() method to properly resolve aliases
+        // This ensures that when an alias like "test-edit = github:microsoft/edit" is used,
+        // the repository name is correctly extracted as "microsoft/edit"
+        self.ba.tool_name()
+    }
+
+    // Helper to format asset names for error messages
+    fn format_asset_list<'a, I>(assets: I) -> String
+    where
+        I: Iterator<Item = &'a String>,
+    {
+        assets.cloned().collect::<Vec<_>>().join(", ")
+    }
+
+    /// Finds the release asset to download for the current platform, preferring the
+    /// URL recorded in the lockfile
+    async fn resolve_release_asset(
+        &self,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+    ) -> Result<ReleaseAsset> {
         // Check if URL already exists in lockfile platforms first
         let platform_key = self.get_platform_key();
         let asset = if let Some(existing_platform) = tv.lock_platforms.get(&platform_key) {
@@ -93,167 +220,1399 @@ impl Backend for UnifiedGitBackend {
                 platform_key,
                 existing_platform.url.clone().unwrap_or_default()
             );
-            ReleaseAsset {
+            let locked = ReleaseAsset {
                 name: existing_platform.name.clone().unwrap_or_else(|| {
                     get_filename_from_url(existing_platform.url.as_deref().unwrap_or(""))
                 }),
                 url: existing_platform.url.clone().unwrap_or_default(),
                 url_api: existing_platform.url_api.clone().unwrap_or_default(),
                 digest: None, // Don't use old digest from lockfile, will be fetched fresh if needed
+                ..Default::default()
+            };
+            // the lockfile only records the asset, not the release it was found in
+            if needs_release_metadata(opts) {
+                let release = self.find_release_asset(tv, opts).await?;
+                locked.with_release_metadata(release)
+            } else {
+                locked
             }
         } else {
-            // Find the asset URL for this specific version
-            self.resolve_asset_url(&tv, &opts, &repo, &api_url).await?
+            self.find_release_asset(tv, opts).await?
         };
 
-        // Download and install
-        self.download_and_install(ctx, &mut tv, &asset, &opts)
-            .await?;
-
-        Ok(tv)
+        trace!(
+            "asset resolution: selected asset={} url={}",
+            asset.name, asset.url
+        );
+        Ok(asset)
     }
 
-    async fn list_bin_paths(
+    /// Find the asset URL for this specific version
+    async fn find_release_asset(
         &self,
-        _config: &Arc<Config>,
         tv: &ToolVersion,
-    ) -> Result<Vec<std::path::PathBuf>> {
-        let opts = tv.request.options();
-        if let Some(bin_path_template) =
-            lookup_platform_key(&opts, "bin_path").or_else(|| opts.get("bin_path").cloned())
+        opts: &ToolVersionOptions,
+    ) -> Result<ReleaseAsset> {
+        self.with_fallback_repos(opts, |api_url, repo| async move {
+            let asset = self.resolve_asset_url(tv, opts, &repo, &api_url).await?;
+            Ok(ReleaseAsset {
+                api_url: Some(api_url),
+                repo: Some(repo),
+                ..asset
+            })
+        })
+        .await
+    }
+
+    /// Installs `tv`, called with the options of the API host applied by `scoped`
+    async fn install_release(
+        &self,
+        ctx: &InstallContext,
+        mut tv: ToolVersion,
+    ) -> Result<ToolVersion> {
+        let mut opts = tv.request.options();
+        if let Some(reason) = version_blocklist(&opts).get(&tv.version) {
+            let reason = reason.as_deref().unwrap_or("no reason given");
+            warn!("installing {tv} although it is in version_blocklist: {reason}");
+        }
+        let asset = match &ctx.from_file {
+            // named like the release asset it replaces, e.g.: to find it in `checksum_url`
+            Some(path) => ReleaseAsset {
+                name: path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                url: path.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+            None => self.resolve_release_asset(&tv, &opts).await?,
+        };
+        if !opts.contains_key("checksum")
+            && let Some(checksum) = self.checksum_from_url(&tv, &opts, &asset).await?
         {
-            let bin_path = template_string(&bin_path_template, tv);
-            Ok(vec![tv.install_path().join(&bin_path)])
-        } else {
-            self.discover_bin_paths(tv)
+            opts.opts.insert("checksum".to_string(), checksum);
         }
+
+        // Download and install
+        self.download_and_install(ctx, &mut tv, &asset, &opts)
+            .await?;
+        if env::RECORD_PROVENANCE.load(Ordering::Relaxed) {
+            self.write_provenance(&tv, &asset)?;
+        }
+        if opts
+            .get("include_release_notes")
+            .is_some_and(|v| v == "true")
+        {
+            self.write_release_notes(&tv, &asset)?;
+        }
+
+        Ok(tv)
     }
-}
 
-impl UnifiedGitBackend {
-    pub fn from_arg(ba: BackendArg) -> Self {
-        Self { ba: Arc::new(ba) }
+    /// Lists the remote releases, oldest first, with their versions as shown by `ls-remote`
+    pub async fn list_remote_releases(&self) -> Result<Vec<RemoteRelease>> {
+        let opts = self.ba.opts();
+        let mut releases = self
+            .scoped(
+                &opts,
+                self.with_fallback_repos(&opts, |api_url, repo| {
+                    self.list_remote_releases_for_repo(&opts, api_url, repo)
+                }),
+            )
+            .await?;
+        if let Some(days) = opts.get("max_version_age_days") {
+            let days: i64 = days
+                .parse()
+                .wrap_err_with(|| format!("invalid max_version_age_days: {days}"))?;
+            if days > 0 {
+                let now = jiff::Timestamp::now();
+                releases.retain(|r| published_within_days(r.published_at.as_deref(), days, now));
+            }
+        }
+        let version_map = version_map(&opts);
+        for release in &mut releases {
+            if let Some(version) = version_map.get(&release.version) {
+                release.version = version.clone();
+            }
+        }
+        let blocklist = version_blocklist(&opts);
+        releases.retain(|r| {
+            let blocked = blocklist.contains_key(&r.version);
+            if blocked {
+                trace!("release filtered out by version_blocklist: {}", r.version);
+            }
+            !blocked
+        });
+        Ok(releases)
     }
 
-    fn is_gitlab(&self) -> bool {
-        self.ba.backend_type() == BackendType::Gitlab
+    async fn list_remote_releases_for_repo(
+        &self,
+        opts: &ToolVersionOptions,
+        api_url: String,
+        repo: String,
+    ) -> Result<Vec<RemoteRelease>> {
+        if self.use_package_registry(opts)? {
+            let package_name = self.package_name(opts);
+            let packages =
+                gitlab::list_packages_from_url(api_url.as_str(), &repo, &package_name).await?;
+            Ok(packages
+                .into_iter()
+                .map(|p| RemoteRelease {
+                    version: p.version,
+                    published_at: None,
+                    asset_count: None,
+                    prerelease: None,
+                })
+                .collect())
+        } else {
+            // (created_at, release), the version is the tag name until the prefix is stripped
+            let mut releases: Vec<(String, RemoteRelease)> = if self.is_gitlab() {
+                gitlab::list_releases_from_url(api_url.as_str(), &repo)
+                    .await?
+                    .into_iter()
+                    .map(|r| {
+                        let release = RemoteRelease {
+                            version: r.tag_name,
+                            published_at: r.released_at,
+                            asset_count: Some(r.assets.links.len()),
+                            prerelease: Some(false),
+                        };
+                        (r.created_at, release)
+                    })
+                    .collect()
+            } else {
+                let releases = if include_prerelease(opts) {
+                    github::list_releases_with_prereleases_from_url(api_url.as_str(), &repo).await?
+                } else {
+                    github::list_releases_from_url(api_url.as_str(), &repo).await?
+                };
+                releases
+                    .into_iter()
+                    .map(|r| {
+                        let release = RemoteRelease {
+                            version: r.tag_name,
+                            published_at: r.published_at,
+                            asset_count: Some(r.assets.len()),
+                            prerelease: Some(r.prerelease),
+                        };
+                        (r.created_at, release)
+                    })
+                    .collect()
+            };
+            // the API returns the newest releases first
+            releases.reverse();
+            let transform = version_transform(opts)?;
+            let version_sort = opts.get("version_sort").map(|s| s.as_str());
+            match version_sort {
+                None => {}
+                Some("created_at") => releases.sort_by_cached_key(|(created_at, _)| {
+                    created_at.parse::<jiff::Timestamp>().ok()
+                }),
+                Some(other) => bail!("invalid version_sort {other:?}, expected \"created_at\""),
+            }
+            let mut releases = releases
+                .into_iter()
+                .map(|(_, release)| release)
+                .filter(|release| {
+                    let tag_name = &release.version;
+                    let keep = opts
+                        .get("version_prefix")
+                        .is_none_or(|p| strip_tag_prefix(p, tag_name).is_some());
+                    if !keep {
+                        trace!("release filtered out by version_prefix: tag={tag_name}");
+                    }
+                    keep
+                })
+                .map(|release| RemoteRelease {
+                    version: transform_version(
+                        transform.as_ref(),
+                        self.strip_version_prefix(&release.version),
+                    ),
+                    ..release
+                })
+                .collect::<Vec<_>>();
+            if include_prerelease(opts) && version_sort.is_none() {
+                sort_by_semver(&mut releases);
+            }
+            Ok(releases)
+        }
     }
 
-    fn repo(&self) -> String {
-        // Use tool_name() method to properly resolve aliases
-        // This ensures that when an alias like "test-edit = github:microsoft/edit" is used,
-        // the repository name is correctly extracted as "microsoft/edit"
-        self.ba.tool_name()
+    /// The primary repo followed by any `fallback_repos`, each paired with the API url to use
+    fn repo_candidates(&self, opts: &ToolVersionOptions) -> Vec<(String, String)> {
+        let api_url = self.get_api_url(opts);
+        let mut candidates = vec![(api_url.clone(), self.repo())];
+        for fallback in string_list_option(opts, "fallback_repos") {
+            let fallback = fallback
+                .split_once("://")
+                .map(|(_, rest)| rest)
+                .unwrap_or(&fallback)
+                .trim_matches('/');
+            let (first, rest) = fallback.split_once('/').unwrap_or((fallback, ""));
+            // "alt-host.com/owner/repo" or "owner/repo" on the primary host
+            let candidate = if first.contains('.') || first.contains(':') {
+                (
+                    api_url_for_host(&self.ba.backend_type(), first),
+                    rest.to_string(),
+                )
+            } else {
+                (api_url.clone(), fallback.to_string())
+            };
+            candidates.push(candidate);
+        }
+        candidates
     }
 
-    // Helper to format asset names for error messages
-    fn format_asset_list<'a, I>(assets: I) -> String
+    /// Runs `f` against the primary repo, then against each of `fallback_repos` in order
+    /// if the previous one was not found or could not be reached
+    async fn with_fallback_repos<T, F, Fut>(&self, opts: &ToolVersionOptions, f: F) -> Result<T>
     where
-        I: Iterator<Item = &'a String>,
+        F: Fn(String, String) -> Fut,
+        Fut: Future<Output = Result<T>>,
     {
-        assets.cloned().collect::<Vec<_>>().join(", ")
+        let mut candidates = self
+            .repo_candidates(opts)
+            .into_iter()
+            .enumerate()
+            .peekable();
+        loop {
+            let (i, (api_url, repo)) = candidates.next().unwrap();
+            match f(api_url.clone(), repo.clone()).await {
+                Ok(res) => {
+                    if i > 0 {
+                        info!("{}: using fallback repo {repo} ({api_url})", self.ba);
+                    } else {
+                        debug!("{}: using repo {repo} ({api_url})", self.ba);
+                    }
+                    return Ok(res);
+                }
+                Err(err)
+                    if candidates.peek().is_some()
+                        && (error_code(&err) == Some(404) || is_transient_error(&err)) =>
+                {
+                    warn!(
+                        "{}: failed to fetch from {repo}, trying next fallback repo: {err}",
+                        self.ba
+                    );
+                }
+                Err(err) if is_transient_error(&err) => return Err(err),
+                Err(err) => return Err(self.explain_repo_error(&api_url, &repo, err).await),
+            }
+        }
+    }
+
+    /// Adds why requests to `repo` failed when it was deleted or archived, as an archived
+    /// repo gets no new releases
+    async fn explain_repo_error(
+        &self,
+        api_url: &str,
+        repo: &str,
+        err: eyre::Report,
+    ) -> eyre::Report {
+        let archived = match self.is_gitlab() {
+            true => gitlab::get_project_for_url(api_url, repo)
+                .await
+                .map(|p| p.archived),
+            false => github::get_repo_for_url(api_url, repo)
+                .await
+                .map(|r| r.archived),
+        };
+        match archived {
+            Ok(true) => err.wrap_err(format!(
+                "repository archived: updates unavailable for {repo}"
+            )),
+            Ok(false) => err,
+            Err(repo_err) if error_code(&repo_err) == Some(404) => {
+                err.wrap_err(format!("repository not found: {repo} ({api_url})"))
+            }
+            Err(repo_err) => {
+                debug!("{}: failed to fetch repo info: {repo_err:#}", self.ba);
+                err
+            }
+        }
     }
 
     fn get_api_url(&self, opts: &ToolVersionOptions) -> String {
-        opts.get("api_url")
+        let mut api_url = opts
+            .get("api_url")
             .map(|s| s.as_str())
             .unwrap_or(if self.is_gitlab() {
                 DEFAULT_GITLAB_API_BASE_URL
+            } else if self.is_codeberg() {
+                DEFAULT_CODEBERG_API_BASE_URL
             } else {
                 DEFAULT_GITHUB_API_BASE_URL
             })
-            .to_string()
+            .to_string();
+        if opts.contains_key("api_version")
+            && let Some(api) = self.forgejo_api(opts)
+        {
+            api_url = api.api_url(&api_url);
+        }
+        api_url
+    }
+
+    /// The endpoint layout of the Forgejo API version set with `api_version`, v1 by default
+    fn forgejo_api(&self, opts: &ToolVersionOptions) -> Option<&'static dyn ForgejoApiClient> {
+        let version = opts.get("api_version");
+        if !self.is_codeberg() {
+            if version.is_some() {
+                warn_once!("{}: api_version is only supported by codeberg", self.ba);
+            }
+            return None;
+        }
+        let Some(version) = version else {
+            return Some(&forgejo::ForgejoApiV1);
+        };
+        let api = forgejo::api_client(version);
+        if api.is_none() {
+            warn_once!(
+                "{}: unknown api_version {version}, expected one of {}",
+                self.ba,
+                forgejo::API_VERSIONS.join(", ")
+            );
+        }
+        api
+    }
+
+    /// Options for the requests to the API host, e.g.: the certificate pinned by `tls_fingerprint`
+    /// or `user_agent`
+    fn host_options(&self, opts: &ToolVersionOptions) -> Option<http::HostOptions> {
+        let api_url = self.get_api_url(opts);
+        let host = url::Url::parse(&api_url).ok()?.host_str()?.to_string();
+        let rate_limit_pause =
+            opts.get("rate_limit_pause")
+                .and_then(|pause| match duration::parse_duration(pause) {
+                    Ok(pause) => Some(pause),
+                    Err(err) => {
+                        warn!("invalid rate_limit_pause {pause}: {err}");
+                        None
+                    }
+                });
+        Some(http::HostOptions {
+            host,
+            tls_fingerprint: opts.get("tls_fingerprint").cloned(),
+            rate_limit_pause,
+            user_agent: opts.get("user_agent").cloned(),
+        })
+    }
+
+    /// Runs `f` with the options of the API host and the tokens of this forge applied to its
+    /// requests
+    async fn scoped<T>(&self, opts: &ToolVersionOptions, f: impl Future<Output = T>) -> T {
+        let f = github::with_forge(self.ba.backend_type(), f);
+        match self.host_options(opts) {
+            Some(options) => http::with_host_options(options, f).await,
+            None => f.await,
+        }
+    }
+
+    /// Cheap authenticated endpoint used to check that the API is reachable
+    pub fn health_check_url(&self) -> String {
+        let opts = self.ba.opts();
+        let api_url = self.get_api_url(&opts);
+        if let Some(api) = self.forgejo_api(&opts) {
+            api.version_url(&api_url)
+        } else if self.is_gitlab() || self.is_codeberg() {
+            format!("{api_url}/version")
+        } else {
+            format!("{api_url}/rate_limit")
+        }
+    }
+
+    /// Makes a GET request to `health_check_url` and returns the HTTP status code
+    pub async fn health_check(&self) -> Result<u16> {
+        let resp = self.get_health_check_url().await?;
+        Ok(resp.status().as_u16())
+    }
+
+    /// Current API rate limit status, read from the response headers of `health_check_url`
+    pub async fn rate_limit(&self) -> Result<RateLimit> {
+        let resp = self.get_health_check_url().await?;
+        // github uses x-ratelimit-*, gitlab uses ratelimit-*
+        let header = |name: &str| -> Option<u64> {
+            let headers = resp.headers();
+            headers
+                .get(format!("x-ratelimit-{name}"))
+                .or_else(|| headers.get(format!("ratelimit-{name}")))
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+        };
+        Ok(RateLimit {
+            limit: header("limit"),
+            remaining: header("remaining"),
+            reset: header("reset"),
+        })
+    }
+
+    async fn get_health_check_url(&self) -> Result<reqwest::Response> {
+        let url = self.health_check_url();
+        let headers = self.forge_headers(&url);
+        self.scoped(
+            &self.ba.opts(),
+            crate::http::HTTP_FETCH.get_async_with_headers(url, &headers),
+        )
+        .await
+    }
+
+    pub fn api_url(&self) -> String {
+        self.get_api_url(&self.ba.opts())
+    }
+
+    /// Returns the most recent release version using the "latest release" endpoint so the
+    /// full release list doesn't need to be paginated. Falls back to the full list when the
+    /// latest release doesn't match the configured `version_prefix` or when
+    /// `max_version_age_days` filters the list, and to the cached list when offline or when
+    /// the latest release can't be fetched from any of `fallback_repos`.
+    pub async fn latest_release_version(&self, config: &Arc<Config>) -> Result<Option<String>> {
+        let opts = self.ba.opts();
+        if *env::OFFLINE
+            || self.use_package_registry(&opts)?
+            || opts.contains_key("max_version_age_days")
+        {
+            return Ok(self.list_remote_versions(config).await?.last().cloned());
+        }
+        let tag_name = self
+            .scoped(
+                &opts,
+                self.with_fallback_repos(&opts, |api_url, repo| async move {
+                    if self.is_gitlab() {
+                        gitlab::get_latest_release_for_url(&api_url, &repo)
+                            .await
+                            .map(|r| r.tag_name)
+                    } else {
+                        github::get_latest_release_for_url(&api_url, &repo)
+                            .await
+                            .map(|r| r.tag_name)
+                    }
+                }),
+            )
+            .await;
+        let tag_name = match tag_name {
+            Ok(tag_name) => tag_name,
+            Err(err) => {
+                debug!("{}: failed to fetch the latest release: {err:#}", self.ba);
+                return Ok(self.list_remote_versions(config).await?.last().cloned());
+            }
+        };
+        if opts
+            .get("version_prefix")
+            .is_some_and(|p| strip_tag_prefix(p, &tag_name).is_none())
+        {
+            return Ok(self.list_remote_versions(config).await?.last().cloned());
+        }
+        let version = transform_version(
+            version_transform(&opts)?.as_ref(),
+            self.strip_version_prefix(&tag_name),
+        );
+        let version = version_map(&opts).get(&version).cloned().unwrap_or(version);
+        if version_blocklist(&opts).contains_key(&version) {
+            return Ok(self.list_remote_versions(config).await?.last().cloned());
+        }
+        Ok(Some(version))
+    }
+
+    /// Link to the release page for `version`, e.g.: https://github.com/cli/cli/releases/tag/v2.62.0
+    /// Only returned when the release has no notes, otherwise the notes are the changelog.
+    /// The release is fetched to resolve the actual tag name (with or without a "v" prefix).
+    pub async fn changelog_url(&self, version: &str) -> Result<Option<String>> {
+        let opts = self.ba.opts();
+        self.scoped(&opts, self.changelog_url_for(&opts, version))
+            .await
+    }
+
+    async fn changelog_url_for(
+        &self,
+        opts: &ToolVersionOptions,
+        version: &str,
+    ) -> Result<Option<String>> {
+        let repo = self.repo();
+        let version = &release_version(opts, version);
+        let api_url = self.get_api_url(opts);
+        let version = &self
+            .untransformed_version(opts, &api_url, &repo, version)
+            .await?;
+        let version_prefix = self.tag_prefix(opts, &api_url, &repo, version).await?;
+        let version_prefix = version_prefix.as_deref();
+        let (tag, body) = if self.is_gitlab() {
+            try_with_v_prefix(version, version_prefix, |candidate| {
+                let (api_url, repo) = (&api_url, &repo);
+                async move {
+                    gitlab::get_release_for_url(api_url, repo, &candidate)
+                        .await
+                        .map(|r| (r.tag_name, r.description))
+                }
+            })
+            .await?
+        } else {
+            try_with_v_prefix(version, version_prefix, |candidate| {
+                let (api_url, repo) = (&api_url, &repo);
+                async move {
+                    github::get_release_for_url(api_url, repo, &candidate)
+                        .await
+                        .map(|r| (r.tag_name, r.body))
+                }
+            })
+            .await?
+        };
+        if body.is_some_and(|body| !body.trim().is_empty()) {
+            return Ok(None);
+        }
+        Ok(Some(self.release_page_url(&api_url, &repo, &tag)))
+    }
+
+    /// The `version_prefix` to build the tag for `version` with. A prefix with wildcards,
+    /// e.g.: `*-v`, is resolved to the actual prefix of the matching release tag.
+    async fn tag_prefix(
+        &self,
+        opts: &ToolVersionOptions,
+        api_url: &str,
+        repo: &str,
+        version: &str,
+    ) -> Result<Option<String>> {
+        let Some(prefix) = opts.get("version_prefix") else {
+            return Ok(None);
+        };
+        if !prefix.contains(['*', '?']) {
+            return Ok(Some(prefix.clone()));
+        }
+        let tags: Vec<String> = if self.is_gitlab() {
+            gitlab::list_releases_from_url(api_url, repo)
+                .await?
+                .into_iter()
+                .map(|r| r.tag_name)
+                .collect()
+        } else {
+            github::list_releases_from_url(api_url, repo)
+                .await?
+                .into_iter()
+                .map(|r| r.tag_name)
+                .collect()
+        };
+        let tag_prefix = tags.iter().find_map(|tag| {
+            (strip_tag_prefix(prefix, tag) == Some(version))
+                .then(|| tag[..tag.len() - version.len()].to_string())
+        });
+        if tag_prefix.is_none() {
+            debug!("no release tag matches version_prefix {prefix} for {version}");
+        }
+        Ok(tag_prefix)
+    }
+
+    /// Maps a version listed after `version_transform` back to the version of the release tag
+    async fn untransformed_version(
+        &self,
+        opts: &ToolVersionOptions,
+        api_url: &str,
+        repo: &str,
+        version: &str,
+    ) -> Result<String> {
+        let Some(transform) = version_transform(opts)? else {
+            return Ok(version.to_string());
+        };
+        let tags: Vec<String> = if self.is_gitlab() {
+            gitlab::list_releases_from_url(api_url, repo)
+                .await?
+                .into_iter()
+                .map(|r| r.tag_name)
+                .collect()
+        } else {
+            github::list_releases_from_url(api_url, repo)
+                .await?
+                .into_iter()
+                .map(|r| r.tag_name)
+                .collect()
+        };
+        let untransformed = tags
+            .iter()
+            .map(|tag| self.strip_version_prefix(tag))
+            .find(|v| transform_version(Some(&transform), v.clone()) == version);
+        if untransformed.is_none() {
+            debug!("no release tag matches version_transform for {version}");
+        }
+        Ok(untransformed.unwrap_or_else(|| version.to_string()))
+    }
+
+    /// The URL that would be downloaded to install `tv` on the current platform
+    pub async fn download_url(&self, tv: &ToolVersion) -> Result<String> {
+        let opts = tv.request.options();
+        let asset = self
+            .scoped(&opts, self.resolve_release_asset(tv, &opts))
+            .await?;
+        Ok(asset.url)
+    }
+
+    fn release_page_url(&self, api_url: &str, repo: &str, tag: &str) -> String {
+        let host = web_url(api_url);
+        if self.is_gitlab() {
+            format!("{host}/{repo}/-/releases/{tag}")
+        } else {
+            format!("{host}/{repo}/releases/tag/{tag}")
+        }
+    }
+
+    /// The auth headers of the forge for `url`, e.g.: the token for the API host
+    fn forge_headers(&self, url: &str) -> reqwest::header::HeaderMap {
+        if self.is_gitlab() {
+            gitlab::get_headers(url)
+        } else {
+            github::get_headers_for_forge(self.ba.backend_type(), url)
+        }
+    }
+
+    /// The headers to download an asset from `url` with. A `download_mirror` is a different
+    /// host than the forge, so it is never sent the forge's tokens or `.netrc` credentials.
+    fn download_headers(&self, url: &str, mirror: Option<&String>) -> reqwest::header::HeaderMap {
+        match mirror {
+            Some(_) => reqwest::header::HeaderMap::new(),
+            None => self.forge_headers(url),
+        }
+    }
+
+    /// Downloads and installs the asset
+    async fn download_and_install(
+        &self,
+        ctx: &InstallContext,
+        tv: &mut ToolVersion,
+        asset: &ReleaseAsset,
+        opts: &ToolVersionOptions,
+    ) -> Result<()> {
+        let filename = asset.name.clone();
+        let file_path = tv.download_path().join(&filename);
+
+        // Count operations dynamically:
+        // 1. Download (always)
+        // 2. Post-download hook (if post_download_hook option present)
+        // 3. Verify checksum (if checksum option present)
+        //    and release signature (if verify_release_signature option present)
+        // 4. Pre-extract script (if pre_extract_script option present)
+        // 5. Extract/install (if file needs extraction)
+        // 6. Post-install script (if post_install_script option present)
+        // 7. Verify binary (if verify_binary option present)
+        let mut op_count = 1; // download
+
+        let post_download_hook = opts.get("post_download_hook");
+        if post_download_hook.is_some() {
+            op_count += 1;
+        }
+        let pre_extract_script = opts.get("pre_extract_script");
+        if pre_extract_script.is_some() {
+            op_count += 1;
+        }
+        // the binaries of another platform can't be run here, see `mise install --platform`
+        let post_install_script = opts
+            .get("post_install_script")
+            .filter(|_| !ctx.foreign_platform);
+        if post_install_script.is_some() {
+            op_count += 1;
+        }
+        let verify_binary = opts.get("verify_binary").filter(|_| !ctx.foreign_platform);
+        if verify_binary.is_some() {
+            op_count += 1;
+        }
+        let verify_signature = opts
+            .get("verify_release_signature")
+            .is_some_and(|v| v == "true");
+        if verify_signature {
+            op_count += 1;
+        }
+
+        // Check if we'll verify checksum
+        let has_checksum = lookup_platform_key(opts, "checksum")
+            .or_else(|| opts.get("checksum").cloned())
+            .is_some();
+        if has_checksum {
+            op_count += 1;
+        }
+
+        // Check if we'll extract (archives need extraction)
+        let needs_extraction = filename.ends_with(".tar.gz")
+            || filename.ends_with(".tar.xz")
+            || filename.ends_with(".tar.bz2")
+            || filename.ends_with(".tar.zst")
+            || filename.ends_with(".tgz")
+            || filename.ends_with(".txz")
+            || filename.ends_with(".tbz2")
+            || filename.ends_with(".zip")
+            || filename.ends_with(".7z")
+            || filename.ends_with(".cab")
+            || filename.ends_with(".pkg");
+        if needs_extraction {
+            op_count += 1;
+        }
+
+        ctx.pr.start_operations(op_count);
+
+        let unsupported = asset_detector::unsupported_cpu_features(&asset.name);
+        if !unsupported.is_empty() && !ctx.foreign_platform {
+            warn!(
+                "{}: {} is built for {} which this CPU does not support, it may crash when run",
+                self.ba,
+                asset.name,
+                unsupported.join(", ")
+            );
+        }
+
+        // Store the asset URL and digest (if available) in the tool version
+        let platform_key = self.get_platform_key();
+        let platform_info = tv.lock_platforms.entry(platform_key).or_default();
+        // a local file has no url to lock, its checksum is still checked against the lockfile
+        if ctx.from_file.is_none() {
+            platform_info.name = Some(asset.name.clone());
+            platform_info.url = Some(asset.url.clone());
+            platform_info.url_api = Some(asset.url_api.clone());
+        }
+        if let Some(digest) = &asset.digest {
+            debug!("using GitHub API digest for checksum verification");
+            platform_info.checksum = Some(digest.clone());
+        }
+
+        let mirror = opts
+            .get("download_mirror")
+            .filter(|_| ctx.from_file.is_none());
+        let url = if let Some(mirror) = mirror {
+            // only the download goes through the mirror, the API is always queried directly
+            mirror_url(&asset.url, mirror)?
+        } else {
+            match ctx.from_file.is_none()
+                && (asset.url_api.starts_with(DEFAULT_GITHUB_API_BASE_URL)
+                    || asset.url_api.starts_with(DEFAULT_GITLAB_API_BASE_URL))
+            {
+                // check if url is reachable, 404 might indicate a private repo or asset.
+                // This is needed, because private repos and assets cannot be downloaded
+                // via browser url, therefore a fallback to api_url is needed in such cases.
+                true => match HTTP.head(asset.url.clone()).await {
+                    Ok(_) => asset.url.clone(),
+                    Err(_) => asset.url_api.clone(),
+                },
+
+                // Custom API URLs usually imply that a custom GitHub/GitLab instance is used.
+                // Often times such instances do not allow browser URL downloads, e.g. due to
+                // upstream company SSOs. Therefore, using the api_url for downloading is the safer approach.
+                false if ctx.from_file.is_some() => asset.url.clone(),
+                false => {
+                    debug!(
+                        "Since the tool resides on a custom GitHub/GitLab API ({:?}), the asset download will be performed using the given API instead of browser URL download",
+                        asset.url_api
+                    );
+                    asset.url_api.clone()
+                }
+            }
+        };
+
+        let headers = self.download_headers(&url, mirror);
+
+        ctx.pr.set_message(format!("download {filename}"));
+        let stream_format = self
+            .stream_extract_format(tv, opts, &filename)
+            .filter(|_| ctx.from_file.is_none());
+        if let Some(format) = stream_format {
+            self.stream_install(ctx, tv, asset, url, &headers, format, opts)
+                .await?;
+        } else {
+            let res = match &ctx.from_file {
+                Some(path) => {
+                    ctx.pr
+                        .set_message(format!("copy {}", file::display_path(path)));
+                    file::create_dir_all(tv.download_path())
+                        .and_then(|_| file::copy(path, &file_path))
+                }
+                None => {
+                    HTTP.download_file_with_headers(
+                        &url,
+                        &file_path,
+                        &headers,
+                        Some(ctx.pr.as_ref()),
+                    )
+                    .await
+                }
+            };
+            match res {
+                // the browser url may be served by a CDN that is unreachable while the API is not
+                Err(err)
+                    if !asset.url_api.is_empty()
+                        && url != asset.url_api
+                        && http::is_connection_error(&err) =>
+                {
+                    warn!(
+                        "failed to download {url}: {err:#}, retrying with {}",
+                        asset.url_api
+                    );
+                    let api_headers = self.forge_headers(&asset.url_api);
+                    HTTP.download_file_with_headers(
+                        &asset.url_api,
+                        &file_path,
+                        &api_headers,
+                        Some(ctx.pr.as_ref()),
+                    )
+                    .await?;
+                }
+                res => res?,
+            }
+
+            if let Some(hook) = post_download_hook {
+                self.run_post_download_hook(ctx, &file_path, &filename, hook)?;
+            }
+
+            // Verify and install
+            verify_artifact(tv, &file_path, opts, Some(ctx.pr.as_ref()))?;
+            if verify_signature {
+                self.verify_release_signature(ctx, tv, opts, asset, &file_path)
+                    .await?;
+            }
+            if let Some(script) = pre_extract_script {
+                // the lockfile checksum is of the asset as downloaded, not as modified
+                self.verify_checksum(ctx, tv, &file_path)?;
+                self.run_pre_extract_script(ctx, &file_path, &filename, script)?;
+            }
+            install_artifact(
+                tv,
+                &file_path,
+                opts,
+                asset.content_type.as_deref(),
+                Some(ctx.pr.as_ref()),
+            )?;
+        }
+        if let Some(content_path) = opts.get("content_path") {
+            self.apply_content_path(tv, content_path)?;
+        }
+        if let Some(template) = opts.get("bin_path_template") {
+            let Some(bin) = resolve_bin_path_template(template, tv, opts) else {
+                bail!(
+                    "bin_path_template {template} did not match any file in {filename} for this platform"
+                );
+            };
+            debug!("bin_path_template: using {}", bin.display());
+            file::make_executable(tv.install_path().join(bin))?;
+        }
+        if let Some(mode) = opts.get("bin_chmod")
+            && !ctx.foreign_platform
+        {
+            self.chmod_bin_paths(tv, opts, mode)?;
+        }
+        if stream_format.is_none() && pre_extract_script.is_none() {
+            self.verify_checksum(ctx, tv, &file_path)?;
+        }
+        if let Some(script) = post_install_script {
+            self.run_post_install_script(ctx, tv, script)?;
+        }
+        if let Some(command) = verify_binary {
+            self.run_verify_binary(ctx, tv, opts, command)?;
+        }
+
+        Ok(())
+    }
+
+    /// The tarball format to extract while downloading when `stream_extract` is enabled.
+    /// Returns None if the asset isn't a compressed tarball or if it has to be on disk to be
+    /// verified (checksum, size or a digest from the API/lockfile) or passed to a hook.
+    fn stream_extract_format(
+        &self,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+        filename: &str,
+    ) -> Option<file::TarFormat> {
+        if opts.get("stream_extract").is_none_or(|v| v != "true") {
+            return None;
+        }
+        let ext = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        let is_tarball = match ext {
+            "gz" | "xz" | "bz2" | "zst" => filename.contains(".tar."),
+            "tgz" | "txz" | "tbz2" | "tzst" => true,
+            _ => false,
+        };
+        if !is_tarball {
+            debug!("stream_extract: {filename} is not a compressed tarball, downloading it first");
+            return None;
+        }
+        let platform_info = tv.lock_platforms.get(&self.get_platform_key());
+        let needs_file = [
+            "checksum",
+            "size",
+            "post_download_hook",
+            "pre_extract_script",
+            "verify_release_signature",
+        ]
+        .into_iter()
+        .find(|key| lookup_platform_key(opts, key).is_some() || opts.contains_key(key))
+        .or_else(|| {
+            // sha256 checksums, e.g.: from the API, are checked as the asset streams
+            platform_info
+                .and_then(|p| p.checksum.as_ref())
+                .filter(|checksum| !checksum.starts_with("sha256:"))
+                .map(|_| "lockfile checksum")
+        });
+        if let Some(reason) = needs_file {
+            debug!(
+                "stream_extract: {filename} needs to be on disk for {reason}, downloading it first"
+            );
+            return None;
+        }
+        Some(file::TarFormat::from_ext(ext))
+    }
+
+    /// Extracts the asset while it downloads, without writing the archive to disk. It is
+    /// extracted to a temporary directory, which is moved to the install path once the sha256 of
+    /// the download has been checked, see `verify_streamed_checksum`.
+    #[allow(clippy::too_many_arguments)]
+    async fn stream_install(
+        &self,
+        ctx: &InstallContext,
+        tv: &mut ToolVersion,
+        asset: &ReleaseAsset,
+        url: String,
+        headers: &reqwest::header::HeaderMap,
+        format: file::TarFormat,
+        opts: &ToolVersionOptions,
+    ) -> Result<()> {
+        let strip_components = opts.get("strip_components").and_then(|s| s.parse().ok());
+        // like install_artifact, strip a single top-level directory unless told otherwise
+        let auto_strip = strip_components.is_none()
+            && lookup_platform_key(opts, "bin_path")
+                .or_else(|| opts.get("bin_path").cloned())
+                .is_none()
+            && !opts.contains_key("bin_path_template");
+        let exclude = extract_filter(opts)?;
+        file::create_dir_all(tv.download_path())?;
+        let tmp = tempfile::tempdir_in(tv.download_path())?;
+
+        let extract = |url: String, headers: reqwest::header::HeaderMap| {
+            let dest = tmp.path().to_path_buf();
+            // with auto_strip the paths are only known after extracting, see move_extracted
+            let exclude = exclude.clone().filter(|_| !auto_strip);
+            async move {
+                file::remove_all(&dest)?;
+                HTTP.stream_with_headers(url, &headers, Some(ctx.pr.as_ref()), move |reader| {
+                    let tar_opts = file::TarOptions {
+                        format,
+                        strip_components: strip_components.unwrap_or(0),
+                        exclude: exclude.as_ref(),
+                        ..Default::default()
+                    };
+                    let mut reader = hash::HashingReader::new(reader);
+                    file::untar_reader(&mut reader, &dest, &tar_opts)?;
+                    reader.finish()
+                })
+                .await
+            }
+        };
+        let (sha256, size) = match extract(url.clone(), headers.clone()).await {
+            // the browser url may be served by a CDN that is unreachable while the API is not
+            Err(err)
+                if !asset.url_api.is_empty()
+                    && url != asset.url_api
+                    && http::is_connection_error(&err) =>
+            {
+                warn!(
+                    "failed to download {url}: {err:#}, retrying with {}",
+                    asset.url_api
+                );
+                let api_headers = self.forge_headers(&asset.url_api);
+                extract(asset.url_api.clone(), api_headers).await
+            }
+            res => res,
+        }
+        .wrap_err("stream_extract failed")?;
+        self.verify_streamed_checksum(tv, &asset.name, &sha256, size)?;
+
+        let root = match auto_strip {
+            true => single_subdir(tmp.path())?,
+            false => None,
+        };
+        if root.is_some() {
+            debug!("Auto-detected single directory archive, extracting with strip_components=1");
+        }
+        let install_path = tv.install_path();
+        file::remove_all(&install_path)?;
+        let root = root.as_deref().unwrap_or(tmp.path());
+        move_extracted(root, &install_path, exclude.as_ref().filter(|_| auto_strip))
+    }
+
+    /// Like `verify_checksum` for an asset that was extracted while it downloaded, with the
+    /// sha256 and size of the download. Only sha256 checksums can be checked this way, see
+    /// `stream_extract_format`.
+    fn verify_streamed_checksum(
+        &self,
+        tv: &mut ToolVersion,
+        filename: &str,
+        sha256: &str,
+        size: u64,
+    ) -> Result<()> {
+        if env::SKIP_CHECKSUM.load(Ordering::Relaxed) {
+            debug!("skipping checksum verification of {filename}");
+            return Ok(());
+        }
+        let settings = Settings::get();
+        let lockfile_enabled = settings.lockfile && settings.experimental;
+        let platform_info = tv
+            .lock_platforms
+            .entry(self.get_platform_key())
+            .or_default();
+        match &platform_info.checksum {
+            Some(checksum) => {
+                let expected = checksum.strip_prefix("sha256:").unwrap_or(checksum);
+                if !expected.eq_ignore_ascii_case(sha256) {
+                    bail!(
+                        "Checksum mismatch for {filename}:\nExpected: {checksum}\nActual:   sha256:{sha256}"
+                    );
+                }
+            }
+            None if lockfile_enabled => platform_info.checksum = Some(format!("sha256:{sha256}")),
+            None => {}
+        }
+        match platform_info.size {
+            Some(expected) if expected != size => {
+                bail!("Size mismatch for {filename}: expected {expected}, got {size}");
+            }
+            None if lockfile_enabled => platform_info.size = Some(size),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Looks up the checksum of `asset` in the checksum file at `checksum_url` (or its alias
+    /// `asset_hash_url`), see `checksum_format`. The forge's auth headers are only sent if the
+    /// file is on the forge, e.g.: a release asset of a private repo
+    async fn checksum_from_url(
+        &self,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+        asset: &ReleaseAsset,
+    ) -> Result<Option<String>> {
+        let Some(url) = ["checksum_url", "asset_hash_url"]
+            .into_iter()
+            .find_map(|key| lookup_platform_key(opts, key).or_else(|| opts.get(key).cloned()))
+        else {
+            return Ok(None);
+        };
+        let settings = Settings::get();
+        let url = template_string(&url, tv)
+            .replace("{os}", settings.os())
+            .replace("{arch}", settings.arch());
+        let api_url = asset
+            .api_url
+            .clone()
+            .unwrap_or_else(|| self.get_api_url(opts));
+        let text = if is_forge_url(&api_url, &url) {
+            let headers = self.forge_headers(&url);
+            HTTP.get_async_with_headers(&url, &headers)
+                .await?
+                .text()
+                .await?
+        } else {
+            HTTP.get_text(&url).await?
+        };
+        let format = opts.get("checksum_format").map(|f| f.as_str());
+        match checksum_from_file(&text, &asset.name, format)? {
+            Some(checksum) => Ok(Some(checksum)),
+            None => bail!("{} not found in checksum file {url}", asset.name),
+        }
+    }
+
+    /// Verifies the detached GPG signature of the asset published in the same release
+    /// against the key at `signing_key_url`, which defaults to the GPG keys of the
+    /// owner of the repo, e.g.: https://github.com/<owner>.gpg
+    async fn verify_release_signature(
+        &self,
+        ctx: &InstallContext,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+        asset: &ReleaseAsset,
+        file_path: &Path,
+    ) -> Result<()> {
+        ctx.pr.set_message("verify release signature".to_string());
+        let Some(signature_url) = &asset.signature_url else {
+            bail!(
+                "verify_release_signature: no {name}.asc or {name}.sig found in the release",
+                name = asset.name
+            );
+        };
+        let key_url = match opts.get("signing_key_url") {
+            Some(url) => url.clone(),
+            None => {
+                let api_url = asset
+                    .api_url
+                    .clone()
+                    .unwrap_or_else(|| self.get_api_url(opts));
+                let repo = asset.repo.as_deref().unwrap_or(&self.ba.tool_name);
+                let owner = repo.split('/').next().unwrap_or(repo);
+                format!("{}/{owner}.gpg", web_url(&api_url))
+            }
+        };
+        let keys = HTTP.get_text(&key_url).await?;
+        if keys.trim().is_empty() {
+            bail!("verify_release_signature: no keys found at {key_url}");
+        }
+        let signature_path = tv
+            .download_path()
+            .join(get_filename_from_url(signature_url));
+        let headers = self.forge_headers(signature_url);
+        HTTP.download_file_with_headers(signature_url, &signature_path, &headers, None)
+            .await?;
+        gpg::verify_detached(ctx, &keys, &signature_path, file_path)
+            .wrap_err_with(|| format!("release signature of {} is invalid", asset.name))
+    }
+
+    /// Records where the asset of `tv` came from, see `mise install --record-provenance`
+    fn write_provenance(&self, tv: &ToolVersion, asset: &ReleaseAsset) -> Result<()> {
+        let file_path = tv.download_path().join(&asset.name);
+        let sha256 = match file_path.is_file() {
+            true => Some(hash::file_hash_sha256(&file_path, None)?),
+            // extracted while downloading, its sha256 is only kept if it was checked or locked
+            false => tv
+                .lock_platforms
+                .get(&self.get_platform_key())
+                .and_then(|p| p.checksum.as_deref())
+                .and_then(|checksum| checksum.strip_prefix("sha256:"))
+                .map(str::to_string),
+        };
+        let provenance = Provenance {
+            tool: self.ba.short.clone(),
+            version: tv.version.clone(),
+            url: asset.url.clone(),
+            sha256,
+            tag: asset.tag.clone(),
+            published_at: asset.published_at.clone(),
+            api_url: asset.api_url.clone(),
+        };
+        let path = tv.install_path().join(".mise-provenance.json");
+        file::write(&path, serde_json::to_string_pretty(&provenance)?)?;
+        Ok(())
+    }
+
+    /// Writes the release notes to `.release-notes.md` in the install path, see
+    /// `include_release_notes`
+    fn write_release_notes(&self, tv: &ToolVersion, asset: &ReleaseAsset) -> Result<()> {
+        match asset.release_notes.as_deref().map(str::trim) {
+            Some(notes) if !notes.is_empty() => {
+                file::write(tv.install_path().join(".release-notes.md"), notes)?;
+            }
+            _ => debug!("{}: no release notes for {}", self.ba, tv.version),
+        }
+        Ok(())
+    }
+
+    /// Runs the user-provided post_download_hook against the downloaded asset.
+    /// A non-zero exit aborts the install before the asset is verified or extracted.
+    fn run_post_download_hook(
+        &self,
+        ctx: &InstallContext,
+        file_path: &Path,
+        filename: &str,
+        hook: &str,
+    ) -> Result<()> {
+        ctx.pr.set_message("post_download_hook".to_string());
+        CmdLineRunner::new(&*env::SHELL)
+            .env("MISE_ASSET_PATH", file_path)
+            .env("MISE_ASSET_NAME", filename)
+            .with_pr(ctx.pr.as_ref())
+            .arg(env::SHELL_COMMAND_FLAG)
+            .arg(hook)
+            .execute()
+            .wrap_err_with(|| format!("post_download_hook failed for {filename}"))
+    }
+
+    /// Runs the user-provided pre_extract_script, which may modify the downloaded archive
+    /// in place after it has been verified. A non-zero exit aborts the install.
+    fn run_pre_extract_script(
+        &self,
+        ctx: &InstallContext,
+        file_path: &Path,
+        filename: &str,
+        script: &str,
+    ) -> Result<()> {
+        ctx.pr.set_message("pre_extract_script".to_string());
+        CmdLineRunner::new(&*env::SHELL)
+            .env("MISE_ARCHIVE_PATH", file_path)
+            .with_pr(ctx.pr.as_ref())
+            .arg(env::SHELL_COMMAND_FLAG)
+            .arg(script)
+            .execute()
+            .wrap_err_with(|| format!("pre_extract_script failed for {filename}"))
+    }
+
+    /// Runs the user-provided post_install_script in the install path after the asset
+    /// is extracted. A non-zero exit removes the install path and aborts the install.
+    fn run_post_install_script(
+        &self,
+        ctx: &InstallContext,
+        tv: &ToolVersion,
+        script: &str,
+    ) -> Result<()> {
+        ctx.pr.set_message("post_install_script".to_string());
+        let install_path = tv.install_path();
+        let result = CmdLineRunner::new(&*env::SHELL)
+            .current_dir(&install_path)
+            .env("MISE_INSTALL_PATH", &install_path)
+            .env("MISE_VERSION", &tv.version)
+            .env("MISE_TOOL_NAME", &self.ba.tool_name)
+            .with_pr(ctx.pr.as_ref())
+            .arg(env::SHELL_COMMAND_FLAG)
+            .arg(script)
+            .execute();
+        if let Err(err) = result {
+            file::remove_all(&install_path)?;
+            return Err(err).wrap_err_with(|| format!("post_install_script failed for {tv}"));
+        }
+        Ok(())
     }
 
-    /// Downloads and installs the asset
-    async fn download_and_install(
+    /// Runs the user-provided verify_binary command in the install path and removes the install
+    /// if it exits non-zero or its output doesn't match `verify_binary_output_pattern`
+    fn run_verify_binary(
         &self,
         ctx: &InstallContext,
-        tv: &mut ToolVersion,
-        asset: &ReleaseAsset,
+        tv: &ToolVersion,
         opts: &ToolVersionOptions,
+        command: &str,
     ) -> Result<()> {
-        let filename = asset.name.clone();
-        let file_path = tv.download_path().join(&filename);
-
-        // Count operations dynamically:
-        // 1. Download (always)
-        // 2. Verify checksum (if checksum option present)
-        // 3. Extract/install (if file needs extraction)
-        let mut op_count = 1; // download
-
-        // Check if we'll verify checksum
-        let has_checksum = lookup_platform_key(opts, "checksum")
-            .or_else(|| opts.get("checksum").cloned())
-            .is_some();
-        if has_checksum {
-            op_count += 1;
-        }
-
-        // Check if we'll extract (archives need extraction)
-        let needs_extraction = filename.ends_with(".tar.gz")
-            || filename.ends_with(".tar.xz")
-            || filename.ends_with(".tar.bz2")
-            || filename.ends_with(".tar.zst")
-            || filename.ends_with(".tgz")
-            || filename.ends_with(".txz")
-            || filename.ends_with(".tbz2")
-            || filename.ends_with(".zip");
-        if needs_extraction {
-            op_count += 1;
+        ctx.pr.set_message("verify_binary".to_string());
+        let pattern = opts
+            .get("verify_binary_output_pattern")
+            .map(|p| {
+                Regex::new(p).wrap_err_with(|| format!("invalid verify_binary_output_pattern {p}"))
+            })
+            .transpose()?;
+        let install_path = tv.install_path();
+        let bin = self.verify_binary_path(tv, opts)?;
+        let command = template_string(command, tv)
+            .replace("{bin_path}", &shell_words::quote(&bin.to_string_lossy()));
+        let output = cmd(
+            env::SHELL.as_str(),
+            [env::SHELL_COMMAND_FLAG, command.as_str()],
+        )
+        .dir(&install_path)
+        .stderr_to_stdout()
+        .stdout_capture()
+        .unchecked()
+        .run()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let err = if !output.status.success() {
+            Some(eyre!("{command} exited with {}\n{stdout}", output.status))
+        } else {
+            pattern
+                .filter(|re| !re.is_match(&stdout))
+                .map(|pattern| eyre!("output of {command} does not match {pattern}\n{stdout}"))
+        };
+        if let Some(err) = err {
+            file::remove_all(&install_path)?;
+            return Err(err).wrap_err_with(|| format!("verify_binary failed for {tv}"));
         }
+        Ok(())
+    }
 
-        ctx.pr.start_operations(op_count);
-
-        // Store the asset URL and digest (if available) in the tool version
-        let platform_key = self.get_platform_key();
-        let platform_info = tv.lock_platforms.entry(platform_key).or_default();
-        platform_info.name = Some(asset.name.clone());
-        platform_info.url = Some(asset.url.clone());
-        platform_info.url_api = Some(asset.url_api.clone());
-        if let Some(digest) = &asset.digest {
-            debug!("using GitHub API digest for checksum verification");
-            platform_info.checksum = Some(digest.clone());
+    /// The executable `{bin_path}` expands to in `verify_binary`: the `bin_path_template` match,
+    /// or `install_file`, `bin` or the repo name in the bin paths
+    fn verify_binary_path(&self, tv: &ToolVersion, opts: &ToolVersionOptions) -> Result<PathBuf> {
+        if let Some(template) = opts.get("bin_path_template")
+            && let Some(bin) = resolve_bin_path_template(template, tv, opts)
+        {
+            return Ok(tv.install_path().join(bin));
         }
+        let name = opts
+            .get("install_file")
+            .or_else(|| opts.get("bin"))
+            .cloned()
+            .unwrap_or_else(|| {
+                let tool_name = self.ba.tool_name.as_str();
+                tool_name
+                    .rsplit('/')
+                    .next()
+                    .unwrap_or(tool_name)
+                    .to_string()
+            });
+        let bin_paths = self.bin_paths(tv, opts)?;
+        let bin = bin_paths
+            .iter()
+            .flat_map(|dir| [dir.join(&name), dir.join(format!("{name}.exe"))])
+            .find(|p| p.is_file())
+            .or_else(|| bin_paths.first().map(|dir| dir.join(&name)))
+            .unwrap_or_else(|| tv.install_path().join(&name));
+        Ok(bin)
+    }
 
-        let url = match asset.url_api.starts_with(DEFAULT_GITHUB_API_BASE_URL)
-            || asset.url_api.starts_with(DEFAULT_GITLAB_API_BASE_URL)
+    fn bin_paths(&self, tv: &ToolVersion, opts: &ToolVersionOptions) -> Result<Vec<PathBuf>> {
+        let mut paths = if let Some(template) = opts.get("bin_path_template") {
+            let Some(bin) = resolve_bin_path_template(template, tv, opts) else {
+                bail!("bin_path_template {template} did not match any file for this platform");
+            };
+            let bin_dir = bin.parent().map(|p| p.to_path_buf()).unwrap_or_default();
+            vec![tv.install_path().join(bin_dir)]
+        } else if let Some(bin_path_template) =
+            lookup_platform_key(opts, "bin_path").or_else(|| opts.get("bin_path").cloned())
         {
-            // check if url is reachable, 404 might indicate a private repo or asset.
-            // This is needed, because private repos and assets cannot be downloaded
-            // via browser url, therefore a fallback to api_url is needed in such cases.
-            true => match HTTP.head(asset.url.clone()).await {
-                Ok(_) => asset.url.clone(),
-                Err(_) => asset.url_api.clone(),
-            },
-
-            // Custom API URLs usually imply that a custom GitHub/GitLab instance is used.
-            // Often times such instances do not allow browser URL downloads, e.g. due to
-            // upstream company SSOs. Therefore, using the api_url for downloading is the safer approach.
-            false => {
-                debug!(
-                    "Since the tool resides on a custom GitHub/GitLab API ({:?}), the asset download will be performed using the given API instead of browser URL download",
-                    asset.url_api
-                );
-                asset.url_api.clone()
-            }
-        };
-
-        let headers = if self.is_gitlab() {
-            gitlab::get_headers(&url)
+            let bin_path = template_string(&bin_path_template, tv);
+            vec![tv.install_path().join(&bin_path)]
         } else {
-            github::get_headers(&url)
+            match opts.get("install_layout").map(|s| s.as_str()) {
+                None => self.discover_bin_paths(tv)?,
+                // the binaries and their support files are all at the root of the install path
+                Some("flat") => vec![tv.install_path()],
+                Some(other) => bail!("invalid install_layout {other:?}, expected \"flat\""),
+            }
         };
+        // added to the paths above rather than replacing them like bin_path
+        for extra in string_list_option(opts, "extra_bin_paths") {
+            let path = tv.install_path().join(template_string(&extra, tv));
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        Ok(paths)
+    }
 
-        ctx.pr.set_message(format!("download {filename}"));
-        HTTP.download_file_with_headers(url, &file_path, &headers, Some(ctx.pr.as_ref()))
-            .await?;
-
-        // Verify and install
-        verify_artifact(tv, &file_path, opts, Some(ctx.pr.as_ref()))?;
-        install_artifact(tv, &file_path, opts, Some(ctx.pr.as_ref()))?;
-        self.verify_checksum(ctx, tv, &file_path)?;
+    /// Makes `content_path` within the extracted asset the root of the install path.
+    /// Everything outside of it is deleted.
+    fn apply_content_path(&self, tv: &ToolVersion, content_path: &str) -> Result<()> {
+        let content_path = template_string(content_path, tv);
+        let rel = Path::new(content_path.trim_matches('/'));
+        if rel
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            bail!("invalid content_path {content_path:?}, expected a relative path");
+        }
+        let install_path = tv.install_path();
+        let src = install_path.join(rel);
+        if !src.is_dir() {
+            bail!(
+                "content_path {content_path} not found in {}",
+                file::display_path(&install_path)
+            );
+        }
+        let tmp = install_path.with_file_name(format!(
+            ".{}.content_path",
+            install_path.file_name().unwrap().to_string_lossy()
+        ));
+        file::remove_all(&tmp)?;
+        file::rename(&src, &tmp)?;
+        file::remove_all(&install_path)?;
+        file::rename(&tmp, &install_path)?;
+        Ok(())
+    }
 
+    /// Sets `mode` on every file in the bin paths. Some filesystems (exFAT, NTFS via WSL)
+    /// drop the executable bit stored in the archive.
+    fn chmod_bin_paths(
+        &self,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+        mode: &str,
+    ) -> Result<()> {
+        let mode = u32::from_str_radix(mode, 8).map_err(|_| {
+            eyre!("invalid bin_chmod {mode:?}, expected an octal mode like \"755\"")
+        })?;
+        for bin_path in self.bin_paths(tv, opts)? {
+            for path in file::ls(&bin_path)? {
+                if path.is_file() {
+                    file::chmod(&path, mode)?;
+                }
+            }
+        }
         Ok(())
     }
 
@@ -297,11 +1656,23 @@ impl UnifiedGitBackend {
                 url: direct_url.clone(),
                 url_api: direct_url.clone(),
                 digest: None, // Direct URLs don't have API digest
+                ..Default::default()
             });
         }
 
-        let version = &tv.version;
-        let version_prefix = opts.get("version_prefix").map(|s| s.as_str());
+        if self.use_package_registry(opts)? {
+            return self
+                .resolve_package_asset_url(tv, opts, repo, api_url)
+                .await;
+        }
+        self.asset_content_type_filter(opts)?;
+
+        let version = &release_version(opts, &tv.version);
+        let version = &self
+            .untransformed_version(opts, api_url, repo, version)
+            .await?;
+        let version_prefix = self.tag_prefix(opts, api_url, repo, version).await?;
+        let version_prefix = version_prefix.as_deref();
         if self.is_gitlab() {
             try_with_v_prefix(version, version_prefix, |candidate| async move {
                 self.resolve_gitlab_asset_url(tv, opts, repo, api_url, &candidate)
@@ -325,22 +1696,27 @@ impl UnifiedGitBackend {
         api_url: &str,
         version: &str,
     ) -> Result<ReleaseAsset> {
-        let release = github::get_release_for_url(api_url, repo, version).await?;
+        let release =
+            with_release_retries(|| github::get_release_for_url(api_url, repo, version)).await?;
 
         let available_assets: Vec<String> = release.assets.iter().map(|a| a.name.clone()).collect();
+        trace_release_assets(repo, &release.tag_name, &available_assets);
 
         // Try explicit pattern first, then fall back to auto-detection
-        if let Some(pattern) = lookup_platform_key(opts, "asset_pattern")
-            .or_else(|| opts.get("asset_pattern").cloned())
-        {
+        // the version of the release itself, which differs from tv.version with version_map
+        let upstream_version = release_version(opts, &tv.version);
+        if let Some(pattern) = asset_pattern(opts, &upstream_version)? {
             // Template the pattern with actual values
-            let templated_pattern = template_string(&pattern, tv);
+            let templated_pattern =
+                template_string_with_tag(&pattern, &upstream_version, &release.tag_name);
+            trace!("asset resolution: matching asset_pattern={templated_pattern}");
 
             // Find matching asset using pattern
             let asset = release
                 .assets
-                .into_iter()
+                .iter()
                 .find(|a| self.matches_pattern(&a.name, &templated_pattern))
+                .cloned()
                 .ok_or_else(|| {
                     eyre::eyre!(
                         "No matching asset found for pattern: {}\nAvailable assets: {}",
@@ -349,16 +1725,47 @@ impl UnifiedGitBackend {
                     )
                 })?;
 
+            let signature_url = find_signature(&release.assets, &asset.name, |a| &a.name)
+                .map(|a| a.browser_download_url.clone());
             return Ok(ReleaseAsset {
+                url_api: asset.api_url().to_string(),
                 name: asset.name,
                 url: asset.browser_download_url,
-                url_api: asset.url,
                 digest: asset.digest,
+                tag: Some(release.tag_name),
+                published_at: release.published_at,
+                api_url: None,
+                release_notes: release.body,
+                repo: None,
+                signature_url,
+                content_type: asset.content_type,
             });
         }
 
         // Fall back to auto-detection
-        let asset_name = self.auto_detect_asset(&available_assets)?;
+        let detect_assets = match self.asset_content_type_filter(opts)? {
+            Some(content_type) => {
+                let assets: Vec<String> = release
+                    .assets
+                    .iter()
+                    .filter(|a| {
+                        a.content_type
+                            .as_ref()
+                            .is_some_and(|ct| ct.eq_ignore_ascii_case(content_type))
+                    })
+                    .map(|a| a.name.clone())
+                    .collect();
+                if assets.is_empty() {
+                    bail!(
+                        "No assets with content type {content_type}\nAvailable assets: {}",
+                        Self::format_asset_list(available_assets.iter())
+                    );
+                }
+                assets
+            }
+            None => available_assets.clone(),
+        };
+        let asset_name = self.auto_detect_asset(opts, &detect_assets)?;
         let asset = self
             .find_asset_case_insensitive(&release.assets, &asset_name, |a| &a.name)
             .ok_or_else(|| {
@@ -372,8 +1779,16 @@ impl UnifiedGitBackend {
         Ok(ReleaseAsset {
             name: asset.name.clone(),
             url: asset.browser_download_url.clone(),
-            url_api: asset.url.clone(),
+            url_api: asset.api_url().to_string(),
             digest: asset.digest.clone(),
+            tag: Some(release.tag_name.clone()),
+            published_at: release.published_at.clone(),
+            api_url: None,
+            release_notes: release.body.clone(),
+            repo: None,
+            signature_url: find_signature(&release.assets, &asset.name, |a| &a.name)
+                .map(|a| a.browser_download_url.clone()),
+            content_type: asset.content_type.clone(),
         })
     }
 
@@ -385,7 +1800,8 @@ impl UnifiedGitBackend {
         api_url: &str,
         version: &str,
     ) -> Result<ReleaseAsset> {
-        let release = gitlab::get_release_for_url(api_url, repo, version).await?;
+        let release =
+            with_release_retries(|| gitlab::get_release_for_url(api_url, repo, version)).await?;
 
         let available_assets: Vec<String> = release
             .assets
@@ -393,20 +1809,24 @@ impl UnifiedGitBackend {
             .iter()
             .map(|a| a.name.clone())
             .collect();
+        trace_release_assets(repo, &release.tag_name, &available_assets);
 
         // Try explicit pattern first, then fall back to auto-detection
-        if let Some(pattern) = lookup_platform_key(opts, "asset_pattern")
-            .or_else(|| opts.get("asset_pattern").cloned())
-        {
+        // the version of the release itself, which differs from tv.version with version_map
+        let upstream_version = release_version(opts, &tv.version);
+        if let Some(pattern) = asset_pattern(opts, &upstream_version)? {
             // Template the pattern with actual values
-            let templated_pattern = template_string(&pattern, tv);
+            let templated_pattern =
+                template_string_with_tag(&pattern, &upstream_version, &release.tag_name);
+            trace!("asset resolution: matching asset_pattern={templated_pattern}");
 
             // Find matching asset using pattern
             let asset = release
                 .assets
                 .links
-                .into_iter()
+                .iter()
                 .find(|a| self.matches_pattern(&a.name, &templated_pattern))
+                .cloned()
                 .ok_or_else(|| {
                     eyre::eyre!(
                         "No matching asset found for pattern: {}\nAvailable assets: {}",
@@ -415,16 +1835,25 @@ impl UnifiedGitBackend {
                     )
                 })?;
 
+            let signature_url = find_signature(&release.assets.links, &asset.name, |a| &a.name)
+                .map(|a| a.direct_asset_url.clone());
             return Ok(ReleaseAsset {
                 name: asset.name,
                 url: asset.url,
                 url_api: asset.direct_asset_url,
                 digest: None, // GitLab doesn't provide digests yet
+                tag: Some(release.tag_name),
+                published_at: release.released_at,
+                api_url: None,
+                release_notes: release.description,
+                repo: None,
+                signature_url,
+                content_type: None, // GitLab doesn't report the content type of links
             });
         }
 
         // Fall back to auto-detection
-        let asset_name = self.auto_detect_asset(&available_assets)?;
+        let asset_name = self.auto_detect_asset(opts, &available_assets)?;
         let asset = self
             .find_asset_case_insensitive(&release.assets.links, &asset_name, |a| &a.name)
             .ok_or_else(|| {
@@ -440,15 +1869,123 @@ impl UnifiedGitBackend {
             url: asset.direct_asset_url.clone(),
             url_api: asset.url.clone(),
             digest: None, // GitLab doesn't provide digests yet
+            tag: Some(release.tag_name.clone()),
+            published_at: release.released_at.clone(),
+            api_url: None,
+            release_notes: release.description.clone(),
+            repo: None,
+            signature_url: find_signature(&release.assets.links, &asset.name, |a| &a.name)
+                .map(|a| a.direct_asset_url.clone()),
+            content_type: None, // GitLab doesn't report the content type of links
+        })
+    }
+
+    /// Whether assets come from the generic package registry rather than releases
+    fn use_package_registry(&self, opts: &ToolVersionOptions) -> Result<bool> {
+        if opts.get("use_package_registry").is_none_or(|v| v != "true") {
+            return Ok(false);
+        }
+        if !self.is_gitlab() {
+            bail!("use_package_registry is only supported by the gitlab backend");
+        }
+        Ok(true)
+    }
+
+    /// MIME type that autodetected assets must have, gitlab and Forgejo assets don't report one
+    fn asset_content_type_filter<'a>(
+        &self,
+        opts: &'a ToolVersionOptions,
+    ) -> Result<Option<&'a String>> {
+        let Some(content_type) = opts.get("asset_content_type_filter") else {
+            return Ok(None);
+        };
+        if self.is_gitlab() || self.is_codeberg() {
+            bail!("asset_content_type_filter is only supported by the github backend");
+        }
+        Ok(Some(content_type))
+    }
+
+    /// Package name in the registry, defaults to the project name
+    fn package_name(&self, opts: &ToolVersionOptions) -> String {
+        opts.get("package_name").cloned().unwrap_or_else(|| {
+            let repo = self.repo();
+            repo.rsplit('/').next().unwrap_or(&repo).to_string()
+        })
+    }
+
+    async fn resolve_package_asset_url(
+        &self,
+        tv: &ToolVersion,
+        opts: &ToolVersionOptions,
+        repo: &str,
+        api_url: &str,
+    ) -> Result<ReleaseAsset> {
+        let package_name = self.package_name(opts);
+        let version = release_version(opts, &tv.version);
+        let package = gitlab::list_packages_from_url(api_url, repo, &package_name)
+            .await?
+            .into_iter()
+            .find(|p| p.version == version)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Package {package_name} version {version} not found in package registry"
+                )
+            })?;
+        let files = gitlab::list_package_files(api_url, repo, package.id).await?;
+        let available_assets: Vec<String> = files.iter().map(|f| f.file_name.clone()).collect();
+
+        let asset_name = if let Some(pattern) = asset_pattern(opts, &version)? {
+            let templated_pattern = template_string_with_tag(&pattern, &version, &package.version);
+            available_assets
+                .iter()
+                .find(|a| self.matches_pattern(a, &templated_pattern))
+                .cloned()
+                .ok_or_else(|| {
+                    eyre::eyre!(
+                        "No matching asset found for pattern: {}\nAvailable assets: {}",
+                        templated_pattern,
+                        Self::format_asset_list(available_assets.iter())
+                    )
+                })?
+        } else {
+            self.auto_detect_asset(opts, &available_assets)?
+        };
+        let file = self
+            .find_asset_case_insensitive(&files, &asset_name, |f| &f.file_name)
+            .ok_or_else(|| {
+                eyre::eyre!(
+                    "Auto-detected asset not found: {}\nAvailable assets: {}",
+                    asset_name,
+                    Self::format_asset_list(available_assets.iter())
+                )
+            })?;
+        let url = gitlab::package_file_url(
+            api_url,
+            repo,
+            &package_name,
+            &package.version,
+            &file.file_name,
+        );
+        Ok(ReleaseAsset {
+            name: file.file_name.clone(),
+            url: url.clone(),
+            url_api: url,
+            digest: file.file_sha256.as_ref().map(|d| format!("sha256:{d}")),
+            ..Default::default()
         })
     }
 
-    fn auto_detect_asset(&self, available_assets: &[String]) -> Result<String> {
+    fn auto_detect_asset(
+        &self,
+        opts: &ToolVersionOptions,
+        available_assets: &[String],
+    ) -> Result<String> {
         let settings = Settings::get();
         let picker = asset_detector::AssetPicker::new(
             settings.os().to_string(),
             settings.arch().to_string(),
-        );
+        )
+        .with_arch_aliases(arch_aliases(opts));
 
         picker.pick_best_asset(available_assets).ok_or_else(|| {
             eyre::eyre!(
@@ -479,18 +2016,15 @@ impl UnifiedGitBackend {
     }
 
     fn matches_pattern(&self, asset_name: &str, pattern: &str) -> bool {
-        // Simple pattern matching - convert glob-like pattern to regex
-        let regex_pattern = pattern
-            .replace(".", "\\.")
-            .replace("*", ".*")
-            .replace("?", ".");
-
-        if let Ok(re) = Regex::new(&format!("^{regex_pattern}$")) {
+        let regex_pattern = glob_to_regex(pattern);
+        let matched = if let Ok(re) = Regex::new(&format!("^{regex_pattern}$")) {
             re.is_match(asset_name)
         } else {
             // Fallback to simple contains check
             asset_name.contains(pattern)
-        }
+        };
+        trace!("asset resolution: pattern={pattern} asset={asset_name} matched={matched}");
+        matched
     }
 
     fn strip_version_prefix(&self, tag_name: &str) -> String {
@@ -498,7 +2032,7 @@ impl UnifiedGitBackend {
 
         // If a custom version_prefix is configured, strip it first
         if let Some(prefix) = opts.get("version_prefix")
-            && let Some(stripped) = tag_name.strip_prefix(prefix)
+            && let Some(stripped) = strip_tag_prefix(prefix, tag_name)
         {
             return stripped.to_string();
         }
@@ -512,6 +2046,195 @@ impl UnifiedGitBackend {
     }
 }
 
+/// The web URL of the instance an API URL belongs to, e.g.: "https://github.com"
+fn web_url(api_url: &str) -> &str {
+    if api_url == DEFAULT_GITHUB_API_BASE_URL {
+        "https://github.com"
+    } else {
+        api_url
+            .trim_end_matches('/')
+            .trim_end_matches("/api/v4")
+            .trim_end_matches("/api/v3")
+            .trim_end_matches("/api/v1")
+            .trim_end_matches("/api/v2")
+    }
+}
+
+/// Whether `url` is on the API or web host of the forge at `api_url`
+fn is_forge_url(api_url: &str, url: &str) -> bool {
+    let host = |url: &str| url::Url::parse(url).ok()?.host_str().map(str::to_string);
+    host(url).is_some_and(|h| {
+        host(api_url).as_ref() == Some(&h) || host(web_url(api_url)).as_ref() == Some(&h)
+    })
+}
+
+/// Finds the detached signature of `name`, e.g.: "tool.tar.gz.asc" or "tool.tar.gz.sig"
+fn find_signature<'a, T>(
+    assets: &'a [T],
+    name: &str,
+    get_name: impl Fn(&T) -> &str,
+) -> Option<&'a T> {
+    ["asc", "sig"].iter().find_map(|ext| {
+        let signature = format!("{name}.{ext}");
+        assets.iter().find(|a| get_name(a) == signature)
+    })
+}
+
+fn trace_release_assets(repo: &str, tag: &str, assets: &[String]) {
+    trace!(
+        "asset resolution: fetched release repo={repo} tag={tag} assets={}",
+        assets.len()
+    );
+    for asset in assets {
+        trace!("asset resolution: release asset={asset}");
+    }
+}
+
+/// Whether a release is at most `days` old, see `max_version_age_days`.
+/// Releases without a publication date are kept.
+fn published_within_days(published_at: Option<&str>, days: i64, now: jiff::Timestamp) -> bool {
+    let Some(published_at) = published_at.and_then(|p| p.parse::<jiff::Timestamp>().ok()) else {
+        return true;
+    };
+    now.duration_since(published_at) <= jiff::SignedDuration::from_hours(days * 24)
+}
+
+/// The API url of a github, gitlab or codeberg host, e.g.: "ghe.example.com" -> "https://ghe.example.com/api/v3"
+pub fn api_url_for_host(backend_type: &BackendType, host: &str) -> String {
+    match (backend_type, host) {
+        (BackendType::Gitlab, "gitlab.com") => DEFAULT_GITLAB_API_BASE_URL.to_string(),
+        (BackendType::Gitlab, host) => format!("https://{host}/api/v4"),
+        (BackendType::Codeberg, host) => format!("https://{host}/api/v1"),
+        (_, "github.com") => DEFAULT_GITHUB_API_BASE_URL.to_string(),
+        (_, host) => format!("https://{host}/api/v3"),
+    }
+}
+
+/// Replaces the scheme and host of an asset download url with `mirror`, e.g.:
+/// `https://codeberg.org/o/r/releases/download/v1/a.tgz` -> `https://mirror.example.com/assets/o/r/releases/download/v1/a.tgz`
+fn mirror_url(url: &str, mirror: &str) -> Result<String> {
+    let url = url::Url::parse(url).wrap_err_with(|| format!("invalid asset url {url}"))?;
+    let mut mirrored = format!("{}{}", mirror.trim_end_matches('/'), url.path());
+    if let Some(query) = url.query() {
+        mirrored = format!("{mirrored}?{query}");
+    }
+    Ok(mirrored)
+}
+
+/// Simple pattern matching - convert glob-like pattern to regex
+pub(crate) fn glob_to_regex(pattern: &str) -> String {
+    pattern
+        .replace(".", "\\.")
+        .replace("*", ".*")
+        .replace("?", ".")
+}
+
+/// Returns the rest of `tag` if it starts with `prefix`, which may contain `*` and `?`.
+/// Wildcards match as little as possible and everything else in `prefix` is literal.
+fn strip_tag_prefix<'a>(prefix: &str, tag: &'a str) -> Option<&'a str> {
+    if !prefix.contains(['*', '?']) {
+        return tag.strip_prefix(prefix);
+    }
+    let mut pattern = String::from("^");
+    for c in prefix.chars() {
+        match c {
+            '*' => pattern.push_str(".*?"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    let re = Regex::new(&pattern).ok()?;
+    re.find(tag).map(|m| &tag[m.end()..])
+}
+
+/// Maps a version listed under a `version_map` name back to the release's own version
+fn release_version(opts: &ToolVersionOptions, version: &str) -> String {
+    version_map(opts)
+        .into_iter()
+        .find(|(_, name)| name == version)
+        .map(|(release, _)| release)
+        .unwrap_or_else(|| version.to_string())
+}
+
+/// Retries transient failures when fetching a release, see `release_fetch_retries`
+async fn with_release_retries<T, F, Fut>(f: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let retries = Settings::get().release_fetch_retries.max(0) as usize;
+    // 500ms, 1s, 2s, ...
+    let strategy = ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(10))
+        .map(jitter)
+        .take(retries);
+    RetryIf::spawn(strategy, f, |err: &eyre::Report| {
+        let transient = is_transient_error(err);
+        if transient {
+            debug!("retrying release fetch after error: {err:#}");
+        }
+        transient
+    })
+    .await
+}
+
+/// Whether installing the asset recorded in the lockfile needs its release to be fetched,
+/// e.g.: for the tag written by `--record-provenance`, `include_release_notes` or the signature
+/// checked by `verify_release_signature`
+fn needs_release_metadata(opts: &ToolVersionOptions) -> bool {
+    env::RECORD_PROVENANCE.load(Ordering::Relaxed)
+        || ["include_release_notes", "verify_release_signature"]
+            .iter()
+            .any(|key| opts.get(key).is_some_and(|v| v == "true"))
+}
+
+/// The only entry of `dir` if it is a directory, e.g.: `tool-1.0/` in an archive with a
+/// versioned top-level directory
+fn single_subdir(dir: &Path) -> Result<Option<PathBuf>> {
+    let entries = file::ls(dir)?;
+    Ok(match entries.into_iter().exactly_one() {
+        Ok(entry) if entry.symlink_metadata()?.is_dir() => Some(entry),
+        _ => None,
+    })
+}
+
+/// Moves the files extracted to `root` to `install_path`, skipping the ones matching `exclude`.
+/// Directories are created for the files moved into them so excluded ones aren't left empty.
+fn move_extracted(root: &Path, install_path: &Path, exclude: Option<&GlobSet>) -> Result<()> {
+    file::create_dir_all(install_path.parent().unwrap())?;
+    let Some(exclude) = exclude else {
+        return file::rename(root, install_path);
+    };
+    for entry in walkdir::WalkDir::new(root).min_depth(1) {
+        let entry = entry?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let rel = entry.path().strip_prefix(root)?;
+        if exclude.is_match(rel) {
+            trace!("skipping {}", rel.display());
+            continue;
+        }
+        let dest = install_path.join(rel);
+        file::create_dir_all(dest.parent().unwrap())?;
+        file::rename(entry.path(), &dest)?;
+    }
+    Ok(())
+}
+
+/// Whether releases marked as prereleases are listed, see `include_prerelease`
+fn include_prerelease(opts: &ToolVersionOptions) -> bool {
+    opts.get("include_prerelease").is_some_and(|v| v == "true")
+}
+
+/// Orders releases by semver precedence so prereleases come before their release,
+/// e.g.: 1.0.0-alpha < 1.0.0-beta < 1.0.0-rc.1 < 1.0.0. Versions that don't parse
+/// are listed first, in the order of the API.
+fn sort_by_semver(releases: &mut [RemoteRelease]) {
+    releases.sort_by_cached_key(|r| Versioning::new(&r.version));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -524,6 +2247,101 @@ mod tests {
         ))
     }
 
+    #[tokio::test]
+    async fn test_repo_candidates() {
+        let _config = Config::get().await.unwrap();
+        let backend = create_test_backend();
+        assert_eq!(
+            backend.repo_candidates(&ToolVersionOptions::default()),
+            vec![(
+                DEFAULT_GITHUB_API_BASE_URL.to_string(),
+                "test/repo".to_string()
+            )]
+        );
+
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "fallback_repos".to_string(),
+            r#"["mirror/repo", "https://github.example.com/owner/repo", "github.com/other/repo"]"#
+                .to_string(),
+        );
+        assert_eq!(
+            backend.repo_candidates(&opts),
+            vec![
+                (
+                    DEFAULT_GITHUB_API_BASE_URL.to_string(),
+                    "test/repo".to_string()
+                ),
+                (
+                    DEFAULT_GITHUB_API_BASE_URL.to_string(),
+                    "mirror/repo".to_string()
+                ),
+                (
+                    "https://github.example.com/api/v3".to_string(),
+                    "owner/repo".to_string()
+                ),
+                (
+                    DEFAULT_GITHUB_API_BASE_URL.to_string(),
+                    "other/repo".to_string()
+                ),
+            ]
+        );
+
+        let mut opts = ToolVersionOptions::default();
+        opts.opts
+            .insert("fallback_repos".to_string(), "a/b, c/d".to_string());
+        assert_eq!(
+            string_list_option(&opts, "fallback_repos"),
+            vec!["a/b", "c/d"]
+        );
+    }
+
+    #[test]
+    fn test_move_extracted() {
+        let tmp = tempfile::tempdir().unwrap();
+        let extracted = tmp.path().join("extracted");
+        for f in [
+            "tool-1.0/bin/tool",
+            "tool-1.0/README.md",
+            "tool-1.0/doc/guide.html",
+        ] {
+            file::create_dir_all(extracted.join(f).parent().unwrap()).unwrap();
+            file::write(extracted.join(f), "").unwrap();
+        }
+        let root = single_subdir(&extracted).unwrap().unwrap();
+        assert_eq!(root, extracted.join("tool-1.0"));
+        assert_eq!(single_subdir(&root).unwrap(), None);
+
+        let exclude = globset::GlobSetBuilder::new()
+            .add(globset::Glob::new("*.md").unwrap())
+            .add(globset::Glob::new("doc/**").unwrap())
+            .build()
+            .unwrap();
+        let install_path = tmp.path().join("installs/tool/1.0");
+        move_extracted(&root, &install_path, Some(&exclude)).unwrap();
+        let files = file::recursive_ls(&install_path).unwrap();
+        assert_eq!(files.len(), 1);
+        assert!(install_path.join("bin/tool").is_file());
+        assert!(!install_path.join("doc").exists());
+    }
+
+    #[test]
+    fn test_published_within_days() {
+        let now: jiff::Timestamp = "2025-06-30T00:00:00Z".parse().unwrap();
+        assert!(published_within_days(Some("2025-06-01T12:00:00Z"), 30, now));
+        assert!(published_within_days(
+            Some("2025-05-31T00:00:00.000Z"),
+            30,
+            now
+        ));
+        assert!(!published_within_days(
+            Some("2025-05-30T23:59:59Z"),
+            30,
+            now
+        ));
+        assert!(published_within_days(None, 30, now));
+    }
+
     #[test]
     fn test_pattern_matching() {
         let backend = create_test_backend();
@@ -531,6 +2349,122 @@ mod tests {
         assert!(!backend.matches_pattern("other-v1.0.0.zip", "test-*"));
     }
 
+    #[test]
+    fn test_download_headers() {
+        let backend = create_test_backend();
+        let mirror = "https://mirror.example.com".to_string();
+        let url = mirror_url(
+            "https://github.com/owner/repo/releases/download/v1.0.0/tool.tar.gz",
+            &mirror,
+        )
+        .unwrap();
+        assert!(backend.download_headers(&url, Some(&mirror)).is_empty());
+    }
+
+    #[test]
+    fn test_with_release_metadata() {
+        let locked = ReleaseAsset {
+            name: "tool-linux.tar.gz".to_string(),
+            url: "https://mirror.example.com/tool-linux.tar.gz".to_string(),
+            ..Default::default()
+        };
+        let release = ReleaseAsset {
+            name: "tool-linux-x64.tar.gz".to_string(),
+            url: "https://github.com/owner/repo/releases/download/v1.0.0/tool-linux-x64.tar.gz"
+                .to_string(),
+            tag: Some("v1.0.0".to_string()),
+            published_at: Some("2024-01-01T00:00:00Z".to_string()),
+            release_notes: Some("## Changes".to_string()),
+            signature_url: Some(
+                "https://github.com/owner/repo/releases/download/v1.0.0/tool-linux.tar.gz.asc"
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+        let asset = locked.with_release_metadata(release);
+        assert_eq!(asset.name, "tool-linux.tar.gz");
+        assert_eq!(asset.url, "https://mirror.example.com/tool-linux.tar.gz");
+        assert_eq!(asset.tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(asset.published_at.as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(asset.release_notes.as_deref(), Some("## Changes"));
+        assert!(asset.signature_url.is_some());
+    }
+
+    #[test]
+    fn test_needs_release_metadata() {
+        let mut opts = ToolVersionOptions::default();
+        assert!(!needs_release_metadata(&opts));
+        // the signature is only known from the release, the lockfile doesn't record it
+        opts.opts
+            .insert("verify_release_signature".to_string(), "true".to_string());
+        assert!(needs_release_metadata(&opts));
+        opts.opts
+            .insert("verify_release_signature".to_string(), "false".to_string());
+        opts.opts
+            .insert("include_release_notes".to_string(), "true".to_string());
+        assert!(needs_release_metadata(&opts));
+    }
+
+    #[tokio::test]
+    async fn test_release_page_url() {
+        let _config = Config::get().await.unwrap();
+        let backend = create_test_backend();
+        assert_eq!(
+            backend.release_page_url(DEFAULT_GITHUB_API_BASE_URL, "cli/cli", "v2.62.0"),
+            "https://github.com/cli/cli/releases/tag/v2.62.0"
+        );
+        assert_eq!(
+            backend.release_page_url("https://ghe.example.com/api/v3", "org/tool", "1.0.0"),
+            "https://ghe.example.com/org/tool/releases/tag/1.0.0"
+        );
+        for api_url in ["https://codeberg.org/api/v1", "https://codeberg.org/api/v2"] {
+            assert_eq!(
+                backend.release_page_url(api_url, "org/tool", "v1.0.0"),
+                "https://codeberg.org/org/tool/releases/tag/v1.0.0"
+            );
+        }
+        let backend = UnifiedGitBackend::from_arg(BackendArg::new(
+            "gitlab".to_string(),
+            Some("gitlab:gitlab-org/gitlab-runner".to_string()),
+        ));
+        assert_eq!(
+            backend.release_page_url(
+                DEFAULT_GITLAB_API_BASE_URL,
+                "gitlab-org/gitlab-runner",
+                "v17.0.0"
+            ),
+            "https://gitlab.com/gitlab-org/gitlab-runner/-/releases/v17.0.0"
+        );
+    }
+
+    #[test]
+    fn test_asset_content_type_filter() {
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "asset_content_type_filter".to_string(),
+            "application/gzip".to_string(),
+        );
+        let backend = create_test_backend();
+        assert_eq!(
+            backend.asset_content_type_filter(&opts).unwrap(),
+            Some(&"application/gzip".to_string())
+        );
+        for (backend_type, tool) in [
+            ("gitlab", "gitlab:org/tool"),
+            ("codeberg", "codeberg:org/tool"),
+        ] {
+            let backend = UnifiedGitBackend::from_arg(BackendArg::new(
+                backend_type.to_string(),
+                Some(tool.to_string()),
+            ));
+            let err = backend.asset_content_type_filter(&opts).unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("only supported by the github backend")
+            );
+        }
+    }
+
     #[test]
     fn test_version_prefix_functionality() {
         let mut backend = create_test_backend();
@@ -554,6 +2488,47 @@ mod tests {
         assert_eq!(backend.strip_version_prefix("1.0.0"), "1.0.0");
     }
 
+    #[test]
+    fn test_strip_tag_prefix() {
+        assert_eq!(strip_tag_prefix("release-", "release-1.0.0"), Some("1.0.0"));
+        assert_eq!(strip_tag_prefix("*-v", "component-v1.0.0"), Some("1.0.0"));
+        assert_eq!(strip_tag_prefix("*-v", "other-v2.1.0"), Some("2.1.0"));
+        assert_eq!(strip_tag_prefix("*-v", "v1.0.0"), None);
+        assert_eq!(strip_tag_prefix("cli-?", "cli-v1.0.0"), Some("1.0.0"));
+        assert_eq!(strip_tag_prefix("c++-*-v", "c++-cli-v1.0.0"), Some("1.0.0"));
+        assert_eq!(strip_tag_prefix("(*)-v", "cli-v1.0.0"), None);
+        assert_eq!(strip_tag_prefix("*-v", "cli-v1.0.0-v2"), Some("1.0.0-v2"));
+    }
+
+    #[test]
+    fn test_is_forge_url() {
+        let api_url = DEFAULT_GITHUB_API_BASE_URL;
+        assert!(is_forge_url(
+            api_url,
+            "https://github.com/o/r/releases/download/v1/SHA256SUMS"
+        ));
+        assert!(is_forge_url(api_url, "https://api.github.com/repos/o/r"));
+        assert!(!is_forge_url(api_url, "https://cdn.example.com/SHA256SUMS"));
+        assert!(is_forge_url(
+            "https://ghe.example.com/api/v3",
+            "https://ghe.example.com/o/r/releases/download/v1/SHA256SUMS"
+        ));
+        assert!(!is_forge_url("https://ghe.example.com/api/v3", "not a url"));
+    }
+
+    #[test]
+    fn test_mirror_url() {
+        assert_eq!(
+            mirror_url(
+                "https://codeberg.org/owner/repo/releases/download/v1.0.0/tool.tar.gz",
+                "https://mirror.example.com/forgejo-assets/"
+            )
+            .unwrap(),
+            "https://mirror.example.com/forgejo-assets/owner/repo/releases/download/v1.0.0/tool.tar.gz"
+        );
+        assert!(mirror_url("not a url", "https://mirror.example.com").is_err());
+    }
+
     #[test]
     fn test_find_asset_case_insensitive() {
         let backend = create_test_backend();
@@ -606,4 +2581,33 @@ mod tests {
             backend.find_asset_case_insensitive(&assets, "nonexistent-asset.tar.gz", |a| &a.name);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_sort_by_semver() {
+        let release = |version: &str| RemoteRelease {
+            version: version.to_string(),
+            published_at: None,
+            asset_count: None,
+            prerelease: Some(version.contains('-')),
+        };
+        let mut releases = [
+            "1.0.0",
+            "1.0.0-rc.1",
+            "1.0.0-beta.10",
+            "1.0.0-alpha",
+            "1.0.0-beta.2",
+        ]
+        .map(release);
+        sort_by_semver(&mut releases);
+        assert_eq!(
+            releases.map(|r| r.version),
+            [
+                "1.0.0-alpha",
+                "1.0.0-beta.2",
+                "1.0.0-beta.10",
+                "1.0.0-rc.1",
+                "1.0.0"
+            ]
+        );
+    }
 }
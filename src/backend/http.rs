@@ -286,6 +286,8 @@ impl HttpBackend {
                 strip_components: strip_components.unwrap_or(0),
                 pr,
                 preserve_mtime: false, // Bump mtime when extracting to cache
+                password: None,
+                exclude: None,
             };
 
             // Extract with determined strip_components
@@ -5,6 +5,7 @@ use std::fs::File;
 use std::hash::Hash;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 use tokio::sync::Mutex as TokioMutex;
 
@@ -175,6 +176,7 @@ pub fn arg_to_backend(ba: BackendArg) -> Option<ABackend> {
         BackendType::Dotnet => Some(Arc::new(dotnet::DotnetBackend::from_arg(ba))),
         BackendType::Npm => Some(Arc::new(npm::NPMBackend::from_arg(ba))),
         BackendType::Gem => Some(Arc::new(gem::GemBackend::from_arg(ba))),
+        BackendType::Codeberg => Some(Arc::new(github::UnifiedGitBackend::from_arg(ba))),
         BackendType::Github => Some(Arc::new(github::UnifiedGitBackend::from_arg(ba))),
         BackendType::Gitlab => Some(Arc::new(github::UnifiedGitBackend::from_arg(ba))),
         BackendType::Go => Some(Arc::new(go::GoBackend::from_arg(ba))),
@@ -538,7 +540,9 @@ pub trait Backend: Debug + Send + Sync {
                 debug!("error syncing incomplete file parent directory: {:?}", err);
             }
         }
-        if let Some(script) = tv.request.options().get("postinstall") {
+        if let Some(script) = tv.request.options().get("postinstall")
+            && !ctx.foreign_platform
+        {
             ctx.pr
                 .finish_with_message("running custom postinstall hook".to_string());
             self.run_postinstall_hook(&ctx, &tv, script).await?;
@@ -832,6 +836,11 @@ pub trait Backend: Debug + Send + Sync {
         tv: &mut ToolVersion,
         file: &Path,
     ) -> Result<()> {
+        if env::SKIP_CHECKSUM.load(Ordering::Relaxed) {
+            // don't record the checksum of an unverified file in the lockfile either
+            debug!("skipping checksum verification of {}", file.display());
+            return Ok(());
+        }
         let settings = Settings::get();
         let filename = file.file_name().unwrap().to_string_lossy().to_string();
         let lockfile_enabled = settings.lockfile && settings.experimental;
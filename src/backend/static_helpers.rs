@@ -1,12 +1,19 @@
 // Shared template logic for backends
+use crate::env;
 use crate::file;
 use crate::hash;
 use crate::toolset::ToolVersion;
 use crate::toolset::ToolVersionOptions;
 use crate::ui::progress_report::SingleReport;
-use eyre::{Result, bail};
-use indexmap::IndexSet;
-use std::path::Path;
+use eyre::{Result, WrapErr, bail, eyre};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use indexmap::{IndexMap, IndexSet};
+use itertools::Itertools;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use versions::{Requirement, Versioning};
+use xx::regex;
 
 // Shared OS/arch patterns used across helpers
 const OS_PATTERNS: &[&str] = &[
@@ -55,11 +62,13 @@ where
     };
 
     for candidate in candidates {
+        trace!("asset resolution: trying release tag={candidate}");
         match resolver(candidate.clone()).await {
             Ok(res) => return Ok(res),
             Err(e) => {
                 let is_404 = crate::http::error_code(&e) == Some(404);
                 if is_404 {
+                    trace!("asset resolution: release not found tag={candidate}");
                     errors.push(e);
                 } else {
                     return Err(e);
@@ -102,13 +111,17 @@ pub fn platform_aliases() -> Vec<(String, String)> {
     aliases
 }
 
+/// Tables holding per-platform options, `platform_overrides` is the most explicit name
+/// for tools whose assets don't follow the usual platform naming
+const PLATFORM_PREFIXES: [&str; 3] = ["platforms", "platform", "platform_overrides"];
+
 /// Looks up a value in ToolVersionOptions using nested platform key format.
 /// Supports nested format (platforms.macos-x64.url) with os-arch dash notation.
-/// Also supports both "platforms" and "platform" prefixes.
+/// Also supports the "platform" and "platform_overrides" prefixes.
 pub fn lookup_platform_key(opts: &ToolVersionOptions, key_type: &str) -> Option<String> {
     // Try nested platform structure with os-arch format
     for (os, arch) in platform_aliases() {
-        for prefix in ["platforms", "platform"] {
+        for prefix in PLATFORM_PREFIXES {
             // Try nested format: platforms.macos-x64.url
             let nested_key = format!("{prefix}.{os}-{arch}.{key_type}");
             if let Some(val) = opts.get_nested_string(&nested_key) {
@@ -131,7 +144,8 @@ pub fn list_available_platforms_with_key(opts: &ToolVersionOptions, key_type: &s
     // Gather from flat keys
     for (k, _) in opts.iter() {
         if let Some(rest) = k
-            .strip_prefix("platforms_")
+            .strip_prefix("platform_overrides_")
+            .or_else(|| k.strip_prefix("platforms_"))
             .or_else(|| k.strip_prefix("platform_"))
             && let Some(platform_part) = rest.strip_suffix(&format!("_{}", key_type))
         {
@@ -149,7 +163,7 @@ pub fn list_available_platforms_with_key(opts: &ToolVersionOptions, key_type: &s
     // Probe nested keys using shared patterns
     for os in OS_PATTERNS {
         for arch in ARCH_PATTERNS {
-            for prefix in ["platforms", "platform"] {
+            for prefix in PLATFORM_PREFIXES {
                 let nested_key = format!("{prefix}.{os}-{arch}.{key_type}");
                 if opts.contains_key(&nested_key) {
                     set.insert(format!("{os}-{arch}"));
@@ -166,6 +180,194 @@ pub fn template_string(template: &str, tv: &ToolVersion) -> String {
     template.replace("{version}", version)
 }
 
+/// Expands `{version}` to the release version and `{tag}` to the release tag, i.e.: the
+/// version with its prefix (`v1.0.0` rather than `1.0.0`)
+pub fn template_string_with_tag(template: &str, version: &str, tag: &str) -> String {
+    template.replace("{version}", version).replace("{tag}", tag)
+}
+
+/// Reads `arch_aliases`, a TOML table mapping the arch names a project uses in its
+/// assets to the standard ones, e.g.: `{ x64 = "x86_64", arm64v8 = "aarch64" }`
+pub fn arch_aliases(opts: &ToolVersionOptions) -> IndexMap<String, String> {
+    string_map_option(opts, "arch_aliases")
+}
+
+/// Reads `env_vars`, a TOML table of environment variables set when the tool is active,
+/// e.g.: `{ TOOL_HOME = "{install_path}" }`
+pub fn env_vars(opts: &ToolVersionOptions) -> IndexMap<String, String> {
+    string_map_option(opts, "env_vars")
+}
+
+/// Reads `version_map`, a TOML table mapping release versions to the names they are
+/// listed under, e.g.: `{ "2024.01.15" = "1.0" }`
+pub fn version_map(opts: &ToolVersionOptions) -> IndexMap<String, String> {
+    string_map_option(opts, "version_map")
+}
+
+/// Reads `version_blocklist`, versions that are never listed, each with its reason from the
+/// `version_blocklist_reason` table if any, e.g.: `{ "1.2.3" = "CVE-2024-1234" }`
+pub fn version_blocklist(opts: &ToolVersionOptions) -> IndexMap<String, Option<String>> {
+    let mut reasons = string_map_option(opts, "version_blocklist_reason");
+    string_list_option(opts, "version_blocklist")
+        .into_iter()
+        .map(|v| {
+            let reason = reasons.shift_remove(&v);
+            (v, reason)
+        })
+        .collect()
+}
+
+/// The `asset_pattern` for `version`: the pattern of the first `asset_pattern_overrides` entry
+/// whose version range matches, otherwise the platform-specific or plain `asset_pattern`
+pub fn asset_pattern(opts: &ToolVersionOptions, version: &str) -> Result<Option<String>> {
+    let version = Versioning::new(version);
+    for (range, pattern) in asset_pattern_overrides(opts)? {
+        if let Some(version) = &version
+            && version_matches_range(version, &range)?
+        {
+            return Ok(Some(pattern));
+        }
+    }
+    Ok(lookup_platform_key(opts, "asset_pattern").or_else(|| opts.get("asset_pattern").cloned()))
+}
+
+/// Reads `asset_pattern_overrides`, the (version range, pattern) entries in the order they are
+/// tried, e.g.: `[{ version = ">=2.0", pattern = "tool-v{version}.tar.gz" }]`
+pub fn asset_pattern_overrides(opts: &ToolVersionOptions) -> Result<Vec<(String, String)>> {
+    let Some(value) = opts.get("asset_pattern_overrides") else {
+        return Ok(vec![]);
+    };
+    let table = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .wrap_err("invalid asset_pattern_overrides")?;
+    let Some(overrides) = table.get("value").and_then(|v| v.as_array()) else {
+        bail!("asset_pattern_overrides must be an array of tables");
+    };
+    overrides
+        .iter()
+        .map(|o| {
+            let (Some(range), Some(pattern)) = (
+                o.get("version").and_then(|v| v.as_str()),
+                o.get("pattern").and_then(|v| v.as_str()),
+            ) else {
+                bail!("each asset_pattern_overrides entry needs a version and a pattern");
+            };
+            Ok((range.to_string(), pattern.to_string()))
+        })
+        .collect()
+}
+
+/// Whether `version` satisfies every comma-separated requirement in `range`, e.g.: ">=2.0, <3"
+pub(crate) fn version_matches_range(version: &Versioning, range: &str) -> Result<bool> {
+    for req in range.split(',').map(|r| r.trim()) {
+        let Some(req) = Requirement::new(req) else {
+            bail!("invalid version range {req:?} in asset_pattern_overrides");
+        };
+        if !req.matches(version) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Reads `version_transform`, a regex and its replacement applied to versions after the
+/// tag prefix is stripped, e.g.: `{ pattern = "-(alpha|beta|rc)\\.", replacement = "-$1" }`
+pub fn version_transform(opts: &ToolVersionOptions) -> Result<Option<(Regex, String)>> {
+    let transform = string_map_option(opts, "version_transform");
+    let Some(pattern) = transform.get("pattern") else {
+        if opts.contains_key("version_transform") {
+            bail!("version_transform requires a pattern");
+        }
+        return Ok(None);
+    };
+    let regex = Regex::new(pattern)
+        .wrap_err_with(|| format!("invalid version_transform pattern: {pattern}"))?;
+    let replacement = transform.get("replacement").cloned().unwrap_or_default();
+    Ok(Some((regex, replacement)))
+}
+
+pub fn transform_version(transform: Option<&(Regex, String)>, version: String) -> String {
+    match transform {
+        Some((regex, replacement)) => regex.replace_all(&version, replacement).to_string(),
+        None => version,
+    }
+}
+
+/// Reads an option holding a TOML table of strings
+fn string_map_option(opts: &ToolVersionOptions, key: &str) -> IndexMap<String, String> {
+    let Some(value) = opts.get(key) else {
+        return Default::default();
+    };
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|t| t.get("value").and_then(|v| v.as_table()).cloned())
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(k, v)| v.as_str().map(|v| (k, v.to_string())))
+        .collect()
+}
+
+/// Reads an option holding either a TOML array of strings or a comma-separated list
+pub fn string_list_option(opts: &ToolVersionOptions, key: &str) -> Vec<String> {
+    let Some(value) = opts.get(key) else {
+        return vec![];
+    };
+    let value = value.trim();
+    if value.starts_with('[') {
+        toml::from_str::<toml::Table>(&format!("value = {value}"))
+            .ok()
+            .and_then(|t| t.get("value").and_then(|v| v.as_array()).cloned())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|v| v.as_str().map(|s| s.to_string()))
+            .collect()
+    } else {
+        value
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
+
+/// The files not to extract from `extract_filter`, e.g.: `["**/*.md", "**/man/**"]`.
+/// Paths are matched relative to the install path.
+pub fn extract_filter(opts: &ToolVersionOptions) -> Result<Option<GlobSet>> {
+    let patterns = string_list_option(opts, "extract_filter");
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in &patterns {
+        builder.add(GlobBuilder::new(pattern).literal_separator(true).build()?);
+    }
+    Ok(Some(builder.build()?))
+}
+
+/// Finds the binary for the current platform in a multi-arch archive, e.g.: with
+/// `bin/{os}-{arch}/{name}`, `bin/linux-amd64/tool` or `bin/linux-x64/tool`.
+/// Every os/arch spelling from `platform_aliases` and `arch_aliases` is tried, returns
+/// the path relative to the install path.
+pub fn resolve_bin_path_template(
+    template: &str,
+    tv: &ToolVersion,
+    opts: &ToolVersionOptions,
+) -> Option<PathBuf> {
+    let tool_name = tv.ba().tool_name();
+    let name = tool_name.rsplit('/').next().unwrap_or(&tool_name);
+    let template = template_string(template, tv).replace("{name}", name);
+    let mut platforms = platform_aliases();
+    let aliases = arch_aliases(opts);
+    for (os, arch) in platforms.clone() {
+        for (alias, _) in aliases.iter().filter(|(_, a)| **a == arch) {
+            platforms.push((os.clone(), alias.clone()));
+        }
+    }
+    platforms
+        .into_iter()
+        .map(|(os, arch)| PathBuf::from(template.replace("{os}", &os).replace("{arch}", &arch)))
+        .find(|rel| tv.install_path().join(rel).is_file())
+}
+
 pub fn get_filename_from_url(url_str: &str) -> String {
     let filename = if let Ok(url) = url::Url::parse(url_str) {
         // Use proper URL parsing to get the path and extract filename
@@ -186,10 +388,12 @@ pub fn get_filename_from_url(url_str: &str) -> String {
         .unwrap_or(filename)
 }
 
+/// `content_type` is the Content-Type of the release asset if known, used to detect scripts
 pub fn install_artifact(
     tv: &crate::toolset::ToolVersion,
     file_path: &Path,
     opts: &ToolVersionOptions,
+    content_type: Option<&str>,
     pr: Option<&dyn SingleReport>,
 ) -> eyre::Result<()> {
     let install_path = tv.install_path();
@@ -213,7 +417,9 @@ pub fn install_artifact(
         // Handle compressed single binary
         let decompressed_name = file_name.trim_end_matches(&format!(".{}", ext));
         // Determine the destination path with support for bin_path
-        let dest = if let Some(bin_path_template) =
+        let dest = if let Some(dest) = install_file_dest(&install_path, opts)? {
+            dest
+        } else if let Some(bin_path_template) =
             lookup_platform_key(opts, "bin_path").or_else(|| opts.get("bin_path").cloned())
         {
             let bin_path = template_string(&bin_path_template, tv);
@@ -239,32 +445,42 @@ pub fn install_artifact(
         }
 
         file::make_executable(&dest)?;
+    } else if ext == "pkg" {
+        install_pkg(file_path, &install_path, opts)?;
     } else if format == file::TarFormat::Raw {
+        let interpreter = script_interpreter(opts, content_type);
         // Copy the file directly to the bin_path directory or install_path
-        if let Some(bin_path_template) =
+        let dest = if let Some(dest) = install_file_dest(&install_path, opts)? {
+            dest
+        } else if let Some(bin_path_template) =
             lookup_platform_key(opts, "bin_path").or_else(|| opts.get("bin_path").cloned())
         {
             let bin_path = template_string(&bin_path_template, tv);
             let bin_dir = install_path.join(&bin_path);
             file::create_dir_all(&bin_dir)?;
-            let dest = bin_dir.join(file_path.file_name().unwrap());
-            file::copy(file_path, &dest)?;
-            file::make_executable(&dest)?;
+            bin_dir.join(file_path.file_name().unwrap())
         } else if let Some(bin_name) =
             lookup_platform_key(opts, "bin").or_else(|| opts.get("bin").cloned())
         {
             // If bin is specified, rename the file to this name
-            let dest = install_path.join(&bin_name);
-            file::copy(file_path, &dest)?;
-            file::make_executable(&dest)?;
+            install_path.join(&bin_name)
         } else {
             // Always auto-clean binary names by removing OS/arch suffixes
-            let original_name = file_path.file_name().unwrap().to_string_lossy();
-            let cleaned_name = clean_binary_name(&original_name, Some(&tv.ba().tool_name));
-            let dest = install_path.join(cleaned_name);
-            file::copy(file_path, &dest)?;
-            file::make_executable(&dest)?;
+            let cleaned_name = clean_binary_name(&file_name, Some(&tv.ba().tool_name));
+            if interpreter.is_some() {
+                // scripts go in bin/ so nothing else in the install path ends up on PATH
+                let bin_dir = install_path.join("bin");
+                file::create_dir_all(&bin_dir)?;
+                bin_dir.join(cleaned_name)
+            } else {
+                install_path.join(cleaned_name)
+            }
+        };
+        file::copy(file_path, &dest)?;
+        if let Some(interpreter) = interpreter {
+            add_shebang(&dest, &interpreter)?;
         }
+        file::make_executable(&dest)?;
     } else {
         // Handle archive formats
         // Auto-detect if we need strip_components=1 before extracting
@@ -273,16 +489,20 @@ pub fn install_artifact(
             && lookup_platform_key(opts, "bin_path")
                 .or_else(|| opts.get("bin_path").cloned())
                 .is_none()
+            && !opts.contains_key("bin_path_template")
             && let Ok(should_strip) = file::should_strip_components(file_path, format)
             && should_strip
         {
             debug!("Auto-detected single directory archive, extracting with strip_components=1");
             strip_components = Some(1);
         }
+        let exclude = extract_filter(opts)?;
         let tar_opts = file::TarOptions {
             format,
             strip_components: strip_components.unwrap_or(0),
             pr,
+            password: opts.get("archive_password").cloned(),
+            exclude: exclude.as_ref(),
             ..Default::default()
         };
 
@@ -292,17 +512,127 @@ pub fn install_artifact(
     Ok(())
 }
 
+/// The interpreter of a single script asset, from the `interpreter` option or the Content-Type of
+/// the release asset, e.g.: text/x-shellscript
+fn script_interpreter(opts: &ToolVersionOptions, content_type: Option<&str>) -> Option<String> {
+    if let Some(interpreter) = opts.get("interpreter") {
+        return Some(interpreter.clone());
+    }
+    let mime = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+    match mime.as_str() {
+        "text/x-shellscript" | "text/x-sh" | "application/x-sh" | "application/x-shellscript" => {
+            Some("/bin/sh".to_string())
+        }
+        "text/x-python" | "text/x-python3" | "text/x-script.python" | "application/x-python" => {
+            Some("/usr/bin/env python3".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Prepends `#!<interpreter>` to the script at `path` unless it already has a shebang
+fn add_shebang(path: &Path, interpreter: &str) -> Result<()> {
+    let content = file::read(path)?;
+    if content.starts_with(b"#!") {
+        return Ok(());
+    }
+    let mut script = format!("#!{interpreter}\n").into_bytes();
+    script.extend(content);
+    file::write(path, script)?;
+    Ok(())
+}
+
+/// `bin/<install_file>` for assets that are a single binary, e.g.: a statically linked executable
+fn install_file_dest(install_path: &Path, opts: &ToolVersionOptions) -> Result<Option<PathBuf>> {
+    let Some(name) = opts.get("install_file") else {
+        return Ok(None);
+    };
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        bail!("invalid install_file {name:?}, expected a file name");
+    }
+    let bin_dir = install_path.join("bin");
+    file::create_dir_all(&bin_dir)?;
+    Ok(Some(bin_dir.join(name)))
+}
+
+/// Expands a macOS installer package and copies the files of its payload to `install_path`
+/// instead of installing them to the locations the package would use, e.g.: usr/local/bin
+fn install_pkg(file_path: &Path, install_path: &Path, opts: &ToolVersionOptions) -> Result<()> {
+    if !cfg!(target_os = "macos") {
+        bail!(
+            "{} is a macOS installer package, which can only be installed on macOS",
+            file::display_path(file_path)
+        );
+    }
+    let tmp = tempfile::tempdir()?;
+    // pkgutil fails if the destination exists
+    let expanded = tmp.path().join("pkg");
+    file::un_pkg(file_path, &expanded)?;
+    let payload = find_pkg_payload(&expanded, opts.get("pkg_payload_path").map(|s| s.as_str()))?;
+    debug!("installing pkg payload {}", payload.display());
+    let exclude = extract_filter(opts)?;
+    for path in file::recursive_ls(&payload)? {
+        let rel = path.strip_prefix(&payload)?;
+        if exclude
+            .as_ref()
+            .is_some_and(|exclude| exclude.is_match(rel))
+        {
+            trace!("skipping {}", rel.display());
+            continue;
+        }
+        let dest = install_path.join(rel);
+        file::create_dir_all(dest.parent().unwrap())?;
+        file::copy(&path, &dest)?;
+    }
+    Ok(())
+}
+
+/// Finds the payload to install in an expanded package, either the one at `payload_path`
+/// or the only one in the package. Component packages hold theirs in `<name>.pkg/Payload`.
+fn find_pkg_payload(expanded: &Path, payload_path: Option<&str>) -> Result<PathBuf> {
+    if let Some(payload_path) = payload_path {
+        let payload = expanded.join(payload_path);
+        if !payload.is_dir() {
+            bail!("pkg_payload_path {payload_path} not found in package");
+        }
+        return Ok(payload);
+    }
+    let payloads = std::iter::once(expanded.to_path_buf())
+        .chain(file::ls(expanded)?)
+        .map(|dir| dir.join("Payload"))
+        .filter(|p| p.is_dir())
+        .collect::<Vec<_>>();
+    match payloads.as_slice() {
+        [payload] => Ok(payload.clone()),
+        [] => bail!("no payload found in package"),
+        _ => bail!(
+            "multiple payloads found in package, set pkg_payload_path to one of: {}",
+            payloads
+                .iter()
+                .map(|p| p.strip_prefix(expanded).unwrap_or(p).display().to_string())
+                .join(", ")
+        ),
+    }
+}
+
 pub fn verify_artifact(
     _tv: &crate::toolset::ToolVersion,
     file_path: &Path,
     opts: &crate::toolset::ToolVersionOptions,
     pr: Option<&dyn SingleReport>,
 ) -> Result<()> {
+    if env::SKIP_CHECKSUM.load(Ordering::Relaxed) {
+        debug!("skipping checksum verification of {}", file_path.display());
+        return Ok(());
+    }
     // Check platform-specific checksum first, then fall back to generic
     let checksum = lookup_platform_key(opts, "checksum").or_else(|| opts.get("checksum").cloned());
 
     if let Some(checksum) = checksum {
-        verify_checksum_str(file_path, &checksum, pr)?;
+        let require_all = opts
+            .get("require_all_checksums")
+            .is_some_and(|v| v == "true");
+        verify_checksums_str(file_path, &checksum, require_all, pr)?;
     }
 
     // Check platform-specific size first, then fall back to generic
@@ -323,6 +653,56 @@ pub fn verify_artifact(
     Ok(())
 }
 
+/// Finds the checksum of `filename` in the text of a checksum file, as `algo:hash`.
+/// `format` is "gnu" (`<hash>  <file>`, as written by sha256sum) or "bsd"
+/// (`SHA256 (<file>) = <hash>`, as written by `shasum --tag`), if not set it is
+/// detected from the first line.
+pub fn checksum_from_file(
+    text: &str,
+    filename: &str,
+    format: Option<&str>,
+) -> Result<Option<String>> {
+    let bsd_re = regex!(r"^(\w+) ?\((.+)\) ?= ?([0-9a-fA-F]+)$");
+    let lines = text.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+    let is_bsd = match format {
+        Some("bsd") => true,
+        Some("gnu") => false,
+        Some(format) => bail!("invalid checksum_format: {format}, expected gnu or bsd"),
+        None => lines.clone().next().is_some_and(|l| bsd_re.is_match(l)),
+    };
+    let matches_file = |name: &str| {
+        let name = name.trim_start_matches('*');
+        name == filename || name.rsplit('/').next() == Some(filename)
+    };
+    let algo = |hash: &str| match hash.len() {
+        40 => Ok("sha1"),
+        64 => Ok("sha256"),
+        128 => Ok("sha512"),
+        _ => Err(eyre!("unrecognized checksum for {filename}: {hash}")),
+    };
+    // a file holding only the hash of one asset, e.g.: tool.tar.gz.sha256
+    if let Ok(hash) = lines.clone().exactly_one()
+        && hash.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        return Ok(Some(format!("{}:{hash}", algo(hash)?)));
+    }
+    for line in lines {
+        if is_bsd {
+            if let Some(caps) = bsd_re.captures(line)
+                && matches_file(&caps[2])
+            {
+                let algo = caps[1].to_lowercase().replace('-', "");
+                return Ok(Some(format!("{algo}:{}", &caps[3])));
+            }
+        } else if let Some((hash, name)) = line.split_once(char::is_whitespace)
+            && matches_file(name.trim())
+        {
+            return Ok(Some(format!("{}:{hash}", algo(hash)?)));
+        }
+    }
+    Ok(None)
+}
+
 pub fn verify_checksum_str(
     file_path: &Path,
     checksum: &str,
@@ -336,6 +716,34 @@ pub fn verify_checksum_str(
     Ok(())
 }
 
+/// Verifies a checksum that may contain several space-separated `algo:hash` pairs,
+/// e.g.: "sha256:abc123 sha512:def456". At least one of them must match unless
+/// `require_all` is set, in which case every one of them must match.
+pub fn verify_checksums_str(
+    file_path: &Path,
+    checksums: &str,
+    require_all: bool,
+    pr: Option<&dyn SingleReport>,
+) -> Result<()> {
+    let checksums = checksums.split_whitespace().collect::<Vec<_>>();
+    if checksums.len() <= 1 {
+        return verify_checksum_str(file_path, checksums.first().unwrap_or(&""), pr);
+    }
+    let mut errors = vec![];
+    for checksum in checksums {
+        match verify_checksum_str(file_path, checksum, pr) {
+            Ok(()) if !require_all => return Ok(()),
+            Ok(()) => {}
+            Err(err) if require_all => return Err(err),
+            Err(err) => errors.push(format!("{err:#}")),
+        }
+    }
+    if errors.is_empty() {
+        return Ok(());
+    }
+    bail!("none of the checksums matched:\n{}", errors.join("\n"))
+}
+
 /// Cleans a binary name by removing OS/arch suffixes and version numbers.
 /// This is useful when downloading single binaries that have platform-specific names.
 /// Executable extensions (.exe, .bat, .sh, etc.) are preserved.
@@ -510,8 +918,167 @@ fn clean_version_suffix(name: &str, tool_name: Option<&str>) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::toolset::ToolVersionOptions;
+    use crate::toolset::{ToolRequest, ToolSource, ToolVersionOptions};
     use indexmap::IndexMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_extract_filter() {
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            vec![],
+            flate2::Compression::default(),
+        ));
+        for f in [
+            "tool-1.0/bin/tool",
+            "tool-1.0/README.md",
+            "tool-1.0/share/man/man1/tool.1",
+            "tool-1.0/doc/guide.html",
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(0);
+            tar.append_data(&mut header, f, &b""[..]).unwrap();
+        }
+        let archive = tar.into_inner().unwrap().finish().unwrap();
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "extract_filter".to_string(),
+            r#"["**/*.md", "**/doc/**", "**/man/**"]"#.to_string(),
+        );
+        let exclude = extract_filter(&opts).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let tar_opts = file::TarOptions {
+            format: file::TarFormat::TarGz,
+            strip_components: 1,
+            exclude: exclude.as_ref(),
+            ..Default::default()
+        };
+        file::untar_reader(std::io::Cursor::new(archive), dir, &tar_opts).unwrap();
+        let files = file::recursive_ls(dir)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.strip_prefix(dir).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        assert_eq!(files, vec![PathBuf::from("bin/tool")]);
+        assert!(!dir.join("doc").exists());
+        assert!(!dir.join("share").exists());
+        assert!(extract_filter(&Default::default()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_arch_aliases() {
+        let mut opts = ToolVersionOptions::default();
+        assert!(arch_aliases(&opts).is_empty());
+        opts.opts.insert(
+            "arch_aliases".to_string(),
+            r#"{ x64 = "x86_64", arm64v8 = "aarch64" }"#.to_string(),
+        );
+        assert_eq!(
+            arch_aliases(&opts),
+            IndexMap::from([
+                ("x64".to_string(), "x86_64".to_string()),
+                ("arm64v8".to_string(), "aarch64".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_version_map() {
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "version_map".to_string(),
+            r#"{ "2024.01.15" = "1.0", "2024.02.01" = "1.1" }"#.to_string(),
+        );
+        let map = version_map(&opts);
+        assert_eq!(map.get("2024.01.15"), Some(&"1.0".to_string()));
+        assert_eq!(map.get("2024.02.01"), Some(&"1.1".to_string()));
+    }
+
+    #[test]
+    fn test_version_blocklist() {
+        let mut opts = ToolVersionOptions::default();
+        assert!(version_blocklist(&opts).is_empty());
+        opts.opts.insert(
+            "version_blocklist".to_string(),
+            r#"["1.2.3", "1.3.0"]"#.to_string(),
+        );
+        opts.opts.insert(
+            "version_blocklist_reason".to_string(),
+            r#"{ "1.2.3" = "CVE-2024-1234" }"#.to_string(),
+        );
+        assert_eq!(
+            version_blocklist(&opts),
+            IndexMap::from([
+                ("1.2.3".to_string(), Some("CVE-2024-1234".to_string())),
+                ("1.3.0".to_string(), None),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_asset_pattern_overrides() {
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "asset_pattern".to_string(),
+            "tool-{version}.tgz".to_string(),
+        );
+        opts.opts.insert(
+            "asset_pattern_overrides".to_string(),
+            r#"[
+                { version = ">=3.0", pattern = "tool-v{version}-new.tar.gz" },
+                { version = ">=2.0, <3.0", pattern = "tool-v{version}.tar.gz" },
+            ]"#
+            .to_string(),
+        );
+        assert_eq!(
+            asset_pattern_overrides(&opts).unwrap(),
+            vec![
+                (
+                    ">=3.0".to_string(),
+                    "tool-v{version}-new.tar.gz".to_string()
+                ),
+                (
+                    ">=2.0, <3.0".to_string(),
+                    "tool-v{version}.tar.gz".to_string()
+                ),
+            ]
+        );
+        let pattern = |v: &str| asset_pattern(&opts, v).unwrap().unwrap();
+        assert_eq!(pattern("3.1.0"), "tool-v{version}-new.tar.gz");
+        assert_eq!(pattern("2.5.0"), "tool-v{version}.tar.gz");
+        assert_eq!(pattern("1.9.0"), "tool-{version}.tgz");
+
+        opts.opts.insert(
+            "asset_pattern_overrides".to_string(),
+            r#"[{ version = ">=2.0" }]"#.to_string(),
+        );
+        assert!(asset_pattern(&opts, "2.0.0").is_err());
+    }
+
+    #[test]
+    fn test_version_transform() {
+        let mut opts = ToolVersionOptions::default();
+        assert!(version_transform(&opts).unwrap().is_none());
+        opts.opts.insert(
+            "version_transform".to_string(),
+            r#"{ pattern = "-(alpha|beta|rc)\\.", replacement = "-$1" }"#.to_string(),
+        );
+        let transform = version_transform(&opts).unwrap();
+        assert_eq!(
+            transform_version(transform.as_ref(), "1.2.0-rc.1".to_string()),
+            "1.2.0-rc1"
+        );
+        assert_eq!(
+            transform_version(transform.as_ref(), "1.2.0".to_string()),
+            "1.2.0"
+        );
+        opts.opts.insert(
+            "version_transform".to_string(),
+            r#"{ replacement = "" }"#.to_string(),
+        );
+        assert!(version_transform(&opts).is_err());
+    }
 
     #[test]
     fn test_clean_binary_name() {
@@ -707,6 +1274,57 @@ size = "5120"
         assert_eq!(size, Some("512".to_string()));
     }
 
+    #[test]
+    fn test_checksum_from_file() {
+        let sha = "a".repeat(64);
+        let gnu = format!(
+            "{sha}  tool-linux.tar.gz\n{}  *tool-macos.tar.gz\n",
+            "b".repeat(128)
+        );
+        assert_eq!(
+            checksum_from_file(&gnu, "tool-linux.tar.gz", None).unwrap(),
+            Some(format!("sha256:{sha}"))
+        );
+        assert_eq!(
+            checksum_from_file(&gnu, "tool-macos.tar.gz", Some("gnu")).unwrap(),
+            Some(format!("sha512:{}", "b".repeat(128)))
+        );
+        assert_eq!(checksum_from_file(&gnu, "other.zip", None).unwrap(), None);
+
+        let bsd = format!("SHA256 (dist/tool-linux.tar.gz) = {sha}\n");
+        assert_eq!(
+            checksum_from_file(&bsd, "tool-linux.tar.gz", None).unwrap(),
+            Some(format!("sha256:{sha}"))
+        );
+        assert_eq!(
+            checksum_from_file(&bsd, "tool-linux.tar.gz", Some("bsd")).unwrap(),
+            Some(format!("sha256:{sha}"))
+        );
+        assert!(checksum_from_file(&bsd, "tool-linux.tar.gz", Some("md5")).is_err());
+
+        assert_eq!(
+            checksum_from_file(&format!("{sha}\n"), "tool-linux.tar.gz", None).unwrap(),
+            Some(format!("sha256:{sha}"))
+        );
+    }
+
+    #[test]
+    fn test_verify_checksums_str() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("artifact");
+        std::fs::write(&path, "hello").unwrap();
+        let sha256 = "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let bad_sha512 = format!("sha512:{}", "0".repeat(128));
+
+        assert!(verify_checksums_str(&path, sha256, false, None).is_ok());
+        let both = format!("{bad_sha512} {sha256}");
+        assert!(verify_checksums_str(&path, &both, false, None).is_ok());
+        assert!(verify_checksums_str(&path, &both, true, None).is_err());
+        assert!(verify_checksums_str(&path, &bad_sha512, false, None).is_err());
+        let twice = format!("{sha256}  {sha256}");
+        assert!(verify_checksums_str(&path, &twice, true, None).is_ok());
+    }
+
     #[test]
     fn test_lookup_platform_key_bin_path() {
         let mut opts = IndexMap::new();
@@ -744,6 +1362,141 @@ bin_path = "."
         }
     }
 
+    #[test]
+    fn test_find_pkg_payload() {
+        let dir = tempfile::tempdir().unwrap();
+        let expanded = dir.path();
+        file::create_dir_all(expanded.join("tool.pkg/Payload/usr/local/bin")).unwrap();
+        file::create_dir_all(expanded.join("Resources")).unwrap();
+        assert_eq!(
+            find_pkg_payload(expanded, None).unwrap(),
+            expanded.join("tool.pkg/Payload")
+        );
+
+        file::create_dir_all(expanded.join("plugins.pkg/Payload")).unwrap();
+        let err = find_pkg_payload(expanded, None).unwrap_err();
+        assert!(err.to_string().contains("plugins.pkg/Payload"));
+        assert_eq!(
+            find_pkg_payload(expanded, Some("plugins.pkg/Payload")).unwrap(),
+            expanded.join("plugins.pkg/Payload")
+        );
+        assert!(find_pkg_payload(expanded, Some("missing.pkg/Payload")).is_err());
+    }
+
+    #[test]
+    fn test_install_file_dest() {
+        let dir = tempfile::tempdir().unwrap();
+        let opts = |name: &str| ToolVersionOptions {
+            opts: IndexMap::from([("install_file".to_string(), name.to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(
+            install_file_dest(dir.path(), &Default::default()).unwrap(),
+            None
+        );
+        assert_eq!(
+            install_file_dest(dir.path(), &opts("tool")).unwrap(),
+            Some(dir.path().join("bin/tool"))
+        );
+        assert!(dir.path().join("bin").is_dir());
+        assert!(install_file_dest(dir.path(), &opts("../tool")).is_err());
+    }
+
+    #[test]
+    fn test_add_shebang() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tool.py");
+        file::write(&script, "print('hi')\n").unwrap();
+        add_shebang(&script, "/usr/bin/env python3").unwrap();
+        add_shebang(&script, "/bin/sh").unwrap();
+        assert_eq!(
+            file::read_to_string(&script).unwrap(),
+            "#!/usr/bin/env python3\nprint('hi')\n"
+        );
+    }
+
+    #[test]
+    fn test_script_interpreter() {
+        let none = ToolVersionOptions::default();
+        assert_eq!(
+            script_interpreter(&none, Some("text/x-shellscript")),
+            Some("/bin/sh".to_string())
+        );
+        assert_eq!(
+            script_interpreter(&none, Some("text/x-python; charset=utf-8")),
+            Some("/usr/bin/env python3".to_string())
+        );
+        assert_eq!(
+            script_interpreter(&none, Some("application/octet-stream")),
+            None
+        );
+        assert_eq!(script_interpreter(&none, None), None);
+
+        let opts = ToolVersionOptions {
+            opts: IndexMap::from([(
+                "interpreter".to_string(),
+                "/usr/bin/env python3.12".to_string(),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            script_interpreter(&opts, Some("text/x-shellscript")),
+            Some("/usr/bin/env python3.12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_install_artifact_script_content_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tool.sh");
+        file::write(&script, "echo hi\n").unwrap();
+        let request = ToolRequest::new(
+            Arc::new("github:owner/tool".into()),
+            "1.0.0",
+            ToolSource::Argument,
+        )
+        .unwrap();
+        let mut tv = ToolVersion::new(request, "1.0.0".to_string());
+        tv.install_path = Some(dir.path().join("install"));
+        install_artifact(
+            &tv,
+            &script,
+            &Default::default(),
+            Some("text/x-shellscript"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            file::read_to_string(dir.path().join("install/bin/tool.sh")).unwrap(),
+            "#!/bin/sh\necho hi\n"
+        );
+    }
+
+    #[test]
+    fn test_lookup_platform_key_platform_overrides() {
+        let (os, arch) = platform_aliases().remove(0);
+        let mut opts = IndexMap::new();
+        opts.insert(
+            "platform_overrides".to_string(),
+            format!("[{os}-{arch}]\nasset_pattern = \"tool-win64.zip\"\n"),
+        );
+        opts.insert(
+            "platform_overrides_linux_riscv64_asset_pattern".to_string(),
+            "tool-riscv.tar.gz".to_string(),
+        );
+        let tool_opts = ToolVersionOptions {
+            opts,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            lookup_platform_key(&tool_opts, "asset_pattern"),
+            Some("tool-win64.zip".to_string())
+        );
+        let platforms = list_available_platforms_with_key(&tool_opts, "asset_pattern");
+        assert!(platforms.contains(&"linux-riscv64".to_string()));
+    }
+
     #[test]
     fn test_lookup_platform_key_bin() {
         let mut opts = IndexMap::new();
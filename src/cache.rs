@@ -4,22 +4,27 @@ use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use eyre::Result;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use eyre::{Result, bail, eyre};
 use flate2::Compression;
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
+use hkdf::Hkdf;
 use itertools::Itertools;
 use once_cell::sync::OnceCell;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use sha2::Sha256;
 use std::sync::LazyLock as Lazy;
 
 use crate::build_time::built_info;
 use crate::config::Settings;
+use crate::duration::DAILY;
 use crate::file::{display_path, modified_duration};
 use crate::hash::hash_to_str;
 use crate::rand::random_string;
-use crate::{dirs, file};
+use crate::{dirs, env, file};
 
 #[derive(Debug)]
 pub struct CacheManagerBuilder {
@@ -41,6 +46,9 @@ pub static BASE_CACHE_KEYS: Lazy<Vec<String>> = Lazy::new(|| {
     .collect()
 });
 
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
 impl CacheManagerBuilder {
     pub fn new(cache_file_path: impl AsRef<Path>) -> Self {
         let settings = Settings::get();
@@ -80,7 +88,12 @@ impl CacheManagerBuilder {
         let key = self.cache_key();
         let (base, ext) = file::split_file_name(&self.cache_file_path);
         let mut cache_file_path = self.cache_file_path;
-        cache_file_path.set_file_name(format!("{base}-{key}.{ext}"));
+        let enc = if env::MISE_CACHE_KEY.is_some() {
+            ".enc"
+        } else {
+            ""
+        };
+        cache_file_path.set_file_name(format!("{base}-{key}.{ext}{enc}"));
         CacheManager {
             cache_file_path,
             cache: Box::new(OnceCell::new()),
@@ -157,10 +170,22 @@ where
         Ok(val)
     }
 
+    /// Returns the cached value if the cache file is fresh, without fetching
+    pub fn get_cached(&self) -> Option<T> {
+        if !self.is_fresh() {
+            return None;
+        }
+        self.parse().ok()
+    }
+
     fn parse(&self) -> Result<T> {
         let path = &self.cache_file_path;
         trace!("reading {}", display_path(path));
-        let mut zlib = ZlibDecoder::new(File::open(path)?);
+        let mut bytes = file::read(path)?;
+        if let Some(key) = &*env::MISE_CACHE_KEY {
+            bytes = decrypt(key.as_bytes(), &bytes)?;
+        }
+        let mut zlib = ZlibDecoder::new(&bytes[..]);
         let mut bytes = Vec::new();
         zlib.read_to_end(&mut bytes)?;
         Ok(rmp_serde::from_slice(&bytes)?)
@@ -174,8 +199,13 @@ where
         let partial_path = self
             .cache_file_path
             .with_extension(format!("part-{}", random_string(8)));
-        let mut zlib = ZlibEncoder::new(File::create(&partial_path)?, Compression::fast());
+        let mut zlib = ZlibEncoder::new(vec![], Compression::fast());
         zlib.write_all(&rmp_serde::to_vec_named(&val)?[..])?;
+        let mut bytes = zlib.finish()?;
+        if let Some(key) = &*env::MISE_CACHE_KEY {
+            bytes = encrypt(key.as_bytes(), &bytes)?;
+        }
+        File::create(&partial_path)?.write_all(&bytes)?;
         file::rename(&partial_path, &self.cache_file_path)?;
 
         Ok(())
@@ -217,6 +247,80 @@ where
     }
 }
 
+/// AES-256-GCM cipher with a key derived from `MISE_CACHE_KEY` and `salt` with HKDF-SHA256
+fn cipher(key: &[u8], salt: &[u8]) -> Aes256Gcm {
+    let mut okm = [0; 32];
+    Hkdf::<Sha256>::new(Some(salt), key)
+        .expand(b"mise cache", &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Aes256Gcm::new_from_slice(&okm).expect("key is 32 bytes")
+}
+
+/// Encrypts `bytes` with a key derived from `key` and a random salt, prefixing the output with
+/// the salt and nonce used
+fn encrypt(key: &[u8], bytes: &[u8]) -> Result<Vec<u8>> {
+    let salt: [u8; SALT_LEN] = rand::random();
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let ciphertext = cipher(key, &salt)
+        .encrypt(Nonce::from_slice(&nonce), bytes)
+        .map_err(|_| eyre!("failed to encrypt cache file"))?;
+    Ok([&salt[..], &nonce[..], &ciphertext].concat())
+}
+
+fn decrypt(key: &[u8], bytes: &[u8]) -> Result<Vec<u8>> {
+    if bytes.len() < SALT_LEN + NONCE_LEN {
+        bail!("encrypted cache file is truncated");
+    }
+    let (salt, bytes) = bytes.split_at(SALT_LEN);
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    cipher(key, salt)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| eyre!("failed to decrypt cache file, MISE_CACHE_KEY may have changed"))
+}
+
+fn is_cache_file(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.ends_with(".msgpack.z") || path.ends_with(".msgpack.z.enc")
+}
+
+/// A cache file written by a `CacheManager`
+#[derive(Debug)]
+pub struct CacheEntry {
+    /// path of the cache file relative to the cache directory, e.g.: "node/remote_versions-abc12.msgpack.z"
+    pub key: String,
+    pub size: u64,
+    pub age: Duration,
+}
+
+/// Lists all cache files in `dir` which are younger than the `cache_max_age` setting
+pub fn list_entries(dir: &Path) -> Vec<CacheEntry> {
+    let max_age = Settings::get().cache_max_age_duration();
+    let mut entries = vec![];
+    for path in file::recursive_ls(dir).unwrap_or_default() {
+        if !is_cache_file(&path) {
+            continue;
+        }
+        let Ok(metadata) = path.metadata() else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|m| m.elapsed().ok())
+            .unwrap_or_default();
+        if max_age.is_some_and(|max_age| age > max_age) {
+            continue;
+        }
+        let key = path.strip_prefix(dir).unwrap_or(&path);
+        entries.push(CacheEntry {
+            key: key.to_string_lossy().to_string(),
+            size: metadata.len(),
+            age,
+        });
+    }
+    entries
+}
+
 pub(crate) struct PruneResults {
     pub(crate) size: u64,
     pub(crate) count: u64,
@@ -264,6 +368,35 @@ pub(crate) fn auto_prune() -> Result<()> {
     Ok(())
 }
 
+/// Deletes `.msgpack.z` files in `dir` which have not been written within the
+/// `cache_max_age` setting. Runs at most once a day per directory.
+pub(crate) fn evict_stale_msgpack(dir: &Path) -> Result<()> {
+    let Some(age) = Settings::get().cache_max_age_duration() else {
+        return Ok(());
+    };
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    let last_eviction_file = dir.join(".last_eviction");
+    if let Ok(Ok(modified)) = last_eviction_file.metadata().map(|m| m.modified())
+        && modified.elapsed().unwrap_or_default() < DAILY
+    {
+        return Ok(());
+    }
+    xx::file::touch_dir(&last_eviction_file)?;
+    for path in file::ls(dir)? {
+        if !is_cache_file(&path) {
+            continue;
+        }
+        let modified = path.metadata()?.modified()?;
+        if modified.elapsed().unwrap_or_default() > age {
+            debug!("evicting stale cache file {}", display_path(&path));
+            file::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn prune(dir: &Path, opts: &PruneOptions) -> Result<PruneResults> {
     let mut results = PruneResults { size: 0, count: 0 };
     let remove = |file: &Path| {
@@ -315,6 +448,70 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[tokio::test]
+    async fn test_evict_stale_msgpack() {
+        let _config = Config::get().await.unwrap();
+        let dir = dirs::CACHE.join("test-evict");
+        file::remove_all(&dir).unwrap();
+        file::create_dir_all(&dir).unwrap();
+        let stale = dir.join("stale-releases.msgpack.z");
+        let fresh = dir.join("fresh-releases.msgpack.z");
+        let other = dir.join("other.json");
+        for f in [&stale, &fresh, &other] {
+            file::write(f, "").unwrap();
+        }
+        let old = filetime::FileTime::from_unix_time(0, 0);
+        filetime::set_file_mtime(&stale, old).unwrap();
+        filetime::set_file_mtime(&other, old).unwrap();
+
+        evict_stale_msgpack(&dir).unwrap();
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(other.exists());
+
+        // only runs once a day
+        file::write(&stale, "").unwrap();
+        filetime::set_file_mtime(&stale, old).unwrap();
+        evict_stale_msgpack(&dir).unwrap();
+        assert!(stale.exists());
+        file::remove_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_entries() {
+        let _config = Config::get().await.unwrap();
+        let dir = dirs::CACHE.join("test-list-entries");
+        file::remove_all(&dir).unwrap();
+        file::create_dir_all(dir.join("node")).unwrap();
+        file::write(dir.join("node/remote_versions-abc12.msgpack.z"), "abc").unwrap();
+        file::write(dir.join("node/other.json"), "").unwrap();
+        let stale = dir.join("stale-releases.msgpack.z");
+        file::write(&stale, "").unwrap();
+        filetime::set_file_mtime(&stale, filetime::FileTime::from_unix_time(0, 0)).unwrap();
+
+        let entries = list_entries(&dir);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, "node/remote_versions-abc12.msgpack.z");
+        assert_eq!(entries[0].size, 3);
+        file::remove_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let encrypted = encrypt(b"secret", b"hello").unwrap();
+        assert_ne!(&encrypted[SALT_LEN + NONCE_LEN..], b"hello");
+        assert_eq!(decrypt(b"secret", &encrypted).unwrap(), b"hello");
+        // every file has its own salt
+        assert_ne!(
+            encrypt(b"secret", b"hello").unwrap()[..SALT_LEN],
+            encrypted[..SALT_LEN]
+        );
+
+        assert!(decrypt(b"other", &encrypted).is_err());
+        assert!(decrypt(b"secret", b"short").is_err());
+        assert!(is_cache_file(Path::new("releases-abc.msgpack.z.enc")));
+    }
+
     #[tokio::test]
     async fn test_cache() {
         let _config = Config::get().await.unwrap();
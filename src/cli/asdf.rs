@@ -44,6 +44,7 @@ async fn list_versions(config: &Arc<Config>, args: &[String]) -> Result<()> {
         return LsRemote {
             prefix: None,
             all: false,
+            json: false,
             plugin: args.get(3).map(|s| s.parse()).transpose()?,
         }
         .run()
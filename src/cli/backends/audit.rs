@@ -0,0 +1,230 @@
+use crate::backend::backend_type::BackendType;
+use crate::backend::static_helpers::lookup_platform_key;
+use crate::cli::args::BackendArg;
+use crate::config::{Config, Settings};
+use crate::toolset::ToolVersionOptions;
+use crate::ui::table;
+use eyre::Result;
+use tabled::Tabled;
+use xx::regex;
+
+/// Hosts that downloads are expected to come from
+const KNOWN_HOSTS: &[&str] = &[
+    "github.com",
+    "api.github.com",
+    "objects.githubusercontent.com",
+    "gitlab.com",
+    "codeberg.org",
+];
+
+/// Options that provide a checksum for the downloaded asset
+const CHECKSUM_OPTIONS: &[&str] = &["checksum", "checksum_url", "asset_hash_url"];
+
+/// Report risky configuration of the tools in the current config
+///
+/// Checks every configured tool for:
+/// - disabled signature verification (aqua cosign, slsa, minisign and attestations)
+/// - downloads from github, gitlab or http tools without a checksum in the options or lockfile
+/// - plain http:// URLs
+/// - TLS verification against the root certificates disabled by pinning `tls_fingerprint`
+/// - downloads from hosts other than github.com and gitlab.com, reported as info since
+///   self-hosted forges and mirrors are expected to use their own host
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsAudit {
+    /// Don't show table header
+    #[clap(long)]
+    pub no_header: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+enum Severity {
+    High,
+    Medium,
+    Info,
+}
+
+#[derive(Debug, Tabled)]
+struct Finding {
+    #[tabled(rename = "Severity")]
+    severity: Severity,
+    #[tabled(rename = "Tool")]
+    tool: String,
+    #[tabled(rename = "Issue")]
+    issue: String,
+}
+
+impl BackendsAudit {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let ts = config.get_toolset().await?;
+        let mut findings = vec![];
+        for tvl in ts.versions.values() {
+            let ba = &tvl.backend;
+            let has_lock_checksum = tvl
+                .versions
+                .iter()
+                .any(|tv| tv.lock_platforms.values().any(|p| p.checksum.is_some()));
+            findings.extend(audit_tool(ba, &ba.opts(), has_lock_checksum));
+        }
+        if findings.is_empty() {
+            info!("no issues found");
+            return Ok(());
+        }
+        findings.sort_by(|a, b| (a.severity, &a.tool).cmp(&(b.severity, &b.tool)));
+        let mut table = tabled::Table::new(findings);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        Ok(())
+    }
+}
+
+fn audit_tool(ba: &BackendArg, opts: &ToolVersionOptions, has_lock_checksum: bool) -> Vec<Finding> {
+    let mut findings = vec![];
+    let mut add = |severity, issue: String| {
+        findings.push(Finding {
+            severity,
+            tool: ba.short.clone(),
+            issue,
+        })
+    };
+    let backend_type = ba.backend_type();
+    if backend_type == BackendType::Aqua {
+        let aqua = &Settings::get().aqua;
+        for (name, enabled) in [
+            ("cosign", aqua.cosign),
+            ("slsa", aqua.slsa),
+            ("minisign", aqua.minisign),
+            ("github_attestations", aqua.github_attestations),
+        ] {
+            if !enabled {
+                add(
+                    Severity::High,
+                    format!("signature verification is disabled (aqua.{name} = false)"),
+                );
+            }
+        }
+    }
+    let urls = opts
+        .iter()
+        .flat_map(|(_, v)| find_urls(v))
+        .collect::<Vec<_>>();
+    for url in &urls {
+        if url.starts_with("http://") {
+            add(Severity::High, format!("insecure http URL: {url}"));
+        }
+    }
+    if opts.contains_key("tls_fingerprint") {
+        add(
+            Severity::Medium,
+            "root certificate verification is skipped for the pinned tls_fingerprint".to_string(),
+        );
+    }
+    if matches!(
+        backend_type,
+        BackendType::Github | BackendType::Gitlab | BackendType::Codeberg | BackendType::Http
+    ) && !has_lock_checksum
+        && !CHECKSUM_OPTIONS
+            .iter()
+            .any(|k| opts.contains_key(k) || lookup_platform_key(opts, k).is_some())
+    {
+        add(
+            Severity::Medium,
+            "no checksum in tool options or mise.lock".to_string(),
+        );
+    }
+    for host in urls
+        .iter()
+        .filter_map(|u| url::Url::parse(u).ok())
+        .filter_map(|u| u.host_str().map(|h| h.to_string()))
+    {
+        if !KNOWN_HOSTS.contains(&host.as_str()) {
+            add(
+                Severity::Info,
+                format!("downloads from unknown host {host}"),
+            );
+        }
+    }
+    findings
+}
+
+/// Finds the URLs in a tool option, which may be a nested table such as `platforms`
+fn find_urls(value: &str) -> Vec<String> {
+    regex!(r#"https?://[^\s"',}]+"#)
+        .find_iter(value)
+        .map(|m| m.as_str().to_string())
+        .collect()
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends audit</bold>
+    Severity  Tool                 Issue
+    high      http:mytool          insecure http URL: http://example.com/mytool.tar.gz
+    medium    github:myorg/tool    no checksum in tool options or mise.lock
+    info      http:mytool          downloads from unknown host example.com
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_tool() {
+        let ba = BackendArg::from("http:mytool");
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "url".to_string(),
+            "http://example.com/mytool.tar.gz".to_string(),
+        );
+        let findings = audit_tool(&ba, &opts, false);
+        let issues = findings
+            .iter()
+            .map(|f| (f.severity, f.issue.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            vec![
+                (
+                    Severity::High,
+                    "insecure http URL: http://example.com/mytool.tar.gz"
+                ),
+                (Severity::Medium, "no checksum in tool options or mise.lock"),
+                (Severity::Info, "downloads from unknown host example.com"),
+            ]
+        );
+
+        let ba = BackendArg::from("github:cli/cli");
+        assert!(audit_tool(&ba, &ToolVersionOptions::default(), true).is_empty());
+
+        let mut opts = ToolVersionOptions::default();
+        opts.opts.insert(
+            "asset_pattern".to_string(),
+            "cli-{version}-checksum-free.tar.gz".to_string(),
+        );
+        assert_eq!(audit_tool(&ba, &opts, false).len(), 1);
+        opts.opts.insert(
+            "checksum_url".to_string(),
+            "https://github.com/cli/cli/releases/download/v{version}/SHA256SUMS".to_string(),
+        );
+        assert!(audit_tool(&ba, &opts, false).is_empty());
+
+        let mut opts = ToolVersionOptions::default();
+        opts.opts
+            .insert("tls_fingerprint".to_string(), "d4:2f:3a:9c".to_string());
+        let findings = audit_tool(&ba, &opts, true);
+        assert_eq!(
+            findings
+                .iter()
+                .map(|f| (f.severity, f.issue.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(
+                Severity::Medium,
+                "root certificate verification is skipped for the pinned tls_fingerprint"
+            )]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::backend::Backend;
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::UnifiedGitBackend;
+use crate::cli::args::ToolArg;
+use crate::config::Config;
+use crate::parallel;
+use crate::toolset::outdated_info::is_outdated_version;
+use crate::toolset::{ToolVersion, ToolsetBuilder};
+use crate::ui::table;
+use eyre::Result;
+use indexmap::IndexMap;
+use serde_derive::Serialize;
+use tabled::Tabled;
+
+/// Check installed tools for newer versions
+///
+/// Unlike `mise outdated`, this only fetches the latest release for github and gitlab
+/// backends rather than listing every version, which makes it much cheaper for repos
+/// with a long release history. Other backends fall back to their latest stable version.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsCheckUpdates {
+    /// Tool(s) to check for updates
+    /// e.g.: node github:cli/cli
+    /// If not specified, all installed tools in global and local configs will be checked
+    #[clap(value_name = "TOOL", verbatim_doc_comment)]
+    pub tool: Vec<ToolArg>,
+
+    /// Output in JSON format
+    #[clap(short = 'J', long, verbatim_doc_comment)]
+    pub json: bool,
+
+    /// Don't show table header
+    #[clap(long)]
+    pub no_header: bool,
+}
+
+#[derive(Debug, Serialize, Tabled)]
+struct UpdateInfo {
+    #[tabled(rename = "Tool")]
+    #[serde(skip)]
+    name: String,
+    #[tabled(rename = "Current")]
+    current: String,
+    #[tabled(rename = "Latest")]
+    latest: String,
+}
+
+impl BackendsCheckUpdates {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let ts = ToolsetBuilder::new()
+            .with_args(&self.tool)
+            .build(&config)
+            .await?;
+        let tool_set = self
+            .tool
+            .iter()
+            .map(|t| t.ba.clone())
+            .collect::<HashSet<_>>();
+        let versions = ts
+            .list_current_installed_versions(&config)
+            .into_iter()
+            .filter(|(b, _)| tool_set.is_empty() || tool_set.contains(b.ba()))
+            .map(|(b, tv)| (config.clone(), b, tv))
+            .collect::<Vec<_>>();
+        let updates = parallel::parallel(versions, |(config, backend, tv)| async move {
+            Ok(check_update(&config, backend, tv).await)
+        })
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        match self.json {
+            true => self.display_json(updates)?,
+            false => self.display_table(updates)?,
+        }
+        Ok(())
+    }
+
+    fn display_table(&self, updates: Vec<UpdateInfo>) -> Result<()> {
+        if updates.is_empty() {
+            info!("All tools are up to date");
+            return Ok(());
+        }
+        let mut table = tabled::Table::new(updates);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        Ok(())
+    }
+
+    fn display_json(&self, updates: Vec<UpdateInfo>) -> Result<()> {
+        let mut map = IndexMap::new();
+        for u in updates {
+            map.insert(u.name.clone(), u);
+        }
+        miseprintln!("{}", serde_json::to_string_pretty(&map)?);
+        Ok(())
+    }
+}
+
+async fn check_update(
+    config: &Arc<Config>,
+    backend: Arc<dyn Backend>,
+    tv: ToolVersion,
+) -> Option<UpdateInfo> {
+    let ba = backend.ba();
+    let latest = match ba.backend_type() {
+        BackendType::Github | BackendType::Gitlab | BackendType::Codeberg => {
+            UnifiedGitBackend::from_arg((**ba).clone())
+                .latest_release_version(config)
+                .await
+        }
+        _ => backend.latest_stable_version(config).await,
+    };
+    let latest = match latest {
+        Ok(latest) => latest?,
+        Err(e) => {
+            warn!("Error checking {} for updates: {e:#}", ba.short);
+            return None;
+        }
+    };
+    if !is_outdated_version(&tv.version, &latest) {
+        return None;
+    }
+    Some(UpdateInfo {
+        name: ba.short.clone(),
+        current: tv.version,
+        latest,
+    })
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends check-updates</bold>
+    Tool           Current  Latest
+    github:cli/cli 2.60.0   2.62.0
+
+    $ <bold>mise backends check-updates github:cli/cli --json</bold>
+    {"github:cli/cli": {"current": "2.60.0", "latest": "2.62.0"}}
+"#
+);
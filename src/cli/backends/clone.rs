@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use crate::cli::backends::rename::rebuild_tools_in_doc;
+use crate::config::local_toml_config_path;
+use crate::file;
+use crate::file::display_path;
+use eyre::{Result, eyre};
+use toml_edit::{DocumentMut, Key};
+
+/// Copy a tool's configuration in mise.toml to a new tool name
+///
+/// The copy keeps the tool options and version of the original and is added right
+/// after it. Use this to install the same tool from two sources side by side, e.g.:
+/// the upstream repo and a patched fork, and track their versions independently.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsClone {
+    /// The tool to copy, e.g.: "github:org/tool"
+    src: String,
+
+    /// The name of the copy, e.g.: "github:fork-org/tool"
+    new: String,
+
+    /// The mise.toml file to update
+    ///
+    /// If not provided, the nearest local mise.toml will be used
+    #[clap(short, long)]
+    file: Option<PathBuf>,
+
+    /// Show what would be added without making any changes
+    #[clap(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl BackendsClone {
+    pub async fn run(self) -> Result<()> {
+        let path = self.file.unwrap_or_else(local_toml_config_path);
+        let mut doc: DocumentMut = file::read_to_string(&path)?.parse()?;
+        clone_tool_in_doc(&mut doc, &self.src, &self.new)
+            .map_err(|err| eyre!("{}: {err}", display_path(&path)))?;
+        miseprintln!("{}: {} → {}", display_path(&path), self.src, self.new);
+        if self.dry_run {
+            return Ok(());
+        }
+        file::write(&path, doc.to_string())?;
+        Ok(())
+    }
+}
+
+/// Adds a copy of `src` named `new` right after it in `[tools]`
+fn clone_tool_in_doc(doc: &mut DocumentMut, src: &str, new: &str) -> Result<()> {
+    rebuild_tools_in_doc(doc, src, new, |key, item| {
+        if key.get() != src {
+            return vec![(key, item)];
+        }
+        let mut copy = item.clone();
+        // comments above the original stay with it
+        if let Some(table) = copy.as_table_mut() {
+            table.decor_mut().clear();
+        }
+        vec![(key, item), (Key::new(new), copy)]
+    })
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends clone github:org/tool github:fork-org/tool</bold>
+    mise.toml: github:org/tool → github:fork-org/tool
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_tool_in_doc() {
+        let mut doc: DocumentMut = r#"
+[tools]
+node = "22"
+# upstream
+"github:org/tool" = { version = "1.2.3", bin = "tool" }
+jq = "1.7"
+"#
+        .parse()
+        .unwrap();
+        clone_tool_in_doc(&mut doc, "github:org/tool", "github:fork-org/tool").unwrap();
+        assert_eq!(
+            doc.to_string(),
+            r#"
+[tools]
+node = "22"
+# upstream
+"github:org/tool" = { version = "1.2.3", bin = "tool" }
+"github:fork-org/tool" = { version = "1.2.3", bin = "tool" }
+jq = "1.7"
+"#
+        );
+        assert!(clone_tool_in_doc(&mut doc, "github:missing/tool", "github:x/tool").is_err());
+        assert!(clone_tool_in_doc(&mut doc, "node", "jq").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+use crate::config::Config;
+use crate::toolset::SCRIPT_OPTIONS;
+use eyre::Result;
+use indexmap::{IndexMap, IndexSet};
+
+/// Show the order tools in the current config are installed in
+///
+/// A tool is installed after the tools it depends on, either because its backend
+/// needs them (e.g.: cargo tools need rust) or because one of its hooks
+/// (postinstall, post_download_hook, pre_extract_script or post_install_script)
+/// refers to another configured tool by name.
+///
+/// Prints a tree of each tool and the tools installed after it, or a graph in
+/// the DOT format with --dot.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsGraph {
+    /// Output the graph in the DOT format, e.g.: to render it with graphviz
+    #[clap(long)]
+    dot: bool,
+}
+
+impl BackendsGraph {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let ts = config.get_toolset().await?;
+        let tools = ts
+            .versions
+            .values()
+            .map(|tvl| tvl.backend.clone())
+            .collect::<Vec<_>>();
+        let mut graph: IndexMap<String, IndexSet<String>> = IndexMap::new();
+        for ba in &tools {
+            let mut deps: IndexSet<String> = match ba.backend() {
+                Ok(backend) => backend
+                    .get_all_dependencies(false)?
+                    .into_iter()
+                    .map(|dep| dep.short)
+                    .collect(),
+                Err(err) => {
+                    debug!("{ba}: {err:#}");
+                    Default::default()
+                }
+            };
+            let opts = ba.opts();
+            for other in tools.iter().filter(|other| other.short != ba.short) {
+                let referenced = SCRIPT_OPTIONS
+                    .iter()
+                    .filter_map(|key| opts.get(key))
+                    .any(|script| script_references(script, &other.short, &other.tool_name));
+                if referenced {
+                    deps.insert(other.short.clone());
+                }
+            }
+            graph.insert(ba.short.clone(), deps);
+        }
+        match self.dot {
+            true => miseprint!("{}", render_dot(&graph))?,
+            false => miseprint!("{}", render_tree(&graph))?,
+        }
+        Ok(())
+    }
+}
+
+/// Whether `script` runs the tool, matched by its short name or the last part of its
+/// name, e.g.: "rust" or "ripgrep" for "aqua:BurntSushi/ripgrep"
+fn script_references(script: &str, short: &str, tool_name: &str) -> bool {
+    let bin = tool_name.rsplit('/').next().unwrap_or(tool_name);
+    let is_separator = |c: char| c.is_whitespace() || ";&|()\"'".contains(c);
+    script
+        .split(is_separator)
+        .any(|word| word == short || word == bin)
+}
+
+/// Edges go from a dependency to the tools that depend on it, in install order
+fn render_dot(graph: &IndexMap<String, IndexSet<String>>) -> String {
+    let mut out = String::from("digraph tools {\n");
+    for (tool, deps) in graph {
+        if deps.is_empty() {
+            out.push_str(&format!("  \"{tool}\";\n"));
+        }
+        for dep in deps {
+            out.push_str(&format!("  \"{dep}\" -> \"{tool}\";\n"));
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Renders each tool without dependencies with the tools installed after it below it
+fn render_tree(graph: &IndexMap<String, IndexSet<String>>) -> String {
+    let mut nodes: IndexSet<&str> = IndexSet::new();
+    let mut dependents: IndexMap<&str, Vec<&str>> = IndexMap::new();
+    for (tool, deps) in graph {
+        for dep in deps {
+            nodes.insert(dep);
+            dependents.entry(dep).or_default().push(tool);
+        }
+        nodes.insert(tool);
+    }
+    let mut out = String::new();
+    let mut printed = IndexSet::new();
+    let roots = nodes
+        .iter()
+        .filter(|node| graph.get(**node).is_none_or(|deps| deps.is_empty()))
+        .copied()
+        .collect::<Vec<_>>();
+    for root in roots {
+        render_node(
+            root,
+            "",
+            None,
+            &dependents,
+            &mut vec![],
+            &mut printed,
+            &mut out,
+        );
+    }
+    // tools that only depend on each other
+    for node in nodes {
+        if !printed.contains(node) {
+            render_node(
+                node,
+                "",
+                None,
+                &dependents,
+                &mut vec![],
+                &mut printed,
+                &mut out,
+            );
+        }
+    }
+    out
+}
+
+fn render_node<'a>(
+    node: &'a str,
+    prefix: &str,
+    is_last: Option<bool>,
+    dependents: &IndexMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    printed: &mut IndexSet<&'a str>,
+    out: &mut String,
+) {
+    let (branch, child_prefix) = match is_last {
+        None => ("", String::new()),
+        Some(true) => ("└── ", format!("{prefix}    ")),
+        Some(false) => ("├── ", format!("{prefix}│   ")),
+    };
+    if path.contains(&node) {
+        out.push_str(&format!("{prefix}{branch}{node} (cycle)\n"));
+        return;
+    }
+    out.push_str(&format!("{prefix}{branch}{node}\n"));
+    printed.insert(node);
+    let children = dependents.get(node).cloned().unwrap_or_default();
+    path.push(node);
+    for (i, child) in children.iter().enumerate() {
+        let is_last = i == children.len() - 1;
+        render_node(
+            child,
+            &child_prefix,
+            Some(is_last),
+            dependents,
+            path,
+            printed,
+            out,
+        );
+    }
+    path.pop();
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends graph</bold>
+    rust
+    └── cargo:ripgrep
+        └── github:org/tool
+    node
+    └── npm:prettier
+
+    $ <bold>mise backends graph --dot | dot -Tsvg > tools.svg</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_graph() {
+        let graph = IndexMap::from([
+            ("rust".to_string(), IndexSet::new()),
+            (
+                "cargo:ripgrep".to_string(),
+                IndexSet::from(["rust".to_string()]),
+            ),
+            (
+                "github:org/tool".to_string(),
+                IndexSet::from(["rust".to_string(), "cargo:ripgrep".to_string()]),
+            ),
+            ("node".to_string(), IndexSet::new()),
+        ]);
+        assert_eq!(
+            render_tree(&graph),
+            "rust\n├── cargo:ripgrep\n│   └── github:org/tool\n└── github:org/tool\nnode\n"
+        );
+        assert_eq!(
+            render_dot(&graph),
+            "digraph tools {\n  \"rust\";\n  \"rust\" -> \"cargo:ripgrep\";\n  \"rust\" -> \"github:org/tool\";\n  \"cargo:ripgrep\" -> \"github:org/tool\";\n  \"node\";\n}\n"
+        );
+        assert!(script_references(
+            "rg --version && ripgrep -h",
+            "aqua:BurntSushi/ripgrep",
+            "BurntSushi/ripgrep"
+        ));
+        assert!(!script_references("cargo build", "rust", "rust"));
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Instant;
+
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::UnifiedGitBackend;
+use crate::config::Config;
+use crate::http::error_code;
+use crate::parallel;
+use crate::ui::table;
+use eyre::{Result, bail};
+use indexmap::IndexMap;
+use tabled::Tabled;
+
+/// Check that the APIs used by configured backends can be reached
+///
+/// Makes a lightweight authenticated request to the API of every github and gitlab
+/// backend used in the current config and reports the HTTP status and latency.
+/// Useful as a pre-flight check in CI before running `mise install`.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsHealthCheck {
+    /// Don't show table header
+    #[clap(long)]
+    pub no_header: bool,
+}
+
+#[derive(Debug, Tabled)]
+struct HealthCheck {
+    #[tabled(rename = "Backend")]
+    backend: BackendType,
+    #[tabled(rename = "URL")]
+    url: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Latency")]
+    latency: String,
+    #[tabled(skip)]
+    ok: bool,
+}
+
+impl BackendsHealthCheck {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let ts = config.get_toolset().await?;
+        let mut backends = IndexMap::new();
+        for ba in ts.versions.keys() {
+            let backend_type = ba.backend_type();
+            if !matches!(
+                backend_type,
+                BackendType::Github | BackendType::Gitlab | BackendType::Codeberg
+            ) {
+                continue;
+            }
+            let backend = UnifiedGitBackend::from_arg((**ba).clone());
+            backends
+                .entry(backend.health_check_url())
+                .or_insert((backend_type, backend));
+        }
+        if backends.is_empty() {
+            info!("no github or gitlab backends are configured");
+            return Ok(());
+        }
+        let checks = parallel::parallel(
+            backends.into_iter().collect(),
+            |(url, (backend_type, backend))| async move {
+                let start = Instant::now();
+                let result = backend.health_check().await;
+                let latency = format!("{}ms", start.elapsed().as_millis());
+                let (status, ok) = match result {
+                    Ok(code) => (code.to_string(), true),
+                    Err(err) => {
+                        debug!("{url}: {err:#}");
+                        match error_code(&err) {
+                            Some(code) => (code.to_string(), false),
+                            None => ("unreachable".to_string(), false),
+                        }
+                    }
+                };
+                Ok(HealthCheck {
+                    backend: backend_type,
+                    url,
+                    status,
+                    latency,
+                    ok,
+                })
+            },
+        )
+        .await?;
+        let failed = checks.iter().filter(|c| !c.ok).count();
+        let mut table = tabled::Table::new(checks);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        if failed > 0 {
+            bail!("{failed} backend(s) failed the health check");
+        }
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends health-check</bold>
+    Backend  URL                                        Status  Latency
+    github   https://api.github.com/rate_limit          200     112ms
+    gitlab   https://gitlab.example.com/api/v4/version  401     87ms
+"#
+);
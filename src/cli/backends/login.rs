@@ -0,0 +1,185 @@
+use std::time::{Duration, Instant};
+
+use crate::file::display_path;
+use crate::forge_tokens;
+use crate::http::HTTP;
+use eyre::{Result, bail};
+use serde_derive::Deserialize;
+
+/// Log in to a github, gitlab or codeberg host with the OAuth2 device flow
+///
+/// Shows a URL and a code to enter there, waits for the authorization and saves the
+/// resulting token to ~/.config/mise/<forge>_tokens.toml keyed by hostname.
+/// Saved tokens are used when GITHUB_TOKEN/GITLAB_TOKEN/MISE_CODEBERG_TOKEN or the
+/// enterprise token variables are not set.
+///
+/// The OAuth application must have the device flow enabled.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsLogin {
+    /// The forge and host to log in to, e.g.: "github:github.com" or "gitlab:gitlab.example.com"
+    ///
+    /// The host defaults to github.com, gitlab.com or codeberg.org. Use "codeberg:<host>"
+    /// for other Forgejo instances.
+    #[clap(value_name = "FORGE:HOST")]
+    target: String,
+
+    /// The client ID of the OAuth application
+    #[clap(long)]
+    client_id: String,
+
+    /// The scopes to request, defaults to none for github, "read_api" for gitlab and
+    /// "read:repository" for codeberg
+    #[clap(long)]
+    scope: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeviceCode {
+    device_code: String,
+    user_code: String,
+    verification_uri: String,
+    expires_in: u64,
+    #[serde(default = "default_interval")]
+    interval: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+fn default_interval() -> u64 {
+    5
+}
+
+impl BackendsLogin {
+    pub async fn run(self) -> Result<()> {
+        let (forge, host) = parse_target(&self.target)?;
+        let (device_url, token_url, default_scope) = match forge {
+            "github" => (
+                format!("https://{host}/login/device/code"),
+                format!("https://{host}/login/oauth/access_token"),
+                "",
+            ),
+            "codeberg" => (
+                format!("https://{host}/login/oauth/device/code"),
+                format!("https://{host}/login/oauth/access_token"),
+                "read:repository",
+            ),
+            _ => (
+                format!("https://{host}/oauth/authorize_device"),
+                format!("https://{host}/oauth/token"),
+                "read_api",
+            ),
+        };
+        let scope = self.scope.as_deref().unwrap_or(default_scope);
+        let code: DeviceCode = HTTP
+            .post_form_json(
+                &device_url,
+                &[("client_id", self.client_id.as_str()), ("scope", scope)],
+            )
+            .await?;
+        miseprintln!(
+            "Open {} and enter the code: {}",
+            code.verification_uri,
+            code.user_code
+        );
+
+        let deadline = Instant::now() + Duration::from_secs(code.expires_in);
+        let mut interval = code.interval;
+        let token = loop {
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+            if Instant::now() > deadline {
+                bail!("the device code expired before it was authorized");
+            }
+            let resp: TokenResponse = HTTP
+                .post_form_json(
+                    &token_url,
+                    &[
+                        ("client_id", self.client_id.as_str()),
+                        ("device_code", code.device_code.as_str()),
+                        ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                    ],
+                )
+                .await?;
+            match (resp.access_token, resp.error.as_deref()) {
+                (Some(token), _) => break token,
+                (None, Some("authorization_pending")) => continue,
+                // the server asks to poll less often
+                (None, Some("slow_down")) => interval += 5,
+                (None, err) => bail!(
+                    "failed to log in to {host}: {}",
+                    resp.error_description
+                        .as_deref()
+                        .or(err)
+                        .unwrap_or("no token returned")
+                ),
+            }
+        };
+        forge_tokens::save(forge, host, &token)?;
+        info!(
+            "logged in to {host}, token saved to {}",
+            display_path(forge_tokens::path(forge))
+        );
+        Ok(())
+    }
+}
+
+/// Splits "github:ghe.example.com" into the forge and host, "forgejo" is an alias of "codeberg"
+pub(super) fn parse_target(target: &str) -> Result<(&str, &str)> {
+    let (forge, host) = match target.split_once(':') {
+        Some((forge, host)) => (forge, host.trim_matches('/')),
+        None => (target, ""),
+    };
+    let forge = match forge {
+        "forgejo" => "codeberg",
+        forge => forge,
+    };
+    let host = match (forge, host) {
+        ("github", "") => "github.com",
+        ("gitlab", "") => "gitlab.com",
+        ("codeberg", "") => "codeberg.org",
+        ("github" | "gitlab" | "codeberg", host) => host,
+        _ => bail!("unsupported forge: {forge}, expected github, gitlab or codeberg"),
+    };
+    Ok((forge, host))
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends login github:github.com --client-id Iv1.abc123</bold>
+    Open https://github.com/login/device and enter the code: WDJB-MJHT
+    mise logged in to github.com, token saved to ~/.config/mise/github_tokens.toml
+
+    $ <bold>mise backends login gitlab:gitlab.example.com --client-id 0a1b2c3d</bold>
+    $ <bold>mise backends login codeberg:forgejo.example.com --client-id 4e5f6a7b</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        assert_eq!(parse_target("github").unwrap(), ("github", "github.com"));
+        assert_eq!(parse_target("gitlab:").unwrap(), ("gitlab", "gitlab.com"));
+        assert_eq!(
+            parse_target("github:ghe.example.com").unwrap(),
+            ("github", "ghe.example.com")
+        );
+        assert_eq!(
+            parse_target("codeberg").unwrap(),
+            ("codeberg", "codeberg.org")
+        );
+        assert_eq!(
+            parse_target("forgejo:forgejo.example.com/").unwrap(),
+            ("codeberg", "forgejo.example.com")
+        );
+        assert!(parse_target("gitea:gitea.example.com").is_err());
+    }
+}
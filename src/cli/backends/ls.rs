@@ -1,14 +1,38 @@
 use crate::backend::backend_type::BackendType;
+use crate::backend::github::UnifiedGitBackend;
+use crate::backend::{self, ABackend};
+use crate::config::Config;
 use eyre::Result;
+use indexmap::IndexMap;
+use serde_derive::Serialize;
 use strum::IntoEnumIterator;
 
 /// List built-in backends
+///
+/// With --json, lists the backends of configured and installed tools instead
+/// along with their installed versions and number of cached remote versions.
 #[derive(Debug, clap::Args)]
 #[clap(visible_alias = "list", after_long_help = AFTER_LONG_HELP, verbatim_doc_comment)]
-pub struct BackendsLs {}
+pub struct BackendsLs {
+    /// Output configured and installed backends in JSON format
+    #[clap(long, short = 'J')]
+    pub json: bool,
+}
+
+#[derive(Serialize)]
+struct BackendJson {
+    name: String,
+    r#type: String,
+    api_url: Option<String>,
+    installed_versions: Vec<String>,
+    cached_versions_count: usize,
+}
 
 impl BackendsLs {
-    pub fn run(self) -> Result<()> {
+    pub async fn run(self) -> Result<()> {
+        if self.json {
+            return self.run_json().await;
+        }
         let mut backends = BackendType::iter().collect::<Vec<BackendType>>();
         backends.retain(|f| !matches!(f, BackendType::Unknown));
 
@@ -17,6 +41,47 @@ impl BackendsLs {
         }
         Ok(())
     }
+
+    async fn run_json(&self) -> Result<()> {
+        let config = Config::get().await?;
+        let mut backends: IndexMap<String, ABackend> = IndexMap::new();
+        for ba in config.get_tool_request_set().await?.list_tools() {
+            if let Some(backend) = backend::get(ba) {
+                backends.insert(ba.full(), backend);
+            }
+        }
+        for backend in backend::list() {
+            if !backend.list_installed_versions().is_empty() {
+                backends.entry(backend.ba().full()).or_insert(backend);
+            }
+        }
+        let mut out = vec![];
+        for (name, backend) in backends {
+            let backend_type = backend.get_type();
+            let api_url = match backend_type {
+                BackendType::Github | BackendType::Gitlab | BackendType::Codeberg => {
+                    Some(UnifiedGitBackend::from_arg((**backend.ba()).clone()).api_url())
+                }
+                _ => None,
+            };
+            let cached_versions_count = backend
+                .get_remote_version_cache()
+                .lock()
+                .await
+                .get_cached()
+                .map(|v| v.len())
+                .unwrap_or_default();
+            out.push(BackendJson {
+                name,
+                r#type: backend_type.to_string(),
+                api_url,
+                installed_versions: backend.list_installed_versions(),
+                cached_versions_count,
+            });
+        }
+        miseprintln!("{}", serde_json::to_string_pretty(&out)?);
+        Ok(())
+    }
 }
 
 static AFTER_LONG_HELP: &str = color_print::cstr!(
@@ -35,5 +100,16 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     spm
     ubi
     vfox
+
+    $ <bold>mise backends ls --json</bold>
+    [
+      {
+        "name": "github:cli/cli",
+        "type": "github",
+        "api_url": "https://api.github.com",
+        "installed_versions": ["2.62.0"],
+        "cached_versions_count": 30
+      }
+    ]
 "#
 );
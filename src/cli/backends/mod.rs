@@ -1,7 +1,20 @@
 use clap::Subcommand;
 use eyre::Result;
 
+mod audit;
+mod check_updates;
+mod clone;
+mod graph;
+mod health_check;
+mod import;
+mod login;
 mod ls;
+mod prune;
+mod rename;
+mod search;
+mod show;
+mod status;
+mod validate_config;
 
 #[derive(Debug, clap::Args)]
 #[clap(about = "Manage backends", visible_alias = "b", aliases = ["backend", "backend-list"])]
@@ -12,21 +25,47 @@ pub struct Backends {
 
 #[derive(Debug, Subcommand)]
 enum Commands {
+    Audit(audit::BackendsAudit),
+    CheckUpdates(check_updates::BackendsCheckUpdates),
+    Clone(clone::BackendsClone),
+    Graph(graph::BackendsGraph),
+    HealthCheck(health_check::BackendsHealthCheck),
+    Import(import::BackendsImport),
+    Login(login::BackendsLogin),
     Ls(ls::BackendsLs),
+    Prune(prune::BackendsPrune),
+    Rename(rename::BackendsRename),
+    Search(search::BackendsSearch),
+    Show(Box<show::BackendsShow>),
+    Status(status::BackendsStatus),
+    ValidateConfig(validate_config::BackendsValidateConfig),
 }
 
 impl Commands {
-    pub fn run(self) -> Result<()> {
+    pub async fn run(self) -> Result<()> {
         match self {
-            Self::Ls(cmd) => cmd.run(),
+            Self::Audit(cmd) => cmd.run().await,
+            Self::CheckUpdates(cmd) => cmd.run().await,
+            Self::Clone(cmd) => cmd.run().await,
+            Self::Graph(cmd) => cmd.run().await,
+            Self::HealthCheck(cmd) => cmd.run().await,
+            Self::Import(cmd) => cmd.run().await,
+            Self::Login(cmd) => cmd.run().await,
+            Self::Ls(cmd) => cmd.run().await,
+            Self::Prune(cmd) => cmd.run().await,
+            Self::Rename(cmd) => cmd.run().await,
+            Self::Search(cmd) => cmd.run().await,
+            Self::Show(cmd) => cmd.run().await,
+            Self::Status(cmd) => cmd.run().await,
+            Self::ValidateConfig(cmd) => cmd.run().await,
         }
     }
 }
 
 impl Backends {
     pub async fn run(self) -> Result<()> {
-        let cmd = self.command.unwrap_or(Commands::Ls(ls::BackendsLs {}));
+        let cmd = self.command.unwrap_or(Commands::Ls(ls::BackendsLs { json: false }));
 
-        cmd.run()
+        cmd.run().await
     }
 }
@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::UnifiedGitBackend;
+use crate::cli::cache::prune::bytes_str;
+use crate::config::Config;
+use crate::dirs;
+use crate::file::{self, display_path};
+use eyre::Result;
+use heck::ToKebabCase;
+
+/// Remove cached release data for github/gitlab tools no longer in mise.toml
+///
+/// Compares the tools in all config files with the github and gitlab entries
+/// in the cache directory and removes any that no tool references anymore.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsPrune {
+    /// Just show what would be pruned
+    #[clap(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl BackendsPrune {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let tools = config.get_tool_request_set().await?.list_tools();
+        let keep_dirs = tools
+            .iter()
+            .filter_map(|ba| ba.cache_path.file_name())
+            .map(|f| f.to_string_lossy().to_string())
+            .collect();
+        let mut repo_keys = HashSet::new();
+        for ba in tools.iter().copied() {
+            let repo = ba.tool_name();
+            repo_keys.insert(repo.to_kebab_case());
+            if matches!(
+                ba.backend_type(),
+                BackendType::Github | BackendType::Gitlab | BackendType::Codeberg
+            ) {
+                let api_url = UnifiedGitBackend::from_arg((**ba).clone()).api_url();
+                repo_keys.insert(crate::github::repo_key(&api_url, &repo));
+            }
+        }
+
+        let (mut count, mut size) = (0, 0);
+        for path in orphaned_cache_paths(&dirs::CACHE, &keep_dirs, &repo_keys)? {
+            let files = match path.is_dir() {
+                true => file::recursive_ls(&path)?.into_iter().collect(),
+                false => vec![path.clone()],
+            };
+            count += files.len();
+            size += files
+                .iter()
+                .filter_map(|f| f.metadata().ok())
+                .map(|m| m.len())
+                .sum::<u64>();
+            if self.dry_run {
+                info!("pruning {}", display_path(&path));
+            } else {
+                debug!("pruning {}", display_path(&path));
+                file::remove_file_or_dir(&path)?;
+            }
+        }
+        let size = bytes_str(size);
+        info!("backends pruned {count} files, {size}");
+        Ok(())
+    }
+}
+
+/// Returns the github/gitlab cache entries in `cache_dir` which are not used by any tool.
+///
+/// These are the per-tool directories, e.g.: `github-cli-cli/`, and the release/tag
+/// caches in `github/` and `gitlab/`, which are named after the repo, see `repo_key`.
+fn orphaned_cache_paths(
+    cache_dir: &Path,
+    keep_dirs: &HashSet<String>,
+    repo_keys: &HashSet<String>,
+) -> Result<Vec<PathBuf>> {
+    let mut orphans = vec![];
+    for subdir in file::dir_subdirs(cache_dir)? {
+        if (subdir.starts_with("github-") || subdir.starts_with("gitlab-"))
+            && !keep_dirs.contains(&subdir)
+        {
+            orphans.push(cache_dir.join(subdir));
+        }
+    }
+    for forge in ["github", "gitlab"] {
+        for path in file::ls(&cache_dir.join(forge))? {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            if path.is_file() && !repo_key(&name).is_some_and(|key| repo_keys.contains(key)) {
+                orphans.push(path);
+            }
+        }
+    }
+    Ok(orphans)
+}
+
+/// The repo a release cache file belongs to, e.g.: `cli-cli` for
+/// `cli-cli-releases-abc12.msgpack.z` or `cli-cli@v-2-0-0-abc12.msgpack.z`
+fn repo_key(file_name: &str) -> Option<&str> {
+    let (name, _ext) = file_name.split_once('.')?;
+    let (name, _cache_key) = name.rsplit_once('-')?;
+    match name.split_once('@') {
+        Some((key, _)) => Some(key),
+        None => name
+            .strip_suffix("-releases")
+            .or_else(|| name.strip_suffix("-tags")),
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends prune --dry-run</bold>
+    mise pruning ~/.cache/mise/github-old-org-tool
+    mise pruning ~/.cache/mise/github/old-org-tool-releases-a1b2c3.msgpack.z
+    mise backends pruned 3 files, 12.4 KiB
+
+    $ <bold>mise backends prune</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orphaned_cache_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        for d in ["github-cli-cli", "github-old-org-tool", "node", "github"] {
+            file::create_dir_all(dir.join(d)).unwrap();
+        }
+        for f in [
+            "github/cli-cli-releases-abc12.msgpack.z",
+            "github/cli-cli@v-2-0-0-abc12.msgpack.z",
+            "github/cli-cli@1a2b3c-response-abc12.msgpack.z",
+            "github/cli-cli-extra-tags-abc12.msgpack.z",
+            "github/old-org-tool-releases-abc12.msgpack.z",
+            "github/1a2b3c-response-abc12.msgpack.z",
+        ] {
+            file::write(dir.join(f), "").unwrap();
+        }
+        let keep_dirs = HashSet::from(["github-cli-cli".to_string()]);
+        let repo_keys = HashSet::from(["cli-cli".to_string()]);
+        let mut orphans = orphaned_cache_paths(dir, &keep_dirs, &repo_keys).unwrap();
+        orphans.sort();
+        assert_eq!(
+            orphans,
+            vec![
+                dir.join("github/1a2b3c-response-abc12.msgpack.z"),
+                dir.join("github/cli-cli-extra-tags-abc12.msgpack.z"),
+                dir.join("github/old-org-tool-releases-abc12.msgpack.z"),
+                dir.join("github-old-org-tool"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::BackendArg;
+use crate::config::local_toml_config_path;
+use crate::file;
+use crate::file::display_path;
+use crate::lockfile::Lockfile;
+use crate::toolset::install_state;
+use eyre::{Report, Result, bail, eyre};
+use itertools::Itertools;
+use toml_edit::{DocumentMut, Item, Key};
+
+/// Rename a tool in mise.toml, mise.lock and the installs directory
+///
+/// Use this when a tool's repository moves, e.g.: after an org rename.
+/// The tool options in mise.toml are kept as-is and installed versions
+/// are moved so they don't need to be reinstalled.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsRename {
+    /// The current tool name, e.g.: "github:old-org/tool"
+    old: String,
+
+    /// The new tool name, e.g.: "github:new-org/tool"
+    new: String,
+
+    /// The mise.toml file to update
+    ///
+    /// If not provided, the nearest local mise.toml will be used
+    #[clap(short, long)]
+    file: Option<PathBuf>,
+
+    /// Show what would be renamed without making any changes
+    #[clap(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl BackendsRename {
+    pub async fn run(self) -> Result<()> {
+        let old_ba = BackendArg::from(self.old.as_str());
+        let new_ba = BackendArg::from(self.new.as_str());
+        let path = self.file.unwrap_or_else(local_toml_config_path);
+
+        // check everything up front so nothing is left half renamed
+        ensure_writable(&path)?;
+        let toml = file::read_to_string(&path)?;
+        let mut doc: DocumentMut = toml.parse()?;
+        rename_tool_in_doc(&mut doc, &self.old, &self.new)
+            .map_err(|err| eyre!("{}: {err}", display_path(&path)))?;
+        let lockfile_path = path.with_extension("lock");
+        let _lock = Lockfile::lock(&lockfile_path).await?;
+        let mut lockfile = Lockfile::read(&lockfile_path)?;
+        if lockfile.tools().contains_key(&new_ba.short) {
+            bail!(
+                "{} already has an entry for {}",
+                display_path(&lockfile_path),
+                self.new
+            );
+        }
+        let rename_lockfile = lockfile.rename_tool(&old_ba.short, &new_ba);
+        let lockfile_content = match rename_lockfile {
+            true => {
+                ensure_writable(&lockfile_path)?;
+                Some(file::read_to_string(&lockfile_path)?)
+            }
+            false => None,
+        };
+        let rename_installs = old_ba.installs_path.exists();
+        if rename_installs {
+            if new_ba.installs_path.exists() {
+                bail!("{} already exists", display_path(&new_ba.installs_path));
+            }
+            ensure_writable(old_ba.installs_path.parent().unwrap())?;
+        }
+
+        miseprintln!("{}: {} → {}", display_path(&path), self.old, self.new);
+        if rename_lockfile {
+            miseprintln!(
+                "{}: {} → {}",
+                display_path(&lockfile_path),
+                old_ba.short,
+                new_ba.short
+            );
+        }
+        if rename_installs {
+            miseprintln!(
+                "{} → {}",
+                display_path(&old_ba.installs_path),
+                display_path(&new_ba.installs_path)
+            );
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        // the completed steps are rolled back if a later one fails
+        file::write(&path, doc.to_string())?;
+        let mut written = vec![(path.as_path(), toml)];
+        if let Some(content) = lockfile_content {
+            if let Err(err) = lockfile.save(&lockfile_path) {
+                return Err(rollback(err, &written));
+            }
+            written.push((lockfile_path.as_path(), content));
+        }
+        if rename_installs {
+            if let Err(err) = rename_installs_dir(&old_ba, &new_ba) {
+                return Err(rollback(err, &written));
+            }
+            install_state::reset();
+        }
+        Ok(())
+    }
+}
+
+fn ensure_writable(path: &Path) -> Result<()> {
+    if path.metadata()?.permissions().readonly() {
+        bail!("{} is read-only", display_path(path));
+    }
+    Ok(())
+}
+
+fn rename_installs_dir(old_ba: &BackendArg, new_ba: &BackendArg) -> Result<()> {
+    file::rename(&old_ba.installs_path, &new_ba.installs_path)?;
+    if let Err(err) = install_state::write_backend_meta(new_ba) {
+        if let Err(err) = file::rename(&new_ba.installs_path, &old_ba.installs_path) {
+            warn!(
+                "failed to move back {}: {err}",
+                display_path(&new_ba.installs_path)
+            );
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Restores the files already written before `err`
+fn rollback(err: Report, written: &[(&Path, String)]) -> Report {
+    for (path, content) in written {
+        if let Err(err) = file::write(path, content) {
+            warn!("failed to restore {}: {err}", display_path(path));
+        }
+    }
+    err.wrap_err("rename failed, the changes were rolled back")
+}
+
+/// Renames `old` to `new` in `[tools]`, keeping its position, options and comments
+fn rename_tool_in_doc(doc: &mut DocumentMut, old: &str, new: &str) -> Result<()> {
+    rebuild_tools_in_doc(doc, old, new, |key, item| {
+        let key = match key.get() == old {
+            true => Key::new(new).with_leaf_decor(key.leaf_decor().clone()),
+            false => key,
+        };
+        vec![(key, item)]
+    })
+}
+
+/// Rebuilds `[tools]` with each entry replaced by the entries `f` returns for it, keeping their
+/// order and comments. Fails unless `src` is in `[tools]` and `new` is not.
+pub(super) fn rebuild_tools_in_doc(
+    doc: &mut DocumentMut,
+    src: &str,
+    new: &str,
+    mut f: impl FnMut(Key, Item) -> Vec<(Key, Item)>,
+) -> Result<()> {
+    let Some(tools) = doc.get_mut("tools").and_then(|t| t.as_table_mut()) else {
+        bail!("no [tools] table found");
+    };
+    if !tools.contains_key(src) {
+        bail!("{src} not found in [tools]");
+    }
+    if tools.contains_key(new) {
+        bail!("{new} is already in [tools]");
+    }
+    let entries = tools
+        .iter()
+        .map(|(k, item)| (tools.key(k).unwrap().clone(), item.clone()))
+        .collect_vec();
+    tools.clear();
+    for (key, item) in entries {
+        for (key, item) in f(key, item) {
+            tools.insert_formatted(&key, item);
+        }
+    }
+    Ok(())
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends rename github:old-org/tool github:new-org/tool</bold>
+    mise.toml: github:old-org/tool → github:new-org/tool
+    mise.lock: github:old-org/tool → github:new-org/tool
+    ~/.local/share/mise/installs/github-old-org-tool → ~/.local/share/mise/installs/github-new-org-tool
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_tool_in_doc() {
+        let mut doc: DocumentMut = r#"
+[tools]
+node = "22"
+# pinned until the next release
+"github:old-org/tool" = { version = "1.2.3", bin = "tool" }
+jq = "1.7"
+"#
+        .parse()
+        .unwrap();
+        rename_tool_in_doc(&mut doc, "github:old-org/tool", "github:new-org/tool").unwrap();
+        assert_eq!(
+            doc.to_string(),
+            r#"
+[tools]
+node = "22"
+# pinned until the next release
+"github:new-org/tool" = { version = "1.2.3", bin = "tool" }
+jq = "1.7"
+"#
+        );
+        assert!(rename_tool_in_doc(&mut doc, "github:old-org/tool", "github:x/tool").is_err());
+        assert!(rename_tool_in_doc(&mut doc, "node", "jq").is_err());
+    }
+
+    #[test]
+    fn test_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mise.toml");
+        file::write(&path, "renamed").unwrap();
+        let err = rollback(eyre!("failed"), &[(path.as_path(), "original".to_string())]);
+        assert_eq!(file::read_to_string(&path).unwrap(), "original");
+        assert!(err.to_string().contains("rolled back"));
+    }
+}
@@ -0,0 +1,117 @@
+use super::login::parse_target;
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::api_url_for_host;
+use crate::ui::table;
+use crate::{github, gitlab, parallel};
+use eyre::Result;
+use tabled::Tabled;
+
+/// Search a github, gitlab or codeberg host for tools by repository topic
+///
+/// Lists the most starred repositories tagged with the topic, e.g.: "mise-tool",
+/// along with their latest release so they can be added to mise.toml with `mise use`.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsSearch {
+    /// The repository topic to search for
+    topic: String,
+
+    /// The forge and host to search, e.g.: "github:github.com" or "gitlab:gitlab.example.com"
+    ///
+    /// Defaults to github.com
+    #[clap(value_name = "FORGE:HOST", default_value = "github")]
+    target: String,
+
+    /// Don't show table header
+    #[clap(long)]
+    no_header: bool,
+}
+
+#[derive(Debug, Tabled)]
+struct SearchResult {
+    #[tabled(rename = "Tool")]
+    tool: String,
+    #[tabled(rename = "Latest")]
+    latest: String,
+    #[tabled(rename = "Description")]
+    description: String,
+}
+
+impl BackendsSearch {
+    pub async fn run(self) -> Result<()> {
+        let (forge, host) = parse_target(&self.target)?;
+        let backend_type = BackendType::guess(forge);
+        let api_url = api_url_for_host(&backend_type, host);
+        let repos = match backend_type {
+            BackendType::Gitlab => gitlab::search_projects_by_topic(&api_url, &self.topic)
+                .await?
+                .into_iter()
+                .map(|p| (p.path_with_namespace, p.description))
+                .collect::<Vec<_>>(),
+            BackendType::Codeberg => github::with_forge(
+                BackendType::Codeberg,
+                github::search_forgejo_repos_by_topic(&api_url, &self.topic),
+            )
+            .await?
+            .into_iter()
+            .map(|r| (r.full_name, r.description))
+            .collect(),
+            _ => github::search_repos_by_topic(&api_url, &self.topic)
+                .await?
+                .into_iter()
+                .map(|r| (r.full_name, r.description))
+                .collect(),
+        };
+        if repos.is_empty() {
+            info!("no repositories found with topic {}", self.topic);
+            return Ok(());
+        }
+        let repos = repos
+            .into_iter()
+            .map(|(repo, description)| (api_url.clone(), forge.to_string(), repo, description))
+            .collect();
+        let results = parallel::parallel(repos, |(api_url, forge, repo, description)| async move {
+            let latest = match forge.as_str() {
+                "gitlab" => gitlab::get_latest_release_for_url(&api_url, &repo)
+                    .await
+                    .map(|r| r.tag_name),
+                _ => github::with_forge(
+                    BackendType::guess(&forge),
+                    github::get_latest_release_for_url(&api_url, &repo),
+                )
+                .await
+                .map(|r| r.tag_name),
+            };
+            let latest = latest.unwrap_or_else(|err| {
+                debug!("{repo}: no latest release: {err:#}");
+                String::new()
+            });
+            Ok(SearchResult {
+                tool: format!("{forge}:{repo}"),
+                latest,
+                description: description.unwrap_or_default(),
+            })
+        })
+        .await?;
+        let mut table = tabled::Table::new(results);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        if !matches!(host, "github.com" | "gitlab.com" | "codeberg.org") {
+            info!("set api_url = \"{api_url}\" in the tool options to use these tools");
+        }
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends search mise-tool</bold>
+    Tool                     Latest   Description
+    github:myorg/formatter   v1.4.0   Formats things
+    github:myorg/linter      v0.9.2   Lints things
+
+    $ <bold>mise backends search mise-tool gitlab:gitlab.example.com</bold>
+    $ <bold>mise backends search mise-tool codeberg</bold>
+"#
+);
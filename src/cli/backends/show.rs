@@ -0,0 +1,113 @@
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::{RateLimit, UnifiedGitBackend};
+use crate::cli::args::BackendArg;
+use crate::ui::table;
+use eyre::Result;
+use serde_derive::Serialize;
+
+/// Show backend details for a tool, including API rate limits
+///
+/// For github and gitlab tools this makes a request to the API to report the
+/// current rate limit status.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsShow {
+    /// Tool to show backend details for
+    tool: BackendArg,
+
+    /// Output in JSON format
+    #[clap(long, short = 'J')]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct BackendInfo {
+    backend: String,
+    backend_type: String,
+    api_url: Option<String>,
+    cached_versions: Option<usize>,
+    rate_limit: Option<RateLimit>,
+}
+
+impl BackendsShow {
+    pub async fn run(self) -> Result<()> {
+        let backend = self.tool.backend()?;
+        let ba = backend.ba();
+        let cached_versions = backend
+            .get_remote_version_cache()
+            .lock()
+            .await
+            .get_cached()
+            .map(|v| v.len());
+        let backend_type = backend.get_type();
+        let (api_url, rate_limit) = match backend_type {
+            BackendType::Github | BackendType::Gitlab | BackendType::Codeberg => {
+                let git = UnifiedGitBackend::from_arg((**ba).clone());
+                let rate_limit = match git.rate_limit().await {
+                    Ok(rate_limit) => Some(rate_limit),
+                    Err(err) => {
+                        warn!("failed to fetch rate limit: {err:#}");
+                        None
+                    }
+                };
+                (Some(git.api_url()), rate_limit)
+            }
+            _ => (None, None),
+        };
+        let info = BackendInfo {
+            backend: ba.full(),
+            backend_type: backend_type.to_string(),
+            api_url,
+            cached_versions,
+            rate_limit,
+        };
+
+        if self.json {
+            miseprintln!("{}", serde_json::to_string_pretty(&info)?);
+            return Ok(());
+        }
+        let none = || "[none]".to_string();
+        let mut table = vec![
+            ("Backend:", info.backend),
+            ("Backend Type:", info.backend_type),
+            ("API URL:", info.api_url.unwrap_or_else(none)),
+            (
+                "Cached Versions:",
+                info.cached_versions
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(none),
+            ),
+        ];
+        if let Some(rate_limit) = info.rate_limit {
+            let remaining = match (rate_limit.remaining, rate_limit.limit) {
+                (Some(remaining), Some(limit)) => format!("{remaining}/{limit}"),
+                (Some(remaining), None) => remaining.to_string(),
+                _ => none(),
+            };
+            table.push(("Rate Limit Remaining:", remaining));
+            let reset = rate_limit
+                .reset
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                .map(|t| t.with_timezone(&chrono::Local).to_string())
+                .unwrap_or_else(none);
+            table.push(("Rate Limit Reset:", reset));
+        }
+        let mut table = tabled::Table::new(table);
+        table::default_style(&mut table, true);
+        miseprintln!("{table}");
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends show github:cli/cli</bold>
+    Backend:               github:cli/cli
+    Backend Type:          github
+    API URL:               https://api.github.com
+    Cached Versions:       30
+    Rate Limit Remaining:  4987/5000
+    Rate Limit Reset:      2025-01-01 12:00:00 -08:00
+"#
+);
@@ -0,0 +1,81 @@
+use crate::cache;
+use crate::config::{Config, Settings};
+use crate::ui::style;
+use crate::ui::table;
+use eyre::Result;
+use indicatif::HumanDuration;
+use tabled::Tabled;
+
+/// Show how fresh the cached remote versions of configured tools are
+///
+/// Lists every configured tool with the age of its cached list of remote versions.
+/// Tools whose cache is stale or missing will fetch their versions on next use,
+/// fresh ones are served from the cache until `fetch_remote_versions_cache` elapses.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsStatus {
+    /// Don't show table header
+    #[clap(long)]
+    pub no_header: bool,
+}
+
+#[derive(Debug, Tabled)]
+struct Status {
+    #[tabled(rename = "Tool")]
+    tool: String,
+    #[tabled(rename = "Cache Age")]
+    age: String,
+    #[tabled(rename = "Status")]
+    status: String,
+}
+
+impl BackendsStatus {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let fresh_duration = Settings::get().fetch_remote_versions_cache();
+        let mut rows = vec![];
+        for ba in config.get_tool_request_set().await?.list_tools() {
+            let age = cache::list_entries(&ba.cache_path)
+                .into_iter()
+                .filter(|e| e.key.starts_with("remote_versions"))
+                .map(|e| e.age)
+                .min();
+            let (age, status) = match age {
+                None => ("-".to_string(), style::nyellow("missing").to_string()),
+                Some(age) => {
+                    let status = match fresh_duration {
+                        Some(fresh) if age < fresh => style::nstyle("fresh").green(),
+                        // with prefer-offline the cache is always used
+                        None => style::nstyle("fresh").green(),
+                        Some(_) => style::nred("stale"),
+                    };
+                    (HumanDuration(age).to_string(), status.to_string())
+                }
+            };
+            rows.push(Status {
+                tool: ba.full(),
+                age,
+                status,
+            });
+        }
+        if rows.is_empty() {
+            info!("no tools are configured");
+            return Ok(());
+        }
+        let mut table = tabled::Table::new(rows);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends status</bold>
+    Tool                     Cache Age   Status
+    github:cli/cli           12 minutes  fresh
+    aqua:BurntSushi/ripgrep  3 days      stale
+    core:node                -           missing
+"#
+);
@@ -0,0 +1,202 @@
+use crate::backend::github::glob_to_regex;
+use crate::backend::static_helpers::{
+    asset_pattern_overrides, lookup_platform_key, version_matches_range,
+};
+use crate::cli::args::BackendArg;
+use crate::config::Config;
+use crate::toolset::ToolVersionOptions;
+use crate::ui::table;
+use eyre::{Result, bail};
+use regex::Regex;
+use tabled::Tabled;
+use versions::Versioning;
+use xx::regex;
+
+/// Variables `bin_path` may reference
+const BIN_PATH_VARIABLES: &[&str] = &["version"];
+
+/// Check the options of the tools in the current config for errors
+///
+/// Reports every problem at once instead of failing on the first one during `mise install`:
+/// - `api_url` is not a valid http(s) URL
+/// - `asset_pattern` and `asset_pattern_overrides` don't compile
+/// - `checksum` has an unknown algorithm or a hash of the wrong length
+/// - `bin_path` references an unknown variable
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BackendsValidateConfig {
+    /// Don't show table header
+    #[clap(long)]
+    pub no_header: bool,
+}
+
+#[derive(Debug, Tabled)]
+struct Problem {
+    #[tabled(rename = "Tool")]
+    tool: String,
+    #[tabled(rename = "Option")]
+    option: String,
+    #[tabled(rename = "Error")]
+    error: String,
+}
+
+impl BackendsValidateConfig {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::get().await?;
+        let trs = config.get_tool_request_set().await?;
+        let problems = trs
+            .list_tools()
+            .into_iter()
+            .flat_map(|ba| validate_tool(ba, &ba.opts()))
+            .collect::<Vec<_>>();
+        if problems.is_empty() {
+            info!("no problems found");
+            return Ok(());
+        }
+        let count = problems.len();
+        let mut table = tabled::Table::new(problems);
+        table::default_style(&mut table, self.no_header);
+        miseprintln!("{table}");
+        bail!("found {count} problem(s) in the tool options");
+    }
+}
+
+fn validate_tool(ba: &BackendArg, opts: &ToolVersionOptions) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut add = |option: &str, error: String| {
+        problems.push(Problem {
+            tool: ba.short.clone(),
+            option: option.to_string(),
+            error,
+        })
+    };
+    if let Some(api_url) = opts.get("api_url") {
+        match url::Url::parse(api_url) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+            Ok(url) => add("api_url", format!("unsupported scheme {}", url.scheme())),
+            Err(err) => add("api_url", format!("invalid URL {api_url}: {err}")),
+        }
+    }
+    let pattern =
+        lookup_platform_key(opts, "asset_pattern").or_else(|| opts.get("asset_pattern").cloned());
+    let overrides = match asset_pattern_overrides(opts) {
+        Ok(overrides) => overrides,
+        Err(err) => {
+            add("asset_pattern_overrides", format!("{err:#}"));
+            vec![]
+        }
+    };
+    // any version works, this only checks that the ranges parse
+    let version = Versioning::new("0.0.0").unwrap();
+    for (range, _) in &overrides {
+        if let Err(err) = version_matches_range(&version, range) {
+            add("asset_pattern_overrides", err.to_string());
+        }
+    }
+    let override_patterns = overrides.into_iter().map(|(_, pattern)| pattern);
+    for pattern in pattern.into_iter().chain(override_patterns) {
+        // patterns are matched after `{version}` and `{tag}` are expanded
+        let glob = pattern
+            .replace("{version}", "0.0.0")
+            .replace("{tag}", "v0.0.0");
+        if let Err(err) = Regex::new(&format!("^{}$", glob_to_regex(&glob))) {
+            add("asset_pattern", format!("invalid pattern {pattern}: {err}"));
+        }
+    }
+    let checksum = lookup_platform_key(opts, "checksum").or_else(|| opts.get("checksum").cloned());
+    for checksum in checksum.iter().flat_map(|c| c.split_whitespace()) {
+        if let Err(err) = validate_checksum(checksum) {
+            add("checksum", err);
+        }
+    }
+    let bin_path = lookup_platform_key(opts, "bin_path").or_else(|| opts.get("bin_path").cloned());
+    if let Some(bin_path) = bin_path {
+        for var in regex!(r"\{([^}]*)\}").captures_iter(&bin_path) {
+            if !BIN_PATH_VARIABLES.contains(&&var[1]) {
+                add(
+                    "bin_path",
+                    format!("unknown variable {{{}}} in {bin_path}", &var[1]),
+                );
+            }
+        }
+    }
+    problems
+}
+
+/// Checks a single `algo:hash` checksum
+fn validate_checksum(checksum: &str) -> Result<(), String> {
+    let Some((algo, hash)) = checksum.split_once(':') else {
+        return Err(format!("{checksum} is not in the algo:hash format"));
+    };
+    let len = match algo {
+        "md5" => 32,
+        "sha1" => 40,
+        "sha256" | "blake3" => 64,
+        "sha512" => 128,
+        _ => return Err(format!("unknown checksum algorithm {algo}")),
+    };
+    if hash.len() != len || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "{algo} checksum must be {len} hex characters, got {hash}"
+        ));
+    }
+    Ok(())
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise backends validate-config</bold>
+    Tool               Option    Error
+    github:myorg/tool  api_url   invalid URL api.example.com: relative URL without a base
+    github:myorg/tool  checksum  sha256 checksum must be 64 hex characters, got abc123
+    Error: found 2 problem(s) in the tool options
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_tool() {
+        let ba = BackendArg::from("github:myorg/tool");
+        let mut opts = ToolVersionOptions::default();
+        for (k, v) in [
+            ("api_url", "api.example.com"),
+            ("asset_pattern", "tool-{version}-(linux.tar.gz"),
+            ("checksum", "sha256:abc123 sha384:abc"),
+            ("bin_path", "{name}-{verison}/bin"),
+            (
+                "asset_pattern_overrides",
+                r#"[{ version = "!!2", pattern = "tool.tgz" }]"#,
+            ),
+        ] {
+            opts.opts.insert(k.to_string(), v.to_string());
+        }
+        let problems = validate_tool(&ba, &opts);
+        let options = problems
+            .iter()
+            .map(|p| p.option.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            options,
+            vec![
+                "api_url",
+                "asset_pattern_overrides",
+                "asset_pattern",
+                "checksum",
+                "checksum",
+                "bin_path",
+                "bin_path"
+            ]
+        );
+
+        let mut opts = ToolVersionOptions::default();
+        opts.opts
+            .insert("checksum".to_string(), format!("sha256:{}", "a".repeat(64)));
+        opts.opts
+            .insert("bin_path".to_string(), "tool-{version}/bin".to_string());
+        assert!(validate_tool(&ba, &opts).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::dirs::CACHE;
-use crate::file::{display_path, remove_all};
+use crate::duration;
+use crate::file::{display_path, remove_all, remove_file};
 use eyre::Result;
 use filetime::set_file_times;
 use walkdir::WalkDir;
@@ -12,6 +13,11 @@ pub struct CacheClear {
     /// e.g.: node, python
     plugin: Option<Vec<String>>,
 
+    /// Only delete cache files last modified longer ago than this
+    /// e.g.: 12h, 7d, 2w
+    #[clap(long, value_name = "DURATION", conflicts_with = "outdate")]
+    older_than: Option<String>,
+
     /// Mark all cache files as old
     #[clap(long, hide = true)]
     outdate: bool,
@@ -40,6 +46,30 @@ impl CacheClear {
                     }
                 }
             }
+        } else if let Some(older_than) = &self.older_than {
+            let age = duration::parse_duration(older_than)?;
+            let mut count = 0;
+            for p in cache_dirs {
+                if p.exists() {
+                    debug!(
+                        "clearing cache older than {older_than} from {}",
+                        display_path(&p)
+                    );
+                    let files: Vec<_> = WalkDir::new(&p)
+                        .into_iter()
+                        .filter_map(|e| e.ok())
+                        .filter(|e| e.file_type().is_file())
+                        .collect();
+                    for e in files {
+                        let modified = e.metadata()?.modified()?;
+                        if modified.elapsed().unwrap_or_default() > age {
+                            remove_file(e.path())?;
+                            count += 1;
+                        }
+                    }
+                }
+            }
+            info!("cache cleared {count} files older than {older_than}");
         } else {
             for p in cache_dirs {
                 if p.exists() {
@@ -0,0 +1,73 @@
+use crate::cache;
+use crate::dirs::CACHE;
+use crate::ui::table;
+use eyre::Result;
+use indicatif::HumanDuration;
+use tabled::Tabled;
+
+use super::prune::bytes_str;
+
+/// Show information about the mise cache
+///
+/// Shows the number of cache entries and their total size.
+/// Use --verbose to list every cache entry with its age and size.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct CacheInfo {
+    /// Show each cache entry
+    #[clap(long, short)]
+    verbose: bool,
+}
+
+#[derive(Debug, Tabled)]
+struct Entry {
+    #[tabled(rename = "Key")]
+    key: String,
+    #[tabled(rename = "Age")]
+    age: String,
+    #[tabled(rename = "Size")]
+    size: String,
+}
+
+impl CacheInfo {
+    pub fn run(self) -> Result<()> {
+        let mut entries = cache::list_entries(*CACHE);
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+        let size = entries.iter().map(|e| e.size).sum();
+        if self.verbose && !entries.is_empty() {
+            let rows = entries
+                .iter()
+                .map(|e| Entry {
+                    key: e.key.clone(),
+                    age: HumanDuration(e.age).to_string(),
+                    size: bytes_str(e.size),
+                })
+                .collect::<Vec<_>>();
+            let mut table = tabled::Table::new(rows);
+            table::default_style(&mut table, false);
+            miseprintln!("{table}");
+        }
+        miseprintln!("Path:    {}", CACHE.display());
+        miseprintln!("Entries: {}", entries.len());
+        miseprintln!("Size:    {}", bytes_str(size));
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise cache info</bold>
+    Path:    ~/.cache/mise
+    Entries: 42
+    Size:    1.2 MiB
+
+    $ <bold>mise cache info --verbose</bold>
+    Key                                     Age      Size
+    node/remote_versions-abc12.msgpack.z    3 hours  12.1 KiB
+    python/remote_versions-abc12.msgpack.z  2 days   4.0 KiB
+    Path:    ~/.cache/mise
+    Entries: 2
+    Size:    16.1 KiB
+"#
+);
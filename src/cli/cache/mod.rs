@@ -4,8 +4,9 @@ use eyre::Result;
 use crate::env;
 
 mod clear;
+mod info;
 mod path;
-mod prune;
+pub(crate) mod prune;
 
 /// Manage the mise cache
 ///
@@ -20,6 +21,7 @@ pub struct Cache {
 #[derive(Debug, Subcommand)]
 enum Commands {
     Clear(clear::CacheClear),
+    Info(info::CacheInfo),
     Path(path::CachePath),
     Prune(prune::CachePrune),
 }
@@ -28,6 +30,7 @@ impl Commands {
     pub fn run(self) -> Result<()> {
         match self {
             Self::Clear(cmd) => cmd.run(),
+            Self::Info(cmd) => cmd.run(),
             Self::Path(cmd) => cmd.run(),
             Self::Prune(cmd) => cmd.run(),
         }
@@ -249,5 +249,6 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     $ <bold>eval "$(mise env -s zsh)"</bold>
     $ <bold>mise env -s fish | source</bold>
     $ <bold>execx($(mise env -s xonsh))</bold>
+    $ <bold>(&mise env -s pwsh) | Out-String | Invoke-Expression</bold>
 "#
 );
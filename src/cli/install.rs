@@ -1,11 +1,24 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
+use crate::backend::backend_type::BackendType;
+use crate::backend::static_helpers::{get_filename_from_url, verify_checksum_str};
 use crate::cli::args::ToolArg;
-use crate::config::Config;
+use crate::cli::install_into::install_into;
+use crate::config::{Config, Settings};
+use crate::file::display_path;
 use crate::hooks::Hooks;
-use crate::toolset::{InstallOptions, ResolveOptions, ToolRequest, ToolSource, Toolset};
-use crate::{config, env, hooks};
-use eyre::Result;
+use crate::install_context::InstallContext;
+use crate::platform::Platform;
+use crate::toolset::{
+    InstallOptions, ResolveOptions, ToolRequest, ToolSource, ToolVersion, Toolset, ToolsetBuilder,
+};
+use crate::ui::multi_progress_report::MultiProgressReport;
+use crate::{config, dirs, env, file, hooks};
+use clap::ValueHint;
+use eyre::{Result, WrapErr, bail};
 use itertools::Itertools;
 
 /// Install a tool version
@@ -44,16 +57,172 @@ pub struct Install {
     #[clap(long, short, action = clap::ArgAction::Count)]
     verbose: u8,
 
+    /// Install the tools listed in this file, one TOOL@VERSION per line
+    ///
+    /// Blank lines and lines starting with `#` are ignored. The tools are installed
+    /// in addition to any given as arguments and no config file is modified.
+    #[clap(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        conflicts_with_all = ["verify_only", "target_dir"],
+        verbatim_doc_comment
+    )]
+    file: Option<PathBuf>,
+
+    /// Install the tool from this archive instead of downloading its release asset
+    ///
+    /// e.g.: in air-gapped environments. Only a single github, gitlab or codeberg
+    /// tool can be installed this way. Checksums are still verified if configured.
+    #[clap(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        requires = "tool",
+        conflicts_with_all = ["dry_run", "verify_only", "target_dir", "file", "platform"],
+        verbatim_doc_comment
+    )]
+    from_file: Option<PathBuf>,
+
+    /// Confirm the use of --skip-checksum
+    #[clap(long, requires = "skip_checksum")]
+    i_know_what_im_doing: bool,
+
+    /// Timeout in seconds for network requests made by this install
+    ///
+    /// Overrides `http_timeout` and `fetch_remote_versions_timeout` for this
+    /// invocation only, e.g.: for a large download on a slow connection.
+    #[clap(long, value_name = "SECS", verbatim_doc_comment)]
+    network_timeout: Option<u64>,
+
+    /// Don't create or update shims for the installed tools
+    ///
+    /// Useful for tools only run with `mise exec`. Run `mise reshim` to create
+    /// the shims later.
+    #[clap(long, verbatim_doc_comment)]
+    no_shims: bool,
+
+    /// Download assets for a different platform than the current one
+    /// e.g.: linux-x64, macos-arm64, windows-x64
+    ///
+    /// When the platform differs from the current one, the tools are installed into
+    /// `~/.local/share/mise/downloads/platforms/<PLATFORM>` instead of the installs directory
+    /// and downloaded files are kept so they can be distributed to other systems.
+    /// Config files, the lockfile, shims and symlinks are not updated, and the installed binaries
+    /// are never run, so postinstall, post_install_script and verify_binary are skipped.
+    #[clap(long, verbatim_doc_comment)]
+    platform: Option<String>,
+
     /// Directly pipe stdin/stdout/stderr from plugin to user
     /// Sets --jobs=1
     #[clap(long, overrides_with = "jobs")]
     raw: bool,
+
+    /// Write a provenance record of each installed github/gitlab tool
+    ///
+    /// Saves the download URL, its SHA-256, the release tag and date and the API
+    /// URL used to `.mise-provenance.json` in the install directory of the tool.
+    #[clap(long, conflicts_with = "verify_only", verbatim_doc_comment)]
+    record_provenance: bool,
+
+    /// Skip checksum and size verification of downloaded artifacts
+    ///
+    /// Useful when a mirror modifies assets during download. This is unsafe and
+    /// must be confirmed with --i-know-what-im-doing.
+    #[clap(
+        long,
+        requires = "i_know_what_im_doing",
+        conflicts_with = "verify_only",
+        verbatim_doc_comment
+    )]
+    skip_checksum: bool,
+
+    /// Install the tool into this directory instead of the mise data directory
+    ///
+    /// Only a single tool can be installed this way. Config files, the lockfile,
+    /// shims and symlinks are not updated, same as `mise install-into`.
+    #[clap(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::DirPath,
+        requires = "tool",
+        conflicts_with_all = ["dry_run", "verify_only", "platform"],
+        verbatim_doc_comment
+    )]
+    target_dir: Option<PathBuf>,
+
+    /// Verify checksums of installed tools against the lockfile instead of installing
+    ///
+    /// Checks the downloaded artifact of each installed tool version against the checksum
+    /// recorded in mise.lock. Artifacts are only available if they were kept after install
+    /// (see `always_keep_download`) or were installed as a single file. Tools that can't be
+    /// checked are reported, and the command fails if none could be.
+    #[clap(long, conflicts_with_all = ["force", "dry_run"], verbatim_doc_comment)]
+    verify_only: bool,
+
+    #[clap(skip)]
+    foreign_platform: Option<Platform>,
 }
 
 impl Install {
     #[async_backtrace::framed]
-    pub async fn run(self) -> Result<()> {
+    pub async fn run(mut self) -> Result<()> {
+        if let Some(secs) = self.network_timeout {
+            // must be set before the first request as the http clients are only built once
+            Settings::add_cli_overrides(|s| {
+                s.http_timeout = Some(secs.to_string());
+                s.fetch_remote_versions_timeout = Some(secs.to_string());
+            });
+        }
+        if let Some(platform) = self.platform.clone() {
+            // must be checked before the override as Platform::current() reads the same settings
+            self.detect_foreign_platform(&Platform::current())?;
+            override_platform(&platform)?;
+            if let Some(platform) = &self.foreign_platform {
+                warn!("installing for {platform}, installed tools will not run on this system");
+                Settings::add_cli_overrides(|s| s.always_keep_download = Some(true));
+            }
+        }
+        if self.skip_checksum {
+            warn!(
+                "--skip-checksum: downloaded artifacts will not be verified against their checksums"
+            );
+            env::SKIP_CHECKSUM.store(true, Ordering::Relaxed);
+        }
+        if self.record_provenance {
+            env::RECORD_PROVENANCE.store(true, Ordering::Relaxed);
+        }
         let config = Config::get().await?;
+        if self.verify_only {
+            return self.verify_installed(&config).await;
+        }
+        if let Some(target_dir) = &self.target_dir {
+            let [tool] = self.tool.as_deref().unwrap_or_default() else {
+                bail!("--target-dir can only be used with a single tool");
+            };
+            return install_into(&config, tool, target_dir).await;
+        }
+        if let Some(path) = &self.from_file {
+            let [tool] = self.tool.as_deref().unwrap_or_default() else {
+                bail!("--from-file can only be used with a single tool");
+            };
+            if !matches!(
+                tool.ba.backend_type(),
+                BackendType::Github | BackendType::Gitlab | BackendType::Codeberg
+            ) {
+                bail!("--from-file is only supported by the github, gitlab and codeberg backends");
+            }
+            if !path.is_file() {
+                bail!("{} not found", display_path(path));
+            }
+        }
+        if let Some(path) = &self.file {
+            let tools = read_tool_file(path)?;
+            self.tool.get_or_insert_default().extend(tools);
+        }
+        if let Some(platform) = &self.foreign_platform {
+            return self.install_for_platform(&config, platform).await;
+        }
         match &self.tool {
             Some(runtime) => {
                 let original_tool_args = env::TOOL_ARGS.read().unwrap().clone();
@@ -66,6 +235,17 @@ impl Install {
         Ok(())
     }
 
+    /// Sets `foreign_platform` if --platform has a different os or arch than `host`
+    fn detect_foreign_platform(&mut self, host: &Platform) -> Result<()> {
+        if let Some(platform) = &self.platform {
+            let platform = parse_platform(platform)?;
+            if !platform.is_compatible_with(host) {
+                self.foreign_platform = Some(platform);
+            }
+        }
+        Ok(())
+    }
+
     #[async_backtrace::framed]
     async fn install_runtimes(
         &self,
@@ -101,7 +281,127 @@ impl Install {
 
         // Skip rebuilding shims and symlinks in dry-run mode
         if !self.dry_run {
-            config::rebuild_shims_and_runtime_symlinks(&config, ts, &versions).await?;
+            self.rebuild_shims_and_runtime_symlinks(&config, ts, &versions)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Installs the tools into `downloads/platforms/<PLATFORM>` instead of the installs
+    /// directory so they are not treated as installed on this system
+    async fn install_for_platform(&self, config: &Arc<Config>, platform: &Platform) -> Result<()> {
+        let tools = self.tool.clone().unwrap_or_default();
+        let ts = Arc::new(
+            ToolsetBuilder::new()
+                .with_args(&tools)
+                .build(config)
+                .await?,
+        );
+        let dir = platform_install_dir(platform);
+        let mpr = MultiProgressReport::get();
+        for (backend, mut tv) in ts.list_current_versions() {
+            if !tools.is_empty() && !tools.iter().any(|t| t.ba.as_ref() == tv.ba()) {
+                continue;
+            }
+            let install_path = dir
+                .join(tv.ba().installs_path.file_name().unwrap_or_default())
+                .join(tv.tv_pathname());
+            if install_path.exists() && !self.force {
+                info!(
+                    "{tv} is already installed in {}",
+                    display_path(&install_path)
+                );
+                continue;
+            }
+            let install_ctx = InstallContext {
+                config: config.clone(),
+                ts: ts.clone(),
+                pr: mpr.add(&tv.style()),
+                force: true,
+                dry_run: self.dry_run,
+                from_file: None,
+                foreign_platform: true,
+            };
+            tv.install_path = Some(install_path);
+            backend.install_version(install_ctx, tv).await?;
+        }
+        Ok(())
+    }
+
+    async fn rebuild_shims_and_runtime_symlinks(
+        &self,
+        config: &Arc<Config>,
+        ts: &Toolset,
+        versions: &[ToolVersion],
+    ) -> Result<()> {
+        if self.no_shims {
+            config::rebuild_runtime_symlinks(config, ts, versions).await
+        } else {
+            config::rebuild_shims_and_runtime_symlinks(config, ts, versions).await
+        }
+    }
+
+    async fn verify_installed(&self, config: &Arc<Config>) -> Result<()> {
+        let tools = self
+            .tool
+            .iter()
+            .flatten()
+            .map(|t| t.ba.clone())
+            .collect::<HashSet<_>>();
+        let ts = config.get_toolset().await?;
+        let mut failures = 0;
+        let mut verified = 0;
+        let mut unverified = 0;
+        for (backend, tv) in ts.list_current_installed_versions(config) {
+            if !tools.is_empty() && !tools.contains(backend.ba()) {
+                continue;
+            }
+            let Some(platform_info) = tv.lock_platforms.get(&backend.get_platform_key()) else {
+                warn!("{tv}: no lockfile entry for this platform, cannot verify");
+                unverified += 1;
+                continue;
+            };
+            let Some(checksum) = &platform_info.checksum else {
+                warn!("{tv}: no checksum in lockfile, cannot verify");
+                unverified += 1;
+                continue;
+            };
+            let Some(name) = platform_info
+                .name
+                .clone()
+                .or_else(|| platform_info.url.as_deref().map(get_filename_from_url))
+            else {
+                warn!("{tv}: lockfile has no artifact name, cannot verify");
+                unverified += 1;
+                continue;
+            };
+            let Some(path) = [
+                tv.download_path().join(&name),
+                tv.install_path().join(&name),
+            ]
+            .into_iter()
+            .find(|p| p.is_file()) else {
+                warn!("{tv}: {name} is no longer on disk, cannot verify");
+                unverified += 1;
+                continue;
+            };
+            verified += 1;
+            match verify_checksum_str(&path, checksum, None) {
+                Ok(()) => info!("{tv}: checksum ok"),
+                Err(err) => {
+                    error!("{tv}: {err}");
+                    failures += 1;
+                }
+            }
+        }
+        if failures > 0 {
+            bail!("{failures} installed tool(s) did not match the lockfile checksum");
+        }
+        if verified == 0 {
+            bail!("no installed tools could be verified ({unverified} unverified)");
+        }
+        if unverified > 0 {
+            warn!("{unverified} installed tool(s) could not be verified");
         }
         Ok(())
     }
@@ -117,6 +417,7 @@ impl Install {
                 latest_versions: true,
             },
             dry_run: self.dry_run,
+            from_file: self.from_file.clone(),
             ..Default::default()
         }
     }
@@ -201,13 +502,51 @@ impl Install {
         if !self.dry_run {
             measure!("rebuild_shims_and_runtime_symlinks", {
                 let ts = config.get_toolset().await?;
-                config::rebuild_shims_and_runtime_symlinks(&config, ts, &versions).await?;
+                self.rebuild_shims_and_runtime_symlinks(&config, ts, &versions)
+                    .await?;
             });
         }
         Ok(())
     }
 }
 
+/// Points asset detection at the given platform for the rest of this invocation.
+pub(crate) fn override_platform(platform: &str) -> Result<Platform> {
+    let platform = parse_platform(platform)?;
+    Settings::add_cli_overrides(|s| {
+        s.os = Some(platform.os.clone());
+        s.arch = Some(platform.arch.clone());
+    });
+    Ok(platform)
+}
+
+fn parse_platform(platform: &str) -> Result<Platform> {
+    let platform = Platform::parse(platform)?.normalize();
+    platform.validate()?;
+    if platform.qualifier.is_some() {
+        bail!("--platform only accepts os-arch, e.g.: linux-x64");
+    }
+    Ok(platform)
+}
+
+/// Directory the tools installed with `--platform` for another platform are placed in
+fn platform_install_dir(platform: &Platform) -> PathBuf {
+    dirs::DOWNLOADS.join("platforms").join(platform.to_key())
+}
+
+/// Reads a list of TOOL@VERSION specs, one per line, for `mise install --file`
+fn read_tool_file(path: &Path) -> Result<Vec<ToolArg>> {
+    file::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            line.parse::<ToolArg>()
+                .wrap_err_with(|| format!("{}: invalid tool: {line}", display_path(path)))
+        })
+        .collect()
+}
+
 static AFTER_LONG_HELP: &str = color_print::cstr!(
     r#"<bold><underline>Examples:</underline></bold>
 
@@ -215,5 +554,47 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     $ <bold>mise install node@20</bold>      # install fuzzy node version
     $ <bold>mise install node</bold>         # install version specified in mise.toml
     $ <bold>mise install</bold>              # installs everything specified in mise.toml
+    $ <bold>mise install node --platform windows-x64</bold>  # download node for windows
+    $ <bold>mise install --verify-only</bold>  # check installed tools against mise.lock
+    $ <bold>mise install jq@1.7 --target-dir ./jq</bold>  # install into ./jq without tracking it
+    $ <bold>mise install --trace github:cli/cli</bold>  # log each step of asset resolution
+    $ <bold>mise install --network-timeout 300 zig</bold>  # allow slow downloads
+    $ <bold>mise install --file tools.txt</bold>  # install the tools listed in tools.txt
+    $ <bold>mise install github:cli/cli@2.62.0 --from-file ./gh_2.62.0_linux_amd64.tar.gz</bold>
 "#
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Cli, Commands};
+    use clap::Parser;
+
+    fn parse_install(args: &[&str]) -> Install {
+        let args = ["mise", "install"].iter().chain(args);
+        match Cli::try_parse_from(args).unwrap().command {
+            Some(Commands::Install(install)) => install,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_detect_foreign_platform() {
+        let host = Platform::parse("linux-x64").unwrap();
+
+        let mut install = parse_install(&["--platform", "windows-arm64"]);
+        install.detect_foreign_platform(&host).unwrap();
+        assert_eq!(install.foreign_platform.unwrap().to_key(), "windows-arm64");
+
+        let mut install = parse_install(&["--platform", "darwin-x86_64"]);
+        install.detect_foreign_platform(&host).unwrap();
+        assert_eq!(install.foreign_platform.unwrap().to_key(), "macos-x64");
+
+        let mut install = parse_install(&["--platform", "linux-amd64"]);
+        install.detect_foreign_platform(&host).unwrap();
+        assert!(install.foreign_platform.is_none());
+
+        let mut install = parse_install(&["--platform", "linux-x64-musl"]);
+        assert!(install.detect_foreign_platform(&host).is_err());
+    }
+}
@@ -5,7 +5,8 @@ use crate::toolset::ToolsetBuilder;
 use crate::ui::multi_progress_report::MultiProgressReport;
 use clap::ValueHint;
 use eyre::{Result, eyre};
-use std::{path::PathBuf, sync::Arc};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Install a tool version to a specific path
 ///
@@ -26,35 +27,43 @@ pub struct InstallInto {
 impl InstallInto {
     pub async fn run(self) -> Result<()> {
         let config = Config::get().await?;
-        let ts = Arc::new(
-            ToolsetBuilder::new()
-                .with_args(std::slice::from_ref(&self.tool))
-                .build(&config)
-                .await?,
-        );
-        let mut tv = ts
-            .versions
-            .get(self.tool.ba.as_ref())
-            .ok_or_else(|| eyre!("Tool not found"))?
-            .versions
-            .first()
-            .unwrap()
-            .clone();
-        let backend = tv.backend()?;
-        let mpr = MultiProgressReport::get();
-        let install_ctx = InstallContext {
-            config: config.clone(),
-            ts: ts.clone(),
-            pr: mpr.add(&tv.style()),
-            force: true,
-            dry_run: false,
-        };
-        tv.install_path = Some(self.path.clone());
-        backend.install_version(install_ctx, tv).await?;
-        Ok(())
+        install_into(&config, &self.tool, &self.path).await
     }
 }
 
+/// Installs a single tool version into `path` without tracking it in the installs directory,
+/// config or lockfile
+pub async fn install_into(config: &Arc<Config>, tool: &ToolArg, path: &Path) -> Result<()> {
+    let ts = Arc::new(
+        ToolsetBuilder::new()
+            .with_args(std::slice::from_ref(tool))
+            .build(config)
+            .await?,
+    );
+    let mut tv = ts
+        .versions
+        .get(tool.ba.as_ref())
+        .ok_or_else(|| eyre!("Tool not found"))?
+        .versions
+        .first()
+        .unwrap()
+        .clone();
+    let backend = tv.backend()?;
+    let mpr = MultiProgressReport::get();
+    let install_ctx = InstallContext {
+        config: config.clone(),
+        ts: ts.clone(),
+        pr: mpr.add(&tv.style()),
+        force: true,
+        dry_run: false,
+        from_file: None,
+        foreign_platform: false,
+    };
+    tv.install_path = Some(path.to_path_buf());
+    backend.install_version(install_ctx, tv).await?;
+    Ok(())
+}
+
 static AFTER_LONG_HELP: &str = color_print::cstr!(
     r#"<bold><underline>Examples:</underline></bold>
 
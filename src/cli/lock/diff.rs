@@ -0,0 +1,273 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::config::{Config, Settings};
+use crate::file::{self, display_path};
+use crate::git::Git;
+use crate::lockfile::{Lockfile, LockfileTool};
+use console::style;
+use eyre::{Result, WrapErr, bail};
+
+/// Show changes to mise.lock since the last commit
+///
+/// Compares the lockfile of the closest mise.toml with the version in git HEAD
+/// and lists added and removed tools, version changes, and download URL changes.
+/// Requires `git` to be on PATH.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct LockDiff {}
+
+impl LockDiff {
+    pub async fn run(self) -> Result<()> {
+        Settings::get().ensure_experimental("lock diff")?;
+        if file::which("git").is_none() {
+            bail!("git is required for mise lock diff");
+        }
+        let config = Config::get().await?;
+        let Some(path) = config
+            .config_files
+            .iter()
+            .find(|(_, cf)| cf.source().is_mise_toml())
+            .map(|(p, _)| p.with_extension("lock"))
+        else {
+            bail!("no mise.toml found for mise lock diff");
+        };
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let old = match Git::new(dir).show_file("HEAD", &name) {
+            Ok(content) => Lockfile::parse(&content)
+                .wrap_err_with(|| format!("failed to parse {name} in HEAD"))?,
+            Err(err) => {
+                debug!("no {name} in HEAD: {err:#}");
+                Lockfile::default()
+            }
+        };
+        let new = Lockfile::read(&path)?;
+        let changes = diff_lockfiles(&old, &new);
+        if changes.is_empty() {
+            info!("{} has no changes since HEAD", display_path(&path));
+            return Ok(());
+        }
+        for change in changes {
+            miseprintln!("{}", change.render());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum LockChange {
+    Added {
+        tool: String,
+        version: String,
+    },
+    Removed {
+        tool: String,
+        version: String,
+    },
+    Version {
+        tool: String,
+        old: String,
+        new: String,
+    },
+    Url {
+        tool: String,
+        version: String,
+        platform: String,
+        old: Option<String>,
+        new: Option<String>,
+    },
+}
+
+impl LockChange {
+    fn render(&self) -> String {
+        match self {
+            LockChange::Added { tool, version } => {
+                format!("{} {tool} {version}", style("+").green())
+            }
+            LockChange::Removed { tool, version } => {
+                format!("{} {tool} {version}", style("-").red())
+            }
+            LockChange::Version { tool, old, new } => {
+                format!(
+                    "{} {tool} {} → {}",
+                    style("~").yellow(),
+                    style(old).red(),
+                    style(new).green()
+                )
+            }
+            LockChange::Url {
+                tool,
+                version,
+                platform,
+                old,
+                new,
+            } => {
+                let mut out = format!(
+                    "{} {tool} {version} {}",
+                    style("~").yellow(),
+                    style(platform).blue()
+                );
+                if let Some(old) = old {
+                    out.push_str(&format!("\n    {} {old}", style("-").red()));
+                }
+                if let Some(new) = new {
+                    out.push_str(&format!("\n    {} {new}", style("+").green()));
+                }
+                out
+            }
+        }
+    }
+}
+
+fn diff_lockfiles(old: &Lockfile, new: &Lockfile) -> Vec<LockChange> {
+    let tools: BTreeSet<&String> = old.tools().keys().chain(new.tools().keys()).collect();
+    let mut changes = vec![];
+    for tool in tools {
+        let old_versions = old
+            .tools()
+            .get(tool)
+            .map(|v| v.as_slice())
+            .unwrap_or_default();
+        let new_versions = new
+            .tools()
+            .get(tool)
+            .map(|v| v.as_slice())
+            .unwrap_or_default();
+        let find = |versions: &[LockfileTool], version: &str| {
+            versions.iter().find(|t| t.version == version).cloned()
+        };
+        let removed = old_versions
+            .iter()
+            .filter(|t| find(new_versions, &t.version).is_none())
+            .collect::<Vec<_>>();
+        let added = new_versions
+            .iter()
+            .filter(|t| find(old_versions, &t.version).is_none())
+            .collect::<Vec<_>>();
+        match (removed.as_slice(), added.as_slice()) {
+            ([old], [new]) => changes.push(LockChange::Version {
+                tool: tool.clone(),
+                old: old.version.clone(),
+                new: new.version.clone(),
+            }),
+            _ => {
+                changes.extend(removed.iter().map(|t| LockChange::Removed {
+                    tool: tool.clone(),
+                    version: t.version.clone(),
+                }));
+                changes.extend(added.iter().map(|t| LockChange::Added {
+                    tool: tool.clone(),
+                    version: t.version.clone(),
+                }));
+            }
+        }
+        for new_tool in new_versions {
+            let Some(old_tool) = find(old_versions, &new_tool.version) else {
+                continue;
+            };
+            let platforms: BTreeSet<&String> = old_tool
+                .platforms
+                .keys()
+                .chain(new_tool.platforms.keys())
+                .collect();
+            for platform in platforms {
+                let old_url = old_tool.platforms.get(platform).and_then(|p| p.url.clone());
+                let new_url = new_tool.platforms.get(platform).and_then(|p| p.url.clone());
+                if old_url != new_url {
+                    changes.push(LockChange::Url {
+                        tool: tool.clone(),
+                        version: new_tool.version.clone(),
+                        platform: platform.clone(),
+                        old: old_url,
+                        new: new_url,
+                    });
+                }
+            }
+        }
+    }
+    changes
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise lock diff</bold>
+    + jq 1.7.1
+    ~ node 20.11.0 → 20.11.1
+    ~ ripgrep 14.1.1 linux-x64
+        - https://github.com/BurntSushi/ripgrep/releases/download/14.1.1/ripgrep-14.1.1-x86_64-unknown-linux-gnu.tar.gz
+        + https://github.com/BurntSushi/ripgrep/releases/download/14.1.1/ripgrep-14.1.1-x86_64-unknown-linux-musl.tar.gz
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_diff_lockfiles() {
+        let old = Lockfile::parse(
+            r#"
+[[tools.node]]
+version = "20.11.0"
+backend = "core:node"
+
+[[tools.shellcheck]]
+version = "0.9.0"
+backend = "aqua:koalaman/shellcheck"
+
+[[tools.ripgrep]]
+version = "14.1.1"
+backend = "aqua:BurntSushi/ripgrep"
+platforms.linux-x64 = { url = "https://example.com/rg-gnu.tar.gz" }
+"#,
+        )
+        .unwrap();
+        let new = Lockfile::parse(
+            r#"
+[[tools.jq]]
+version = "1.7.1"
+backend = "aqua:jqlang/jq"
+
+[[tools.node]]
+version = "20.11.1"
+backend = "core:node"
+
+[[tools.ripgrep]]
+version = "14.1.1"
+backend = "aqua:BurntSushi/ripgrep"
+platforms.linux-x64 = { url = "https://example.com/rg-musl.tar.gz" }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            diff_lockfiles(&old, &new),
+            vec![
+                LockChange::Added {
+                    tool: "jq".into(),
+                    version: "1.7.1".into(),
+                },
+                LockChange::Version {
+                    tool: "node".into(),
+                    old: "20.11.0".into(),
+                    new: "20.11.1".into(),
+                },
+                LockChange::Url {
+                    tool: "ripgrep".into(),
+                    version: "14.1.1".into(),
+                    platform: "linux-x64".into(),
+                    old: Some("https://example.com/rg-gnu.tar.gz".into()),
+                    new: Some("https://example.com/rg-musl.tar.gz".into()),
+                },
+                LockChange::Removed {
+                    tool: "shellcheck".into(),
+                    version: "0.9.0".into(),
+                },
+            ]
+        );
+        assert!(diff_lockfiles(&new, &new).is_empty());
+    }
+}
@@ -7,9 +7,12 @@ use crate::file::display_path;
 use crate::lockfile::Lockfile;
 use crate::platform::Platform;
 use crate::{cli::args::ToolArg, config::Settings};
+use clap::Subcommand;
 use console::style;
 use eyre::Result;
 
+mod diff;
+
 /// Update lockfile checksums and URLs for all specified platforms
 ///
 /// Updates checksums and download URLs for all platforms already specified in the lockfile.
@@ -19,6 +22,9 @@ use eyre::Result;
 #[derive(Debug, clap::Args)]
 #[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
 pub struct Lock {
+    #[clap(subcommand)]
+    command: Option<Commands>,
+
     /// Tool(s) to update in lockfile
     /// e.g.: node python
     /// If not specified, all tools in lockfile will be updated
@@ -45,8 +51,24 @@ pub struct Lock {
     pub platform: Vec<String>,
 }
 
+#[derive(Debug, Subcommand)]
+enum Commands {
+    Diff(diff::LockDiff),
+}
+
+impl Commands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Diff(cmd) => cmd.run().await,
+        }
+    }
+}
+
 impl Lock {
     pub async fn run(self) -> Result<()> {
+        if let Some(cmd) = self.command {
+            return cmd.run().await;
+        }
         let settings = Settings::get();
         let config = Config::get().await?;
         settings.ensure_experimental("lock")?;
@@ -399,5 +421,6 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
   $ <bold>mise lock --platform linux-x64</bold>     Update only linux-x64 platform
   $ <bold>mise lock --dry-run</bold>                Show what would be updated or created
   $ <bold>mise lock --force</bold>                  Re-download and update even if data exists
+  $ <bold>mise lock diff</bold>                     Show changes to mise.lock since the last commit
 "#
 );
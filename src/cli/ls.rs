@@ -273,6 +273,9 @@ type JSONOutput = IndexMap<String, Vec<JSONToolVersion>>;
 
 #[derive(Serialize)]
 struct JSONToolVersion {
+    tool: String,
+    /// the backend type, e.g.: "core" or "github"
+    backend: String,
     version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     requested_version: Option<String>,
@@ -340,6 +343,8 @@ async fn json_tool_version_from(config: &Arc<Config>, row: RuntimeRow<'_>) -> JS
     let (ls, p, tv, source) = row;
     let vs: VersionStatus = version_status_from(config, (ls, p.as_ref(), &tv, &source)).await;
     JSONToolVersion {
+        tool: p.id().to_string(),
+        backend: p.get_type().to_string(),
         symlinked_to: p.symlink_path(&tv),
         install_path: tv.install_path(),
         version: tv.version.clone(),
@@ -406,6 +411,8 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     {
       "node": [
         {
+          "tool": "node",
+          "backend": "core",
           "version": "20.0.0",
           "install_path": "/Users/jdx/.mise/installs/node/20.0.0",
           "source": {
@@ -416,5 +423,14 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
       ],
       "python": [...]
     }
+
+    $ <bold>mise ls --installed --json | jq '[.[][] | {tool, version, active}]'</bold>
+    [
+      {
+        "tool": "node",
+        "version": "20.0.0",
+        "active": true
+      }
+    ]
 "#
 );
@@ -3,6 +3,8 @@ use std::sync::Arc;
 use eyre::Result;
 
 use crate::backend::Backend;
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::{RemoteRelease, UnifiedGitBackend};
 use crate::cli::args::ToolArg;
 use crate::toolset::{ToolRequest, tool_request};
 use crate::ui::multi_progress_report::MultiProgressReport;
@@ -27,6 +29,13 @@ pub struct LsRemote {
     /// Show all installed plugins and versions
     #[clap(long, verbatim_doc_comment, conflicts_with_all = ["plugin", "prefix"])]
     pub all: bool,
+
+    /// Output in JSON format
+    ///
+    /// For github and gitlab tools this includes the publish date, number of assets and
+    /// whether the release is a prerelease.
+    #[clap(long, short = 'J', conflicts_with = "all", verbatim_doc_comment)]
+    pub json: bool,
 }
 
 impl LsRemote {
@@ -51,6 +60,33 @@ impl LsRemote {
             _ => self.prefix.clone(),
         };
 
+        if self.json {
+            let releases = match plugin.get_type() {
+                BackendType::Github | BackendType::Gitlab | BackendType::Codeberg => {
+                    UnifiedGitBackend::from_arg((**plugin.ba()).clone())
+                        .list_remote_releases()
+                        .await?
+                }
+                _ => plugin
+                    .list_remote_versions(config)
+                    .await?
+                    .into_iter()
+                    .map(|version| RemoteRelease {
+                        version,
+                        published_at: None,
+                        asset_count: None,
+                        prerelease: None,
+                    })
+                    .collect(),
+            };
+            let releases: Vec<_> = releases
+                .into_iter()
+                .filter(|r| prefix.as_ref().is_none_or(|p| r.version.starts_with(p)))
+                .collect();
+            miseprintln!("{}", serde_json::to_string_pretty(&releases)?);
+            return Ok(());
+        }
+
         let versions = plugin.list_remote_versions(config).await?;
         let versions = match prefix {
             Some(prefix) => versions
@@ -110,5 +146,15 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     $ <bold>mise ls-remote node 20</bold>
     20.0.0
     20.1.0
+
+    $ <bold>mise ls-remote github:cli/cli@2.62 --json</bold>
+    [
+      {
+        "version": "2.62.0",
+        "published_at": "2024-11-14T15:30:00Z",
+        "asset_count": 19,
+        "prerelease": false
+      }
+    ]
 "#
 );
@@ -456,6 +456,14 @@ impl Cli {
         if let Err(err) = crate::cache::auto_prune() {
             warn!("auto_prune failed: {err:?}");
         }
+        for evict in [
+            crate::github::evict_stale_cache,
+            crate::gitlab::evict_stale_cache,
+        ] {
+            if let Err(err) = evict() {
+                warn!("cache eviction failed: {err:?}");
+            }
+        }
 
         debug!("ARGS: {}", &args.join(" "));
         trace!("MISE_BIN: {}", crate::env::MISE_BIN.display_user());
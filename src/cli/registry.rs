@@ -1,5 +1,6 @@
+use crate::backend;
 use crate::backend::backend_type::BackendType;
-use crate::config::Settings;
+use crate::config::{Config, Settings};
 use crate::registry::{REGISTRY, RegistryTool, tool_enabled};
 use crate::ui::table::MiseTable;
 use eyre::{Result, bail};
@@ -38,7 +39,7 @@ impl Registry {
                 bail!("tool not found in registry: {name}");
             }
         } else if self.complete {
-            self.complete()?;
+            self.complete().await?;
         } else {
             self.display_table()?;
         }
@@ -73,8 +74,8 @@ impl Registry {
         table.print()
     }
 
-    fn complete(&self) -> Result<()> {
-        REGISTRY
+    async fn complete(&self) -> Result<()> {
+        let mut tools = REGISTRY
             .iter()
             .filter(|(short, _)| filter_enabled(short))
             .filter(|(short, rt)| !self.hide_aliased || **short == rt.short)
@@ -83,9 +84,15 @@ impl Registry {
                     short.to_string(),
                     rt.description
                         .or(rt.backends().first().cloned())
-                        .unwrap_or_default(),
+                        .unwrap_or_default()
+                        .to_string(),
                 )
             })
+            .collect_vec();
+        tools.extend(forge_tools().await);
+        tools
+            .into_iter()
+            .unique_by(|(short, _)| short.clone())
             .sorted_by(|(a, _), (b, _)| a.cmp(b))
             .for_each(|(short, description)| {
                 println!(
@@ -111,6 +118,34 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
 "#
 );
 
+/// github, gitlab and codeberg tools aren't in the registry, so complete the configured and
+/// installed ones. Errors are ignored as completions shouldn't fail on a broken config.
+async fn forge_tools() -> Vec<(String, String)> {
+    let is_forge = |backend_type: BackendType| {
+        matches!(
+            backend_type,
+            BackendType::Github | BackendType::Gitlab | BackendType::Codeberg
+        )
+    };
+    let mut tools = vec![];
+    if let Ok(config) = Config::get().await
+        && let Ok(trs) = config.get_tool_request_set().await
+    {
+        for ba in trs.list_tools() {
+            if is_forge(ba.backend_type()) {
+                tools.push((ba.full(), format!("{} release", ba.backend_type())));
+            }
+        }
+    }
+    for backend in backend::list() {
+        let ba = backend.ba();
+        if is_forge(ba.backend_type()) && !backend.list_installed_versions().is_empty() {
+            tools.push((ba.full(), format!("{} release", ba.backend_type())));
+        }
+    }
+    tools
+}
+
 fn filter_enabled(short: &str) -> bool {
     tool_enabled(
         &Settings::get().enable_tools,
@@ -1,19 +1,20 @@
 use crate::errors::Error;
 use std::iter::once;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 
 use super::args::ToolArg;
 use crate::cli::Cli;
+use crate::cli::exec::exec_program;
 use crate::config::{Config, Settings};
-use crate::duration;
 use crate::task::task_helpers::task_needs_permit;
 use crate::task::task_list::{get_task_lists, resolve_depends};
 use crate::task::task_output::TaskOutput;
 use crate::task::task_output_handler::OutputHandler;
-use crate::task::{Deps, Task};
+use crate::task::{Deps, GetMatchingExt, Task, build_task_ref_map};
 use crate::ui::{ctrlc, style};
+use crate::{duration, file};
 use clap::{CommandFactory, ValueHint};
 use eyre::{Result, bail, eyre};
 use itertools::Itertools;
@@ -238,6 +239,19 @@ impl Run {
             return Ok(());
         }
 
+        if let Some(script) = self.find_tool_script(&config).await? {
+            let ts = config.get_toolset().await?;
+            let env = ts.env_with_path(&config).await?;
+            let args = self
+                .args
+                .iter()
+                .skip(1)
+                .chain(self.args_last.iter())
+                .cloned()
+                .collect_vec();
+            return exec_program(script, args, env);
+        }
+
         time!("run init");
         let tmpdir = tempfile::tempdir()?;
         self.tmpdir = tmpdir.path().to_path_buf();
@@ -276,6 +290,34 @@ impl Run {
         Ok(())
     }
 
+    /// `mise run <tool> <script> [args]…` runs a script shipped in an installed tool's
+    /// install directory. This only applies when there is no task named `<tool>`.
+    async fn find_tool_script(&self, config: &Arc<Config>) -> Result<Option<PathBuf>> {
+        let Some(script) = self.args.first() else {
+            return Ok(None);
+        };
+        let ts = config.get_toolset().await?;
+        let Some((_, tv)) = ts
+            .list_current_installed_versions(config)
+            .into_iter()
+            .find(|(b, _)| b.ba().short == self.task || b.ba().full() == self.task)
+        else {
+            return Ok(None);
+        };
+        let tasks = config.tasks().await?;
+        if !build_task_ref_map(tasks.iter())
+            .get_matching(&self.task)?
+            .is_empty()
+        {
+            return Ok(None);
+        }
+        let path = find_script(&tv.install_path(), script)?;
+        if let Some(path) = &path {
+            debug!("running {} script {}", tv, path.display());
+        }
+        Ok(path)
+    }
+
     fn get_clap_command(&self) -> clap::Command {
         Cli::command()
             .get_subcommands()
@@ -591,6 +633,25 @@ impl Run {
     }
 }
 
+/// Finds `name` in a tool's install directory, either as a relative path or as a file
+/// with that name anywhere beneath it, preferring the shallowest match
+fn find_script(install_path: &Path, name: &str) -> Result<Option<PathBuf>> {
+    if Path::new(name)
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return Ok(None);
+    }
+    let direct = install_path.join(name);
+    if direct.is_file() {
+        return Ok(Some(direct));
+    }
+    Ok(file::recursive_ls(install_path)?
+        .into_iter()
+        .filter(|p| p.file_name().is_some_and(|f| f == name))
+        .min_by_key(|p| p.components().count()))
+}
+
 static AFTER_LONG_HELP: &str = color_print::cstr!(
     r#"<bold><underline>Examples:</underline></bold>
 
@@ -610,5 +671,9 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
 
     # Execute multiple tasks each with their own arguments.
     $ <bold>mise tasks cmd1 arg1 arg2 ::: cmd2 arg1 arg2</bold>
+
+    # Runs the "completions.sh" script shipped with the installed "github:owner/tool",
+    # when there is no task with that name.
+    $ <bold>mise run github:owner/tool completions.sh --shell zsh</bold>
 "#
 );
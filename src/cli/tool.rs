@@ -1,9 +1,14 @@
 use crate::ui::style;
-use eyre::Result;
+use eyre::{Result, bail, eyre};
 use itertools::Itertools;
 use serde_derive::Serialize;
+use std::sync::Arc;
 
-use crate::cli::args::BackendArg;
+use crate::backend::backend_type::BackendType;
+use crate::backend::github::UnifiedGitBackend;
+use crate::backend::platform_target::PlatformTarget;
+use crate::cli::args::ToolArg;
+use crate::cli::install::override_platform;
 use crate::config::Config;
 use crate::toolset::{ToolSource, ToolVersionOptions, ToolsetBuilder};
 use crate::ui::table;
@@ -13,11 +18,17 @@ use crate::ui::table;
 #[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
 pub struct Tool {
     /// Tool name to get information about
-    tool: BackendArg,
+    #[clap(value_name = "TOOL@VERSION")]
+    tool: ToolArg,
     /// Output in JSON format
     #[clap(long, short = 'J')]
     json: bool,
 
+    /// Platform to show the download URL for
+    /// e.g.: linux-x64, macos-arm64, windows-x64
+    #[clap(long, short, requires = "show_download_url", verbatim_doc_comment)]
+    platform: Option<String>,
+
     #[clap(flatten)]
     filter: ToolInfoFilter,
 }
@@ -49,6 +60,10 @@ pub struct ToolInfoFilter {
     #[clap(long)]
     requested: bool,
 
+    /// Only show the download URL of the requested version, without installing it
+    #[clap(long)]
+    show_download_url: bool,
+
     /// Only show tool options
     #[clap(long)]
     tool_options: bool,
@@ -56,12 +71,20 @@ pub struct ToolInfoFilter {
 
 impl Tool {
     pub async fn run(self) -> Result<()> {
+        if let Some(platform) = &self.platform {
+            override_platform(platform)?;
+        }
         let config = Config::get().await?;
+        if self.filter.show_download_url {
+            return self.show_download_url(&config).await;
+        }
         let mut ts = ToolsetBuilder::new().build(&config).await?;
         ts.resolve(&config).await?;
-        let tvl = ts.versions.get(&self.tool);
+        let tvl = ts.versions.get(self.tool.ba.as_ref());
         let tv = tvl.map(|tvl| tvl.versions.first().unwrap());
-        let ba = tv.map(|tv| tv.ba()).unwrap_or_else(|| &self.tool);
+        let ba = tv
+            .map(|tv| tv.ba())
+            .unwrap_or_else(|| self.tool.ba.as_ref());
 
         // Check if the backend exists and fail if it doesn't
         let backend = match ba.backend() {
@@ -80,6 +103,24 @@ impl Tool {
         } else {
             None
         };
+        // only shown in the full output, it costs a few release API requests
+        let changelog_url = match (tv, ba.backend_type()) {
+            (Some(tv), BackendType::Github | BackendType::Gitlab | BackendType::Codeberg)
+                if !self.is_filtered() =>
+            {
+                match UnifiedGitBackend::from_arg(ba.clone())
+                    .changelog_url(&tv.version)
+                    .await
+                {
+                    Ok(url) => url,
+                    Err(err) => {
+                        debug!("failed to get changelog url for {ba}: {err:#}");
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
         let info = ToolInfo {
             backend: ba.full(),
             description,
@@ -105,6 +146,7 @@ impl Tool {
             }),
             config_source: tvl.map(|tvl| tvl.source.clone()),
             tool_options: ba.opts(),
+            changelog_url,
         };
 
         if self.json {
@@ -114,6 +156,46 @@ impl Tool {
         }
     }
 
+    /// Whether only a single field is shown rather than all of them
+    fn is_filtered(&self) -> bool {
+        self.filter.active
+            || self.filter.backend_
+            || self.filter.config_source
+            || self.filter.description
+            || self.filter.installed
+            || self.filter.requested
+            || self.filter.tool_options
+    }
+
+    async fn show_download_url(&self, config: &Arc<Config>) -> Result<()> {
+        let ts = ToolsetBuilder::new()
+            .with_args(std::slice::from_ref(&self.tool))
+            .build(config)
+            .await?;
+        let tv = ts
+            .versions
+            .get(self.tool.ba.as_ref())
+            .and_then(|tvl| tvl.versions.first())
+            .ok_or_else(|| eyre!("{} is not a configured tool, specify a version", self.tool))?;
+        let url = match tv.ba().backend_type() {
+            BackendType::Github | BackendType::Gitlab | BackendType::Codeberg => {
+                UnifiedGitBackend::from_arg(tv.ba().clone())
+                    .download_url(tv)
+                    .await?
+            }
+            _ => {
+                let target = PlatformTarget::from_current();
+                let info = tv.backend()?.resolve_lock_info(tv, &target).await?;
+                let Some(url) = info.url else {
+                    bail!("{tv} does not have a download URL");
+                };
+                url
+            }
+        };
+        miseprintln!("{url}");
+        Ok(())
+    }
+
     fn output_json(&self, info: ToolInfo) -> Result<()> {
         if self.filter.backend_ {
             miseprintln!("{}", serde_json::to_string_pretty(&info.backend)?);
@@ -242,6 +324,9 @@ impl Tool {
                         .join(","),
                 ));
             }
+            if let Some(changelog_url) = info.changelog_url {
+                table.push(("Changelog:", changelog_url));
+            }
             let mut table = tabled::Table::new(table);
             table::default_style(&mut table, true);
             miseprintln!("{table}");
@@ -260,6 +345,7 @@ struct ToolInfo {
     active_versions: Option<Vec<String>>,
     config_source: Option<ToolSource>,
     tool_options: ToolVersionOptions,
+    changelog_url: Option<String>,
 }
 
 static AFTER_LONG_HELP: &str = color_print::cstr!(
@@ -272,5 +358,8 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     Requested Version:  20
     Config Source:      ~/.config/mise/mise.toml
     Tool Options:       [none]
+
+    $ <bold>mise tool github:cli/cli@2.62.0 --show-download-url --platform linux-x64</bold>
+    https://github.com/cli/cli/releases/download/v2.62.0/gh_2.62.0_linux_amd64.tar.gz
 "#
 );
@@ -52,6 +52,13 @@ pub struct Upgrade {
     #[clap(long, short = 'n', verbatim_doc_comment)]
     dry_run: bool,
 
+    /// Exit with an error if any tool can be upgraded, without installing anything
+    ///
+    /// Prints the current and latest version of each outdated tool, e.g.: to check in CI
+    /// that all tools are on the latest version matching mise.toml.
+    #[clap(long, verbatim_doc_comment, conflicts_with_all = ["interactive", "dry_run"])]
+    check: bool,
+
     /// Directly pipe stdin/stdout/stderr from plugin to user
     /// Sets --jobs=1
     #[clap(long, overrides_with = "jobs")]
@@ -84,6 +91,8 @@ impl Upgrade {
                     ""
                 );
             }
+        } else if self.check {
+            return check(&outdated);
         } else {
             self.upgrade(&mut config, outdated).await?;
         }
@@ -255,6 +264,20 @@ impl Upgrade {
     }
 }
 
+/// Prints the outdated tools as a diff of their current and latest versions
+fn check(outdated: &[OutdatedInfo]) -> Result<()> {
+    for o in outdated {
+        if let Some(current) = &o.current {
+            miseprintln!("-{}@{current}", o.name);
+        }
+        miseprintln!("+{}@{}", o.name, o.latest);
+    }
+    Err(eyre!(
+        "{} tool(s) can be upgraded, run `mise upgrade` to install them",
+        outdated.len()
+    ))
+}
+
 static AFTER_LONG_HELP: &str = color_print::cstr!(
     r#"<bold><underline>Examples:</underline></bold>
 
@@ -273,6 +296,9 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     # Just print what would be done, don't actually do it
     $ <bold>mise upgrade --dry-run</bold>
 
+    # Fail if any tool can be upgraded, e.g.: in CI
+    $ <bold>mise upgrade --check</bold>
+
     # Upgrades node and python to the latest versions
     $ <bold>mise upgrade node python</bold>
 
@@ -0,0 +1,310 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::LazyLock as Lazy;
+
+use eyre::Result;
+use indexmap::IndexMap;
+use serde_derive::Deserialize;
+
+use crate::cli::args::BackendArg;
+use crate::config::Settings;
+use crate::config::config_file::ConfigFile;
+use crate::file;
+use crate::plugins::core::CORE_PLUGINS;
+use crate::registry::REGISTRY;
+use crate::toolset::{ToolRequest, ToolRequestSet, ToolSource};
+
+use super::ConfigFileType;
+
+/// Config files from other tools that mise can read tool versions from.
+/// These are opt-in with the `config_adapters` setting and have lower
+/// priority than mise.toml and idiomatic version files in the same directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Adapter {
+    Brewfile,
+    PackageJson,
+}
+
+impl Adapter {
+    pub fn filename(&self) -> &'static str {
+        match self {
+            Adapter::Brewfile => "Brewfile",
+            Adapter::PackageJson => "package.json",
+        }
+    }
+
+    pub fn from_filename(filename: &str) -> Option<Self> {
+        enabled().into_iter().find(|a| a.filename() == filename)
+    }
+}
+
+/// adapters enabled with the `config_adapters` setting
+pub fn enabled() -> Vec<Adapter> {
+    let Ok(settings) = Settings::try_get() else {
+        return vec![];
+    };
+    settings
+        .config_adapters
+        .iter()
+        .filter_map(|a| match a.parse() {
+            Ok(adapter) => Some(adapter),
+            Err(_) => {
+                warn!("unknown config adapter: {a}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Homebrew formulae that map to a mise tool of a different name
+static BREW_FORMULAE: Lazy<IndexMap<&'static str, &'static str>> = Lazy::new(|| {
+    IndexMap::from([
+        ("golang", "go"),
+        ("kubernetes-cli", "kubectl"),
+        ("nodejs", "node"),
+        ("openjdk", "java"),
+        ("python3", "python"),
+        ("rustup", "rust"),
+        ("temurin", "java"),
+    ])
+});
+
+#[derive(Debug, Clone)]
+pub struct AdapterFile {
+    path: PathBuf,
+    tools: ToolRequestSet,
+}
+
+impl AdapterFile {
+    pub fn init(path: PathBuf) -> Self {
+        Self {
+            path,
+            tools: ToolRequestSet::new(),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self> {
+        trace!("parsing config adapter: {}", path.display());
+        let body = file::read_to_string(path)?;
+        let filename = path.file_name().unwrap_or_default().to_string_lossy();
+        let tools = match Adapter::from_filename(&filename) {
+            Some(Adapter::Brewfile) => parse_brewfile(&body),
+            Some(Adapter::PackageJson) => parse_package_json(&body)?,
+            None => vec![],
+        };
+        let source = ToolSource::IdiomaticVersionFile(path.to_path_buf());
+        let mut trs = ToolRequestSet::new();
+        for (tool, version) in tools {
+            if !REGISTRY.contains_key(tool.as_str()) && !CORE_PLUGINS.contains_key(&tool) {
+                debug!("{}: skipping unknown tool {tool}", path.display());
+                continue;
+            }
+            let ba = BackendArg::from(tool.as_str());
+            let tr = ToolRequest::new(Arc::new(ba), &version, source.clone())?;
+            trs.add_version(tr, &source);
+        }
+        Ok(Self {
+            path: path.to_path_buf(),
+            tools: trs,
+        })
+    }
+}
+
+/// Reads `brew "name"` and `brew install name` lines from a Brewfile.
+/// Versioned formulae like `python@3.12` are pinned to that version, anything
+/// else resolves to the latest version.
+fn parse_brewfile(body: &str) -> Vec<(String, String)> {
+    let mut tools = vec![];
+    for line in body.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some(rest) = line.strip_prefix("brew ") else {
+            continue;
+        };
+        let rest = rest.trim();
+        let formulae = match rest.strip_prefix("install ") {
+            Some(names) => names
+                .split_whitespace()
+                .filter(|n| !n.starts_with('-'))
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>(),
+            None => rest
+                .split(',')
+                .next()
+                .map(|n| n.trim().trim_matches(|c| c == '"' || c == '\''))
+                .filter(|n| !n.is_empty())
+                .map(|n| vec![n.to_string()])
+                .unwrap_or_default(),
+        };
+        for formula in formulae {
+            // strip a tap prefix like "hashicorp/tap/terraform"
+            let formula = formula.rsplit('/').next().unwrap_or_default();
+            let (name, version) = match formula.split_once('@') {
+                Some((name, version)) => (name, version),
+                None => (formula, "latest"),
+            };
+            let name = BREW_FORMULAE.get(name).copied().unwrap_or(name);
+            tools.push((name.to_string(), version.to_string()));
+        }
+    }
+    tools
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PackageJson {
+    #[serde(default)]
+    engines: IndexMap<String, String>,
+    #[serde(default)]
+    volta: IndexMap<String, String>,
+}
+
+/// Reads `volta` pins and `engines` ranges from package.json. Volta pins take
+/// precedence over engines since they are exact versions.
+fn parse_package_json(body: &str) -> Result<Vec<(String, String)>> {
+    let pkg: PackageJson = serde_json::from_str(body)?;
+    let mut tools: IndexMap<String, String> = IndexMap::new();
+    for (tool, range) in &pkg.engines {
+        match engine_range_to_version(range) {
+            Some(version) => {
+                tools.insert(tool.clone(), version);
+            }
+            None => debug!("package.json: unsupported engines range for {tool}: {range}"),
+        }
+    }
+    for (tool, version) in &pkg.volta {
+        if tool == "extends" {
+            continue;
+        }
+        tools.insert(tool.clone(), version.clone());
+    }
+    Ok(tools.into_iter().collect())
+}
+
+/// Converts a simple semver range into a mise version prefix, e.g.: "^18.2.0" -> "18",
+/// "~18.2.0" -> "18.2", ">=18" -> "latest". Compound ranges are not supported.
+fn engine_range_to_version(range: &str) -> Option<String> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" || range == "x" {
+        return Some("latest".into());
+    }
+    if range.contains(' ') || range.contains('<') || range.contains("||") {
+        return None;
+    }
+    let (op, version) = match range.find(|c: char| c.is_ascii_digit()) {
+        Some(i) => range.split_at(i),
+        None => return None,
+    };
+    let version = version.trim_end_matches(".x").trim_end_matches(".*");
+    let parts = version.split('.').collect::<Vec<_>>();
+    let version = match op.trim_end_matches('v') {
+        "" | "=" => version.to_string(),
+        "^" => parts[0].to_string(),
+        "~" => parts[..parts.len().min(2)].join("."),
+        ">=" | ">" => "latest".to_string(),
+        _ => return None,
+    };
+    Some(version)
+}
+
+impl ConfigFile for AdapterFile {
+    fn config_type(&self) -> ConfigFileType {
+        ConfigFileType::Adapter
+    }
+
+    fn get_path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn remove_tool(&self, _fa: &BackendArg) -> Result<()> {
+        unimplemented!()
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn replace_versions(
+        &self,
+        _plugin_name: &BackendArg,
+        _versions: Vec<ToolRequest>,
+    ) -> Result<()> {
+        unimplemented!()
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn save(&self) -> Result<()> {
+        unimplemented!()
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn dump(&self) -> Result<String> {
+        unimplemented!()
+    }
+
+    fn source(&self) -> ToolSource {
+        ToolSource::IdiomaticVersionFile(self.path.clone())
+    }
+
+    fn to_tool_request_set(&self) -> Result<ToolRequestSet> {
+        Ok(self.tools.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn pairs(v: &[(&str, &str)]) -> Vec<(String, String)> {
+        v.iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_brewfile() {
+        let body = r#"
+tap "hashicorp/tap"
+brew "jq"
+brew "python@3.12", link: true # pinned
+brew "hashicorp/tap/terraform"
+brew install golang kubernetes-cli
+cask "firefox"
+"#;
+        assert_eq!(
+            parse_brewfile(body),
+            pairs(&[
+                ("jq", "latest"),
+                ("python", "3.12"),
+                ("terraform", "latest"),
+                ("go", "latest"),
+                ("kubectl", "latest"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_package_json() {
+        let body = r#"{
+  "name": "app",
+  "engines": {"node": "^20.11.0", "pnpm": ">=8", "npm": ">=9 <11"},
+  "volta": {"node": "20.11.1", "yarn": "1.22.19"}
+}"#;
+        assert_eq!(
+            parse_package_json(body).unwrap(),
+            pairs(&[("node", "20.11.1"), ("pnpm", "latest"), ("yarn", "1.22.19")])
+        );
+    }
+
+    #[test]
+    fn test_engine_range_to_version() {
+        assert_eq!(engine_range_to_version("18.2.0").as_deref(), Some("18.2.0"));
+        assert_eq!(engine_range_to_version("^18.2.0").as_deref(), Some("18"));
+        assert_eq!(engine_range_to_version("~18.2.0").as_deref(), Some("18.2"));
+        assert_eq!(engine_range_to_version("18.x").as_deref(), Some("18"));
+        assert_eq!(engine_range_to_version("v20").as_deref(), Some("20"));
+        assert_eq!(engine_range_to_version(">=18").as_deref(), Some("latest"));
+        assert_eq!(engine_range_to_version("*").as_deref(), Some("latest"));
+        assert_eq!(engine_range_to_version(">=18 <21"), None);
+        assert_eq!(engine_range_to_version("^18 || ^20"), None);
+    }
+}
@@ -1,4 +1,4 @@
-use eyre::{WrapErr, eyre};
+use eyre::{WrapErr, bail, eyre};
 use indexmap::IndexMap;
 use itertools::Itertools;
 use once_cell::sync::OnceCell;
@@ -13,7 +13,9 @@ use std::{
     sync::{Mutex, MutexGuard},
 };
 use tera::Context as TeraContext;
-use toml_edit::{Array, DocumentMut, InlineTable, Item, Key, Value, table, value};
+use toml_edit::{
+    Array, DocumentMut, InlineTable, Item, Key, Table, TableLike, Value, table, value,
+};
 use versions::Versioning;
 
 use crate::cli::args::{BackendArg, ToolVersionType};
@@ -22,15 +24,18 @@ use crate::config::config_file::{config_root, toml::deserialize_arr};
 use crate::config::env_directive::{AgeFormat, EnvDirective, EnvDirectiveOptions, RequiredValue};
 use crate::config::settings::SettingsPartial;
 use crate::config::{Alias, AliasMap, Config};
-use crate::file;
 use crate::file::{create_dir_all, display_path};
 use crate::hooks::{Hook, Hooks};
 use crate::redactions::Redactions;
 use crate::registry::REGISTRY;
 use crate::task::Task;
 use crate::tera::{BASE_CONTEXT, get_tera};
-use crate::toolset::{ToolRequest, ToolRequestSet, ToolSource, ToolVersionOptions};
+use crate::toolset::{
+    ENV_EXPANDED_OPTIONS, ToolRequest, ToolRequestSet, ToolSource, ToolVersionOptions,
+    expand_env_vars,
+};
 use crate::watch_files::WatchFile;
+use crate::{env, file};
 
 use super::{ConfigFileType, min_version::MinVersionSpec};
 
@@ -56,7 +61,7 @@ pub struct MiseToml {
     doc: Mutex<OnceCell<DocumentMut>>,
     #[serde(default)]
     hooks: IndexMap<Hooks, toml::Value>,
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_tools")]
     tools: Mutex<IndexMap<BackendArg, MiseTomlToolList>>,
     #[serde(default)]
     plugins: HashMap<String, String>,
@@ -438,6 +443,18 @@ impl ConfigFile for MiseToml {
             && let Some(tools) = tools.as_table_like_mut()
         {
             tools.remove(&fa.to_string());
+            // also drop it from the active overrides or they would bring it back
+            if env_override_tables(tools).is_some() {
+                for name in env::MISE_ENV.iter() {
+                    if let Some(overrides) = tools
+                        .get_mut("env")
+                        .and_then(|e| e.get_mut(name))
+                        .and_then(|t| t.as_table_like_mut())
+                    {
+                        overrides.remove(&fa.to_string());
+                    }
+                }
+            }
             if tools.is_empty() {
                 doc.as_table_mut().remove("tools");
             }
@@ -476,6 +493,7 @@ impl ConfigFile for MiseToml {
             .or_insert_with(table)
             .as_table_mut()
             .unwrap();
+        let tools = tools_table_for(tools, ba, &env::MISE_ENV)?;
 
         // create a key from the short name preserving any decorations like prefix/suffix if the key already exists
         let key = get_key_with_decor(tools, ba.short.as_str());
@@ -577,8 +595,12 @@ impl ConfigFile for MiseToml {
             for tool in &tvp.0 {
                 let version = self.parse_template_with_context(&context, &tool.tt.to_string())?;
                 let tvr = if let Some(mut options) = tool.options.clone() {
-                    for v in options.opts.values_mut() {
+                    for (k, v) in options.opts.iter_mut() {
                         *v = self.parse_template_with_context(&context, v)?;
+                        if ENV_EXPANDED_OPTIONS.contains(&k.as_str()) {
+                            *v = expand_env_vars(v, |name| std::env::var(name).ok())
+                                .wrap_err_with(|| format!("failed to expand {ba} option {k}"))?;
+                        }
                     }
                     let mut ba = ba.clone();
                     let mut ba_opts = ba.opts().clone();
@@ -1348,7 +1370,7 @@ impl<'de> de::Deserialize<'de> for MiseTomlToolList {
                         _ => {
                             // Handle nested structures
                             match v {
-                                toml::Value::Table(_) => {
+                                toml::Value::Table(_) | toml::Value::Array(_) => {
                                     // Store as TOML string, will be flattened later
                                     options.opts.insert(k, v.to_string());
                                 }
@@ -1658,6 +1680,89 @@ impl<'de> de::Deserialize<'de> for Alias {
     }
 }
 
+/// `[tools]` along with the `[tools.env.<MISE_ENV>]` override tables
+#[derive(Debug, Default, Deserialize)]
+struct ToolsWithEnv {
+    #[serde(default)]
+    env: Option<EnvOverridesOrTool>,
+    #[serde(flatten)]
+    tools: IndexMap<BackendArg, MiseTomlToolList>,
+}
+
+/// `tools.env` holds the override tables, unless it is a tool named "env"
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum EnvOverridesOrTool {
+    Overrides(IndexMap<String, IndexMap<BackendArg, MiseTomlToolList>>),
+    Tool(MiseTomlToolList),
+}
+
+impl ToolsWithEnv {
+    /// Applies the overrides for each active environment on top of the base tools.
+    /// Later environments take precedence, same as `mise.{MISE_ENV}.toml` files.
+    fn resolve(self, envs: &[String]) -> IndexMap<BackendArg, MiseTomlToolList> {
+        let Self { env, mut tools } = self;
+        let mut env = match env {
+            Some(EnvOverridesOrTool::Overrides(env)) => env,
+            Some(EnvOverridesOrTool::Tool(tool)) => {
+                tools.insert("env".into(), tool);
+                return tools;
+            }
+            None => return tools,
+        };
+        for name in envs {
+            if let Some(overrides) = env.shift_remove(name) {
+                tools.extend(overrides);
+            }
+        }
+        tools
+    }
+}
+
+/// `tools.env` if it holds `[tools.env.<name>]` tables rather than a tool named "env"
+fn env_override_tables(tools: &dyn TableLike) -> Option<&dyn TableLike> {
+    let env = tools.get("env")?.as_table_like()?;
+    env.iter().all(|(_, v)| v.is_table_like()).then_some(env)
+}
+
+/// The table to write `ba` to: the `[tools.env.<name>]` table of the last active environment
+/// that overrides it, since an edit to the base `[tools]` entry would be shadowed by it
+fn tools_table_for<'a>(
+    tools: &'a mut Table,
+    ba: &BackendArg,
+    envs: &[String],
+) -> eyre::Result<&'a mut Table> {
+    let Some(name) = env_override_tables(tools).and_then(|overrides| {
+        envs.iter().rev().find(|name| {
+            overrides
+                .get(name)
+                .and_then(|t| t.as_table_like())
+                .is_some_and(|t| t.contains_key(&ba.short) || t.contains_key(&ba.full()))
+        })
+    }) else {
+        return Ok(tools);
+    };
+    let name = name.clone();
+    match tools
+        .get_mut("env")
+        .and_then(|e| e.get_mut(&name))
+        .and_then(|t| t.as_table_mut())
+    {
+        Some(overrides) => Ok(overrides),
+        None => bail!("{ba} is overridden by an inline tools.env.{name} table, edit it manually"),
+    }
+}
+
+fn deserialize_tools<'de, D>(
+    deserializer: D,
+) -> Result<Mutex<IndexMap<BackendArg, MiseTomlToolList>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let tools = <ToolsWithEnv as de::Deserialize>::deserialize(deserializer)?;
+    Ok(Mutex::new(tools.resolve(&env::MISE_ENV)))
+}
+
 fn is_tools_sorted(tools: &IndexMap<BackendArg, MiseTomlToolList>) -> bool {
     let mut last = None;
     for k in tools.keys() {
@@ -1680,6 +1785,9 @@ mod tests {
     use insta::{assert_debug_snapshot, assert_snapshot};
     use test_log::test;
 
+    use crate::backend::static_helpers::{
+        asset_pattern, string_list_option, transform_version, version_transform,
+    };
     use crate::dirs;
     use crate::test::replace_path;
     use crate::toolset::ToolRequest;
@@ -2015,6 +2123,190 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn test_tools_env_overrides() {
+        let toml = formatdoc! {r#"
+        node = "20"
+        jq = "1.7"
+        [env.production]
+        node = "22"
+        [env.staging]
+        node = "21"
+        python = "3.12"
+        "#};
+        let resolve = |envs: &[&str]| {
+            let tools: ToolsWithEnv = toml::from_str(&toml).unwrap();
+            let envs = envs.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            tools
+                .resolve(&envs)
+                .into_iter()
+                .map(|(ba, tl)| format!("{}@{}", ba.short, tl.0[0].tt))
+                .join(" ")
+        };
+        assert_eq!(resolve(&[]), "node@20 jq@1.7");
+        assert_eq!(resolve(&["production"]), "node@22 jq@1.7");
+        assert_eq!(
+            resolve(&["production", "staging"]),
+            "node@21 jq@1.7 python@3.12"
+        );
+    }
+
+    #[test]
+    fn test_tools_env_tool() {
+        let tools: ToolsWithEnv = toml::from_str(r#"env = "1.0""#).unwrap();
+        let tools = tools.resolve(&["production".to_string()]);
+        assert_eq!(tools[&BackendArg::from("env")].0[0].tt.to_string(), "1.0");
+    }
+
+    #[tokio::test]
+    async fn test_tools_table_for_env_override() {
+        let _config = Config::get().await.unwrap();
+        let mut doc: DocumentMut = formatdoc! {r#"
+        [tools]
+        node = "20"
+        jq = "1.7"
+        [tools.env.production]
+        node = "22"
+        "#}
+        .parse()
+        .unwrap();
+        let tools = doc["tools"].as_table_mut().unwrap();
+        let production = ["production".to_string()];
+        let node = BackendArg::from("node");
+        tools_table_for(tools, &node, &production)
+            .unwrap()
+            .insert("node", value("23"));
+        tools_table_for(tools, &BackendArg::from("jq"), &production)
+            .unwrap()
+            .insert("jq", value("1.8"));
+        tools_table_for(tools, &node, &[]).unwrap()["node"] = value("21");
+        assert_eq!(
+            doc.to_string(),
+            formatdoc! {r#"
+            [tools]
+            node = "21"
+            jq = "1.8"
+            [tools.env.production]
+            node = "23"
+            "#}
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_array_options() {
+        let _config = Config::get().await.unwrap();
+        let cf = parse(formatdoc! {r#"
+        [tools."github:owner/repo"]
+        version = "1.0.0"
+        fallback_repos = ["mirror/repo", "github.example.com/owner/repo"]
+        "#});
+        let trs = cf.to_tool_request_set().unwrap();
+        let (_, trs, _) = trs.iter().next().unwrap();
+        assert_eq!(
+            string_list_option(&trs[0].options(), "fallback_repos"),
+            vec!["mirror/repo", "github.example.com/owner/repo"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_asset_pattern_overrides() {
+        let _config = Config::get().await.unwrap();
+        let cf = parse(formatdoc! {r#"
+        [tools."github:owner/repo"]
+        version = "2.1.0"
+        asset_pattern = "tool-{{version}}.tar.gz"
+        asset_pattern_overrides = [
+            {{ version = ">=2.0", pattern = "tool-v{{version}}.tar.gz" }},
+            {{ version = ">=1.0", pattern = "old-tool-{{version}}.tar.gz" }},
+        ]
+        "#});
+        let trs = cf.to_tool_request_set().unwrap();
+        let (_, trs, _) = trs.iter().next().unwrap();
+        let opts = trs[0].options();
+        for (version, pattern) in [
+            ("2.1.0", "tool-v{version}.tar.gz"),
+            ("1.5.0", "old-tool-{version}.tar.gz"),
+            ("0.9.0", "tool-{version}.tar.gz"),
+        ] {
+            assert_eq!(
+                asset_pattern(&opts, version).unwrap().as_deref(),
+                Some(pattern)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_script_options_not_expanded() {
+        let _config = Config::get().await.unwrap();
+        let cf = parse(formatdoc! {r#"
+        [tools."github:owner/repo"]
+        version = "1.0.0"
+        pre_extract_script = "echo ${{MISE_TEST_UNSET_VAR}}"
+        post_install_script = "echo ${{MISE_TEST_UNSET_VAR}}"
+        verify_binary = "echo ${{MISE_TEST_UNSET_VAR}}"
+        "#});
+        let trs = cf.to_tool_request_set().unwrap();
+        let (_, trs, _) = trs.iter().next().unwrap();
+        let opts = trs[0].options();
+        for option in ["pre_extract_script", "post_install_script", "verify_binary"] {
+            assert_eq!(opts.get(option).unwrap(), "echo ${MISE_TEST_UNSET_VAR}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_env_vars_only_expanded_in_paths_and_urls() {
+        let _config = Config::get().await.unwrap();
+        let cf = parse(formatdoc! {r#"
+        [tools."gitlab:owner/repo"]
+        version = "1.0.0"
+        api_url = "${{MISE_TEST_UNSET_VAR:-https://gitlab.example.com}}/api/v4"
+        checksum_url = "${{MISE_TEST_UNSET_VAR:-https://gitlab.example.com}}/SHA256SUMS"
+        asset_hash_url = "${{MISE_TEST_UNSET_VAR:-https://gitlab.example.com}}/tool.sha256"
+        fallback_repos = ["${{MISE_TEST_UNSET_VAR:-mirror}}/repo"]
+        asset_pattern = "tool-${{MISE_TEST_UNSET_VAR}}.tar.gz"
+        "#});
+        let trs = cf.to_tool_request_set().unwrap();
+        let (_, trs, _) = trs.iter().next().unwrap();
+        let opts = trs[0].options();
+        assert_eq!(
+            opts.get("api_url").unwrap(),
+            "https://gitlab.example.com/api/v4"
+        );
+        assert_eq!(
+            opts.get("checksum_url").unwrap(),
+            "https://gitlab.example.com/SHA256SUMS"
+        );
+        assert_eq!(
+            opts.get("asset_hash_url").unwrap(),
+            "https://gitlab.example.com/tool.sha256"
+        );
+        assert_eq!(
+            string_list_option(&opts, "fallback_repos"),
+            vec!["mirror/repo"]
+        );
+        assert_eq!(
+            opts.get("asset_pattern").unwrap(),
+            "tool-${MISE_TEST_UNSET_VAR}.tar.gz"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_version_transform_capture_group() {
+        let _config = Config::get().await.unwrap();
+        let cf = parse(formatdoc! {r#"
+        [tools."github:owner/repo"]
+        version = "1.0.0"
+        version_transform = {{ pattern = "^release-(.*)$", replacement = "${{1}}" }}
+        "#});
+        let trs = cf.to_tool_request_set().unwrap();
+        let (_, trs, _) = trs.iter().next().unwrap();
+        let transform = version_transform(&trs[0].options()).unwrap();
+        assert_eq!(
+            transform_version(transform.as_ref(), "release-1.2.3".to_string()),
+            "1.2.3"
+        );
+    }
+
     fn parse(s: String) -> MiseToml {
         let p = CWD.as_ref().unwrap().join(".test.mise.toml");
         file::write(&p, s).unwrap();
@@ -23,6 +23,7 @@ use crate::toolset::{ToolRequest, ToolRequestSet, ToolSource, ToolVersionList, T
 use crate::ui::{prompt, style};
 use crate::watch_files::WatchFile;
 use crate::{backend, config, dirs, env, file, hash};
+use adapter::{Adapter, AdapterFile};
 use eyre::{Result, eyre};
 use idiomatic_version::IdiomaticVersionFile;
 use serde_derive::Deserialize;
@@ -31,6 +32,7 @@ use tool_versions::ToolVersions;
 
 use super::Config;
 
+pub mod adapter;
 pub mod config_root;
 pub mod idiomatic_version;
 pub mod min_version;
@@ -43,6 +45,7 @@ pub enum ConfigFileType {
     MiseToml,
     ToolVersions,
     IdiomaticVersion,
+    Adapter,
 }
 
 pub trait ConfigFile: Debug + Send + Sync {
@@ -228,6 +231,7 @@ async fn init(path: &Path) -> Arc<dyn ConfigFile> {
         Some(ConfigFileType::IdiomaticVersion) => {
             Arc::new(IdiomaticVersionFile::init(path.to_path_buf()))
         }
+        Some(ConfigFileType::Adapter) => Arc::new(AdapterFile::init(path.to_path_buf())),
         _ => panic!("Unknown config file type: {}", path.display()),
     }
 }
@@ -257,6 +261,7 @@ pub async fn parse(path: &Path) -> Result<Arc<dyn ConfigFile>> {
         Some(ConfigFileType::IdiomaticVersion) => {
             Ok(Arc::new(IdiomaticVersionFile::from_file(path).await?))
         }
+        Some(ConfigFileType::Adapter) => Ok(Arc::new(AdapterFile::from_file(path)?)),
         #[allow(clippy::box_default)]
         _ => Ok(Arc::new(MiseToml::default())),
     }
@@ -517,6 +522,7 @@ async fn detect_config_file_type(path: &Path) -> Option<ConfigFileType> {
         .unwrap_or("mise.toml")
     {
         f if filename_is_idiomatic(f.to_string()).await => Some(ConfigFileType::IdiomaticVersion),
+        f if Adapter::from_filename(f).is_some() => Some(ConfigFileType::Adapter),
         f if env::MISE_OVERRIDE_TOOL_VERSIONS_FILENAMES
             .as_ref()
             .is_some_and(|o| o.contains(f)) =>
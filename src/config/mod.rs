@@ -114,10 +114,11 @@ impl Config {
         let idiomatic_files = measure!("config::load idiomatic_files", {
             load_idiomatic_files().await
         });
-        let config_filenames = idiomatic_files
-            .keys()
-            .chain(DEFAULT_CONFIG_FILENAMES.iter())
-            .cloned()
+        let config_filenames = config_file::adapter::enabled()
+            .iter()
+            .map(|a| a.filename().to_string())
+            .chain(idiomatic_files.keys().cloned())
+            .chain(DEFAULT_CONFIG_FILENAMES.iter().cloned())
             .collect_vec();
         let config_paths = measure!("config::load config_paths", {
             load_config_paths(&config_filenames, false)
@@ -1422,6 +1423,16 @@ pub async fn rebuild_shims_and_runtime_symlinks(
             .await
             .wrap_err("failed to rebuild shims")?;
     });
+    rebuild_runtime_symlinks(config, ts, new_versions).await
+}
+
+/// Same as `rebuild_shims_and_runtime_symlinks` without touching the shims, e.g.: for
+/// `mise install --no-shims`
+pub async fn rebuild_runtime_symlinks(
+    config: &Arc<Config>,
+    ts: &Toolset,
+    new_versions: &[ToolVersion],
+) -> Result<()> {
     measure!("rebuilding runtime symlinks", {
         runtime_symlinks::rebuild(config)
             .await
@@ -1429,6 +1440,7 @@ pub async fn rebuild_shims_and_runtime_symlinks(
     });
     measure!("updating lockfiles", {
         lockfile::update_lockfiles(config, ts, new_versions)
+            .await
             .wrap_err("failed to update lockfiles")?;
     });
 
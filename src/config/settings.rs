@@ -302,6 +302,13 @@ impl Settings {
         Self::reset(Some(s));
     }
 
+    /// Applies extra overrides on top of the settings set by global CLI flags
+    pub fn add_cli_overrides(f: impl FnOnce(&mut SettingsPartial)) {
+        let mut s = CLI_SETTINGS.lock().unwrap().clone().unwrap_or_default();
+        f(&mut s);
+        Self::reset(Some(s));
+    }
+
     pub fn parse_settings_file(path: &Path) -> Result<SettingsPartial> {
         let raw = file::read_to_string(path)?;
         let settings_file: SettingsFile = toml::from_str(&raw)?;
@@ -392,6 +399,11 @@ impl Settings {
         Ok(table)
     }
 
+    pub fn cache_max_age_duration(&self) -> Option<Duration> {
+        let age = duration::parse_duration(&self.cache_max_age).unwrap();
+        if age.as_secs() == 0 { None } else { Some(age) }
+    }
+
     pub fn cache_prune_age_duration(&self) -> Option<Duration> {
         let age = duration::parse_duration(&self.cache_prune_age).unwrap();
         if age.as_secs() == 0 { None } else { Some(age) }
@@ -479,6 +491,10 @@ impl Settings {
         match self.arch.as_deref().unwrap_or(ARCH) {
             "x86_64" | "amd64" => "x64",
             "aarch64" | "arm64" => "arm64",
+            // std reports "powerpc64" for both endians
+            "powerpc64" if cfg!(target_endian = "little") => "ppc64le",
+            "ppc64el" | "powerpc64le" => "ppc64le",
+            "zseries" => "s390x",
             other => other,
         }
     }
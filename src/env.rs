@@ -184,6 +184,13 @@ pub static MISE_CEILING_PATHS: Lazy<HashSet<PathBuf>> = Lazy::new(|| {
 });
 pub static MISE_USE_TOML: Lazy<bool> = Lazy::new(|| !var_is_false("MISE_USE_TOML"));
 pub static MISE_LIST_ALL_VERSIONS: Lazy<bool> = Lazy::new(|| var_is_true("MISE_LIST_ALL_VERSIONS"));
+/// Max pages of releases/tags fetched from github/gitlab with MISE_LIST_ALL_VERSIONS, 0 for no limit
+pub static MISE_LIST_ALL_VERSIONS_MAX_PAGES: Lazy<usize> = Lazy::new(|| {
+    var("MISE_LIST_ALL_VERSIONS_MAX_PAGES")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(10)
+});
 pub static ARGV0: Lazy<String> = Lazy::new(|| ARGS.read().unwrap()[0].to_string());
 pub static MISE_BIN_NAME: Lazy<&str> = Lazy::new(|| filename(&ARGV0));
 pub static MISE_LOG_FILE: Lazy<Option<PathBuf>> = Lazy::new(|| var_path("MISE_LOG_FILE"));
@@ -299,6 +306,10 @@ pub static LINUX_DISTRO: Lazy<Option<String>> = Lazy::new(linux_distro);
 pub static PREFER_OFFLINE: Lazy<AtomicBool> =
     Lazy::new(|| prefer_offline(&ARGS.read().unwrap()).into());
 pub static OFFLINE: Lazy<bool> = Lazy::new(|| offline(&ARGS.read().unwrap()));
+/// set by `mise install --skip-checksum --i-know-what-im-doing`
+pub static SKIP_CHECKSUM: AtomicBool = AtomicBool::new(false);
+/// set by `mise install --record-provenance`
+pub static RECORD_PROVENANCE: AtomicBool = AtomicBool::new(false);
 pub static WARN_ON_MISSING_REQUIRED_ENV: Lazy<bool> =
     Lazy::new(|| warn_on_missing_required_env(&ARGS.read().unwrap()));
 /// essentially, this is whether we show spinners or build output on runtime install
@@ -329,6 +340,14 @@ pub static GITLAB_TOKEN: Lazy<Option<String>> =
     Lazy::new(|| get_token(&["MISE_GITLAB_TOKEN", "GITLAB_TOKEN"]));
 pub static MISE_GITLAB_ENTERPRISE_TOKEN: Lazy<Option<String>> =
     Lazy::new(|| get_token(&["MISE_GITLAB_ENTERPRISE_TOKEN"]));
+pub static MISE_CODEBERG_TOKEN: Lazy<Option<String>> =
+    Lazy::new(|| get_token(&["MISE_CODEBERG_TOKEN", "MISE_FORGEJO_TOKEN"]));
+/// when set, cache files are encrypted with a key derived from this value
+pub static MISE_CACHE_KEY: Lazy<Option<String>> =
+    Lazy::new(|| var("MISE_CACHE_KEY").ok().filter(|k| !k.is_empty()));
+/// replaces the default `mise/<version>` user agent of http requests
+pub static MISE_USER_AGENT: Lazy<Option<String>> =
+    Lazy::new(|| var("MISE_USER_AGENT").ok().filter(|ua| !ua.is_empty()));
 
 pub static TEST_TRANCHE: Lazy<usize> = Lazy::new(|| var_u8("TEST_TRANCHE") as usize);
 pub static TEST_TRANCHE_COUNT: Lazy<usize> = Lazy::new(|| var_u8("TEST_TRANCHE_COUNT") as usize);
@@ -640,6 +659,19 @@ fn is_ninja_on_path() -> bool {
     which::which("ninja").is_ok()
 }
 
+/// Whether another page of releases/tags can be fetched from `repo` after `pages` pages, see
+/// MISE_LIST_ALL_VERSIONS_MAX_PAGES
+pub fn more_pages_allowed(pages: usize, repo: &str) -> bool {
+    let max = *MISE_LIST_ALL_VERSIONS_MAX_PAGES;
+    if max != 0 && pages >= max {
+        debug!(
+            "{repo}: stopped after {pages} pages, set MISE_LIST_ALL_VERSIONS_MAX_PAGES to fetch more"
+        );
+        return false;
+    }
+    true
+}
+
 pub fn is_activated() -> bool {
     var("__MISE_DIFF").is_ok()
 }
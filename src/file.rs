@@ -16,6 +16,7 @@ use color_eyre::eyre::{Context, Result};
 use eyre::bail;
 use filetime::{FileTime, set_file_times};
 use flate2::read::GzDecoder;
+use globset::GlobSet;
 use itertools::Itertools;
 use std::sync::LazyLock as Lazy;
 use tar::Archive;
@@ -505,6 +506,20 @@ pub fn make_executable<P: AsRef<Path>>(_path: P) -> Result<()> {
     Ok(())
 }
 
+#[cfg(unix)]
+pub fn chmod<P: AsRef<Path>>(path: P, mode: u32) -> Result<()> {
+    trace!("chmod {mode:o} {}", display_path(&path));
+    let path = path.as_ref();
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        .wrap_err_with(|| format!("failed to chmod {mode:o}: {}", display_path(path)))?;
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn chmod<P: AsRef<Path>>(_path: P, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
 #[cfg(unix)]
 pub async fn make_executable_async<P: AsRef<Path>>(path: P) -> Result<()> {
     trace!("chmod +x {}", display_path(&path));
@@ -698,6 +713,8 @@ pub enum TarFormat {
     Zip,
     #[strum(serialize = "7z")]
     SevenZip,
+    #[strum(serialize = "cab")]
+    Cab,
     #[strum(serialize = "raw")]
     Raw,
 }
@@ -711,6 +728,7 @@ impl TarFormat {
             "zst" | "tzst" => TarFormat::TarZst,
             "zip" => TarFormat::Zip,
             "7z" => TarFormat::SevenZip,
+            "cab" => TarFormat::Cab,
             _ => TarFormat::Raw,
         }
     }
@@ -722,6 +740,10 @@ pub struct TarOptions<'a> {
     pub pr: Option<&'a dyn SingleReport>,
     /// When false, files will be extracted with current timestamp instead of archive's mtime
     pub preserve_mtime: bool,
+    /// Password for encrypted 7z archives
+    pub password: Option<String>,
+    /// Entries to skip, see `is_excluded`
+    pub exclude: Option<&'a GlobSet>,
 }
 
 impl<'a> Default for TarOptions<'a> {
@@ -731,6 +753,8 @@ impl<'a> Default for TarOptions<'a> {
             strip_components: 0,
             pr: None,
             preserve_mtime: true, // Default to preserving mtime for backward compatibility
+            password: None,
+            exclude: None,
         }
     }
 }
@@ -752,15 +776,38 @@ pub fn untar(archive: &Path, dest: &Path, opts: &TarOptions) -> Result<()> {
             dest,
             &ZipOptions {
                 strip_components: opts.strip_components,
+                exclude: opts.exclude,
             },
         );
     } else if format == TarFormat::SevenZip {
-        #[cfg(windows)]
+        if let Some(pr) = &opts.pr {
+            pr.set_message(format!(
+                "extract {}",
+                archive.file_name().unwrap().to_string_lossy()
+            ));
+        }
         return un7z(
             archive,
             dest,
             &SevenZipOptions {
                 strip_components: opts.strip_components,
+                password: opts.password.clone(),
+                exclude: opts.exclude,
+            },
+        );
+    } else if format == TarFormat::Cab {
+        if let Some(pr) = &opts.pr {
+            pr.set_message(format!(
+                "extract {}",
+                archive.file_name().unwrap().to_string_lossy()
+            ));
+        }
+        return uncab(
+            archive,
+            dest,
+            &CabOptions {
+                strip_components: opts.strip_components,
+                exclude: opts.exclude,
             },
         );
     }
@@ -820,7 +867,17 @@ pub fn untar(archive: &Path, dest: &Path, opts: &TarOptions) -> Result<()> {
         // Configure mtime preservation based on options
         entry.set_preserve_mtime(opts.preserve_mtime);
 
-        trace!("extracting {}", entry.path().wrap_err_with(err)?.display());
+        let path = entry.path().wrap_err_with(err)?.to_path_buf();
+        if is_excluded(
+            opts.exclude,
+            &path,
+            entry.header().entry_type().is_dir(),
+            opts.strip_components,
+        ) {
+            trace!("skipping {}", path.display());
+            continue;
+        }
+        trace!("extracting {}", path.display());
         entry.unpack_in(dest).wrap_err_with(err)?;
         // Update position as we extract files
         if let Some(pr) = &opts.pr {
@@ -842,6 +899,12 @@ pub fn untar(archive: &Path, dest: &Path, opts: &TarOptions) -> Result<()> {
     }
 
     if needs_system_tar {
+        if opts.exclude.is_some() {
+            bail!(
+                "files can't be skipped when extracting {} since it contains sparse files",
+                display_path(archive)
+            );
+        }
         // Use system tar for archives with problematic sparse files
         // The tar crate doesn't properly handle certain GNU sparse formats
         debug!("Using system tar for: {}", archive.display());
@@ -869,16 +932,82 @@ pub fn untar(archive: &Path, dest: &Path, opts: &TarOptions) -> Result<()> {
     Ok(())
 }
 
-fn open_tar(format: TarFormat, archive: &Path) -> Result<Box<dyn std::io::Read>> {
-    let f = File::open(archive)?;
+/// Extracts a compressed tarball from `reader` as it is read, e.g.: while it is being
+/// downloaded. Unlike `untar`, archives with GNU sparse files are not supported since
+/// those need to be re-read with the system tar.
+pub fn untar_reader(reader: impl std::io::Read, dest: &Path, opts: &TarOptions) -> Result<()> {
+    debug!("extracting {} stream to {}", opts.format, dest.display());
+    let err = || format!("failed to extract tar to {}", display_path(dest));
+    create_dir_all(dest).wrap_err_with(err)?;
+    let tar = tar_decoder(opts.format, reader)?;
+    for entry in Archive::new(tar).entries().wrap_err_with(err)? {
+        let mut entry = entry.wrap_err_with(err)?;
+        if entry.header().entry_type().is_gnu_sparse() {
+            bail!(
+                "{} contains a sparse file which can't be extracted while streaming",
+                display_path(dest)
+            );
+        }
+        entry.set_preserve_mtime(opts.preserve_mtime);
+        let path = entry.path().wrap_err_with(err)?.to_path_buf();
+        if is_excluded(
+            opts.exclude,
+            &path,
+            entry.header().entry_type().is_dir(),
+            opts.strip_components,
+        ) {
+            trace!("skipping {}", path.display());
+            continue;
+        }
+        trace!("extracting {}", path.display());
+        entry.unpack_in(dest).wrap_err_with(err)?;
+    }
+    strip_archive_path_components(dest, opts.strip_components).wrap_err_with(err)?;
+    Ok(())
+}
+
+/// Whether the archive entry at `path` is skipped by `exclude`, which matches paths as they are
+/// after stripping `strip_components`, e.g.: `**/doc/**`. Directory entries are skipped too when
+/// filtering, directories are created for the files extracted into them so none are left empty.
+fn is_excluded(
+    exclude: Option<&GlobSet>,
+    path: &Path,
+    is_dir: bool,
+    strip_components: usize,
+) -> bool {
+    let Some(exclude) = exclude else {
+        return false;
+    };
+    let stripped = path
+        .components()
+        .skip(strip_components)
+        .collect::<PathBuf>();
+    match stripped.as_os_str().is_empty() {
+        _ if is_dir => true,
+        true => exclude.is_match(path),
+        false => exclude.is_match(stripped),
+    }
+}
+
+fn tar_decoder<'a>(
+    format: TarFormat,
+    r: impl std::io::Read + 'a,
+) -> Result<Box<dyn std::io::Read + 'a>> {
     Ok(match format {
         // TODO: we probably shouldn't assume raw is tar.gz, but this was to retain existing behavior
-        TarFormat::TarGz | TarFormat::Raw => Box::new(GzDecoder::new(f)),
-        TarFormat::TarXz => Box::new(xz2::read::XzDecoder::new(f)),
-        TarFormat::TarBz2 => Box::new(BzDecoder::new(f)),
-        TarFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(f)?),
+        TarFormat::TarGz | TarFormat::Raw => Box::new(GzDecoder::new(r)),
+        TarFormat::TarXz => Box::new(xz2::read::XzDecoder::new(r)),
+        TarFormat::TarBz2 => Box::new(BzDecoder::new(r)),
+        TarFormat::TarZst => Box::new(zstd::stream::read::Decoder::new(r)?),
         TarFormat::Zip => bail!("zip format not supported"),
         TarFormat::SevenZip => bail!("7z format not supported"),
+        TarFormat::Cab => bail!("cab format not supported"),
+        TarFormat::Auto => bail!("tar format must be known"),
+    })
+}
+
+fn open_tar(format: TarFormat, archive: &Path) -> Result<Box<dyn std::io::Read>> {
+    Ok(match format {
         TarFormat::Auto => match archive.extension().and_then(|s| s.to_str()) {
             Some("xz") => open_tar(TarFormat::TarXz, archive)?,
             Some("bz2") => open_tar(TarFormat::TarBz2, archive)?,
@@ -886,6 +1015,7 @@ fn open_tar(format: TarFormat, archive: &Path) -> Result<Box<dyn std::io::Read>>
             Some("zip") => bail!("zip format not supported"),
             _ => open_tar(TarFormat::TarGz, archive)?,
         },
+        _ => tar_decoder(format, File::open(archive)?)?,
     })
 }
 
@@ -926,17 +1056,22 @@ fn strip_archive_path_components(dir: &Path, strip_depth: usize) -> Result<()> {
 }
 
 #[derive(Default)]
-pub struct ZipOptions {
+pub struct ZipOptions<'a> {
     pub strip_components: usize,
+    /// Entries to skip, see `is_excluded`
+    pub exclude: Option<&'a GlobSet>,
 }
 
 pub fn unzip(archive: &Path, dest: &Path, opts: &ZipOptions) -> Result<()> {
     // TODO: show progress
     debug!("unzip {} -d {}", archive.display(), dest.display());
-    ZipArchive::new(File::open(archive)?)
-        .wrap_err_with(|| format!("failed to open zip archive: {}", display_path(archive)))?
-        .extract(dest)
-        .wrap_err_with(|| format!("failed to extract zip archive: {}", display_path(archive)))?;
+    let mut zip = ZipArchive::new(File::open(archive)?)
+        .wrap_err_with(|| format!("failed to open zip archive: {}", display_path(archive)))?;
+    match opts.exclude {
+        Some(_) => unzip_filtered(&mut zip, dest, opts),
+        None => zip.extract(dest).map_err(Into::into),
+    }
+    .wrap_err_with(|| format!("failed to extract zip archive: {}", display_path(archive)))?;
 
     strip_archive_path_components(dest, opts.strip_components).wrap_err_with(|| {
         format!(
@@ -946,6 +1081,34 @@ pub fn unzip(archive: &Path, dest: &Path, opts: &ZipOptions) -> Result<()> {
     })
 }
 
+/// Like `ZipArchive::extract` but skips the entries excluded by `opts.exclude`
+fn unzip_filtered(zip: &mut ZipArchive<File>, dest: &Path, opts: &ZipOptions) -> Result<()> {
+    for i in 0..zip.len() {
+        let mut file = zip.by_index(i)?;
+        let Some(path) = file.enclosed_name() else {
+            bail!("invalid path in zip archive: {}", file.name());
+        };
+        if is_excluded(opts.exclude, &path, file.is_dir(), opts.strip_components) {
+            trace!("skipping {}", path.display());
+            continue;
+        }
+        let out = dest.join(&path);
+        create_dir_all(out.parent().unwrap())?;
+        if file.is_symlink() {
+            let mut target = String::new();
+            std::io::Read::read_to_string(&mut file, &mut target)?;
+            make_symlink(Path::new(&target), &out)?;
+            continue;
+        }
+        std::io::copy(&mut file, &mut File::create(&out)?)?;
+        #[cfg(unix)]
+        if let Some(mode) = file.unix_mode() {
+            fs::set_permissions(&out, fs::Permissions::from_mode(mode))?;
+        }
+    }
+    Ok(())
+}
+
 pub fn un_dmg(archive: &Path, dest: &Path) -> Result<()> {
     debug!(
         "hdiutil attach -quiet -nobrowse -mountpoint {} {}",
@@ -978,15 +1141,44 @@ pub fn un_pkg(archive: &Path, dest: &Path) -> Result<()> {
     Ok(())
 }
 
-#[cfg(windows)]
 #[derive(Default)]
-pub struct SevenZipOptions {
+pub struct SevenZipOptions<'a> {
     pub strip_components: usize,
+    pub password: Option<String>,
+    /// Entries to skip, see `is_excluded`
+    pub exclude: Option<&'a GlobSet>,
 }
 
-#[cfg(windows)]
 pub fn un7z(archive: &Path, dest: &Path, opts: &SevenZipOptions) -> Result<()> {
-    sevenz_rust::decompress_file(archive, dest)
+    debug!("7z x {} -o{}", archive.display(), dest.display());
+    let extract = |entry: &sevenz_rust::SevenZArchiveEntry,
+                   reader: &mut dyn std::io::Read,
+                   path: &PathBuf| {
+        let name = Path::new(entry.name());
+        if is_excluded(
+            opts.exclude,
+            name,
+            entry.is_directory(),
+            opts.strip_components,
+        ) {
+            trace!("skipping {}", name.display());
+            // the entries of solid archives are read from a single stream
+            std::io::copy(reader, &mut std::io::sink()).map_err(sevenz_rust::Error::io)?;
+            return Ok(true);
+        }
+        sevenz_rust::default_entry_extract_fn(entry, reader, path)
+    };
+    let password = opts.password.as_deref().unwrap_or_default();
+    File::open(archive)
+        .map_err(sevenz_rust::Error::io)
+        .and_then(|file| {
+            sevenz_rust::decompress_with_extract_fn_and_password(
+                file,
+                dest,
+                password.into(),
+                extract,
+            )
+        })
         .wrap_err_with(|| format!("failed to extract 7z archive: {}", display_path(archive)))?;
 
     strip_archive_path_components(dest, opts.strip_components).wrap_err_with(|| {
@@ -997,6 +1189,55 @@ pub fn un7z(archive: &Path, dest: &Path, opts: &SevenZipOptions) -> Result<()> {
     })
 }
 
+#[derive(Default)]
+pub struct CabOptions<'a> {
+    pub strip_components: usize,
+    /// Entries to skip, see `is_excluded`
+    pub exclude: Option<&'a GlobSet>,
+}
+
+/// Lists the file names in a cabinet archive, these use `\` as a path separator
+fn cab_file_names<R: std::io::Read + std::io::Seek>(cabinet: &cab::Cabinet<R>) -> Vec<String> {
+    cabinet
+        .folder_entries()
+        .flat_map(|folder| folder.file_entries())
+        .map(|file| file.name().to_string())
+        .collect()
+}
+
+pub fn uncab(archive: &Path, dest: &Path, opts: &CabOptions) -> Result<()> {
+    debug!("expand {} -F:* {}", archive.display(), dest.display());
+    let err = || format!("failed to extract cab archive: {}", display_path(archive));
+    let mut cabinet = cab::Cabinet::new(File::open(archive)?).wrap_err_with(err)?;
+    for name in cab_file_names(&cabinet) {
+        let rel = PathBuf::from(name.replace('\\', "/"));
+        if rel
+            .components()
+            .any(|c| !matches!(c, std::path::Component::Normal(_)))
+        {
+            bail!("invalid path in cab archive: {name}");
+        }
+        if is_excluded(opts.exclude, &rel, false, opts.strip_components) {
+            trace!("skipping {}", rel.display());
+            continue;
+        }
+        let path = dest.join(rel);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        let mut reader = cabinet.read_file(&name).wrap_err_with(err)?;
+        let mut out = File::create(&path)?;
+        std::io::copy(&mut reader, &mut out).wrap_err_with(err)?;
+    }
+
+    strip_archive_path_components(dest, opts.strip_components).wrap_err_with(|| {
+        format!(
+            "failed to strip path components from cab archive: {}",
+            display_path(archive)
+        )
+    })
+}
+
 pub fn split_file_name(path: &Path) -> (String, String) {
     let file_name = path.file_name().unwrap().to_string_lossy();
     let (file_name_base, ext) = file_name
@@ -1085,7 +1326,6 @@ pub fn inspect_zip_contents(archive: &Path) -> Result<Vec<(String, bool)>> {
 }
 
 /// Adapted from inspect_tar_contents for 7z archives
-#[cfg(windows)]
 pub fn inspect_7z_contents(archive: &Path) -> Result<Vec<(String, bool)>> {
     let sevenz = sevenz_rust::SevenZReader::open(archive, sevenz_rust::Password::empty())?;
     let mut top_level_components = std::collections::HashMap::new();
@@ -1105,9 +1345,25 @@ pub fn inspect_7z_contents(archive: &Path) -> Result<Vec<(String, bool)>> {
     Ok(top_level_components.into_iter().collect())
 }
 
-#[cfg(not(windows))]
-pub fn inspect_7z_contents(_archive: &Path) -> Result<Vec<(String, bool)>> {
-    unimplemented!("7z format not supported on this platform")
+/// Inspects the top-level contents of a cabinet archive without extracting it
+pub fn inspect_cab_contents(archive: &Path) -> Result<Vec<(String, bool)>> {
+    let cabinet = cab::Cabinet::new(File::open(archive)?)
+        .wrap_err_with(|| format!("failed to open cab archive: {}", display_path(archive)))?;
+    let mut top_level_components = std::collections::HashMap::new();
+
+    for name in cab_file_names(&cabinet) {
+        let path = PathBuf::from(name.replace('\\', "/"));
+        if let Some(first_component) = path.components().next() {
+            let name = first_component.as_os_str().to_string_lossy().to_string();
+            // cab archives only contain files, so nesting is the only sign of a directory
+            let is_directory = path.components().count() > 1;
+
+            let existing = top_level_components.entry(name.clone()).or_insert(false);
+            *existing = *existing || is_directory;
+        }
+    }
+
+    Ok(top_level_components.into_iter().collect())
 }
 
 /// Determines if strip_components=1 should be applied based on archive structure
@@ -1115,6 +1371,7 @@ pub fn should_strip_components(archive: &Path, format: TarFormat) -> Result<bool
     let top_level_entries = match format {
         TarFormat::Zip => inspect_zip_contents(archive)?,
         TarFormat::SevenZip => inspect_7z_contents(archive)?,
+        TarFormat::Cab => inspect_cab_contents(archive)?,
         _ => inspect_tar_contents(archive, format)?,
     };
 
@@ -1208,6 +1465,37 @@ mod tests {
         // checks if the single entry is a directory before deciding to strip.
     }
 
+    #[test]
+    fn test_untar_reader() {
+        let mut tar = tar::Builder::new(flate2::write::GzEncoder::new(
+            vec![],
+            flate2::Compression::default(),
+        ));
+        let body = b"#!/bin/sh\necho hi\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(body.len() as u64);
+        header.set_mode(0o755);
+        tar.append_data(&mut header, "tool-1.0/bin/tool", &body[..])
+            .unwrap();
+        let archive = tar.into_inner().unwrap().finish().unwrap();
+
+        let dest = tempfile::tempdir().unwrap();
+        untar_reader(
+            std::io::Cursor::new(archive),
+            dest.path(),
+            &TarOptions {
+                format: TarFormat::TarGz,
+                strip_components: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            fs::read(dest.path().join("bin/tool")).unwrap(),
+            body.to_vec()
+        );
+    }
+
     #[test]
     fn test_inspect_tar_contents_logic() {
         // Test the logic of inspect_tar_contents with simulated data
@@ -0,0 +1,98 @@
+//! Tokens saved by `mise backends login` in `~/.config/mise/<forge>_tokens.toml`, keyed by hostname.
+//!
+//! These are used when no token is set in the environment.
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock as Lazy;
+
+use eyre::{Result, WrapErr};
+
+use crate::{dirs, file};
+
+static GITHUB_TOKENS: Lazy<BTreeMap<String, String>> = Lazy::new(|| load("github"));
+static GITLAB_TOKENS: Lazy<BTreeMap<String, String>> = Lazy::new(|| load("gitlab"));
+static CODEBERG_TOKENS: Lazy<BTreeMap<String, String>> = Lazy::new(|| load("codeberg"));
+
+pub fn path(forge: &str) -> PathBuf {
+    dirs::CONFIG.join(format!("{forge}_tokens.toml"))
+}
+
+/// Returns the saved token for `host`, e.g.: "github.com"
+pub fn get(forge: &str, host: &str) -> Option<&'static str> {
+    let tokens = match forge {
+        "github" => &*GITHUB_TOKENS,
+        "gitlab" => &*GITLAB_TOKENS,
+        "codeberg" => &*CODEBERG_TOKENS,
+        _ => return None,
+    };
+    tokens.get(host).map(|t| t.as_str())
+}
+
+pub fn save(forge: &str, host: &str, token: &str) -> Result<()> {
+    save_to(&path(forge), host, token)
+}
+
+fn load(forge: &str) -> BTreeMap<String, String> {
+    read(&path(forge)).unwrap_or_else(|err| {
+        warn!(
+            "failed to read {}: {err:#}",
+            file::display_path(path(forge))
+        );
+        Default::default()
+    })
+}
+
+fn read(path: &Path) -> Result<BTreeMap<String, String>> {
+    if !path.exists() {
+        return Ok(Default::default());
+    }
+    Ok(toml::from_str(&file::read_to_string(path)?)?)
+}
+
+fn save_to(path: &Path, host: &str, token: &str) -> Result<()> {
+    let mut tokens = read(path)?;
+    tokens.insert(host.to_string(), token.to_string());
+    file::create_dir_all(path.parent().unwrap())?;
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // only readable by the user from the moment it is created
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut f = options
+        .open(path)
+        .wrap_err_with(|| format!("failed to write {}", file::display_path(path)))?;
+    // an existing file keeps its permissions
+    file::chmod(path, 0o600)?;
+    f.write_all(toml::to_string(&tokens)?.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_to() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("github_tokens.toml");
+        save_to(&path, "github.com", "gho_abc").unwrap();
+        save_to(&path, "ghe.example.com", "gho_def").unwrap();
+        save_to(&path, "github.com", "gho_ghi").unwrap();
+        let tokens = read(&path).unwrap();
+        assert_eq!(tokens.get("github.com").unwrap(), "gho_ghi");
+        assert_eq!(tokens.get("ghe.example.com").unwrap(), "gho_def");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_save_to_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("github_tokens.toml");
+        save_to(&path, "github.com", "gho_abc").unwrap();
+        let mode = path.metadata().unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,88 @@
+//! Differences between the versions of the Forgejo API, e.g.: codeberg.org, see `api_version`.
+//!
+//! Releases are fetched with the github client as the Forgejo API is compatible with it, this only
+//! covers how the endpoint urls are built for each API version.
+
+/// The API versions that can be set with `api_version`
+pub const API_VERSIONS: &[&str] = &["v1", "v2"];
+
+pub trait ForgejoApiClient: Send + Sync {
+    /// e.g.: "v1"
+    fn version(&self) -> &'static str;
+
+    /// `api_url` with its `/api/<version>` segment set to this version, e.g.:
+    /// `https://codeberg.org/api/v1` -> `https://codeberg.org/api/v2`
+    fn api_url(&self, api_url: &str) -> String {
+        let api_url = api_url.trim_end_matches('/');
+        match api_url.rsplit_once("/api/") {
+            Some((base, _)) => format!("{base}/api/{}", self.version()),
+            None => format!("{api_url}/api/{}", self.version()),
+        }
+    }
+
+    /// Cheap endpoint used to check that the API is reachable
+    fn version_url(&self, api_url: &str) -> String;
+}
+
+pub struct ForgejoApiV1;
+
+impl ForgejoApiClient for ForgejoApiV1 {
+    fn version(&self) -> &'static str {
+        "v1"
+    }
+
+    fn version_url(&self, api_url: &str) -> String {
+        format!("{api_url}/version")
+    }
+}
+
+/// The next version of the API, which is still being designed and keeps the v1 endpoints so far
+pub struct ForgejoApiV2;
+
+impl ForgejoApiClient for ForgejoApiV2 {
+    fn version(&self) -> &'static str {
+        "v2"
+    }
+
+    fn version_url(&self, api_url: &str) -> String {
+        format!("{api_url}/version")
+    }
+}
+
+/// The client for an `api_version`, None if it is not one of `API_VERSIONS`
+pub fn api_client(version: &str) -> Option<&'static dyn ForgejoApiClient> {
+    match version {
+        "v1" => Some(&ForgejoApiV1),
+        "v2" => Some(&ForgejoApiV2),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_url() {
+        assert_eq!(
+            ForgejoApiV2.api_url("https://codeberg.org/api/v1"),
+            "https://codeberg.org/api/v2"
+        );
+        assert_eq!(
+            ForgejoApiV2.api_url("https://git.example.com/forgejo/api/v1/"),
+            "https://git.example.com/forgejo/api/v2"
+        );
+        assert_eq!(
+            ForgejoApiV1.api_url("https://git.example.com"),
+            "https://git.example.com/api/v1"
+        );
+    }
+
+    #[test]
+    fn test_api_client() {
+        for version in API_VERSIONS {
+            assert_eq!(api_client(version).unwrap().version(), *version);
+        }
+        assert!(api_client("v3").is_none());
+    }
+}
@@ -227,6 +227,12 @@ impl Git {
         Ok(aref)
     }
 
+    /// Contents of `path`, relative to `dir`, at the given revision
+    pub fn show_file(&self, rev: &str, path: &str) -> Result<String> {
+        let spec = format!("{rev}:./{path}");
+        git_cmd_read!(&self.dir, "show", spec.as_str())
+    }
+
     pub fn get_remote_url(&self) -> Option<String> {
         let dir = &self.dir;
         if !self.exists() {
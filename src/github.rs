@@ -1,5 +1,8 @@
+use crate::backend::backend_type::BackendType;
 use crate::cache::{CacheManager, CacheManagerBuilder};
-use crate::{dirs, duration, env};
+use crate::hash::hash_to_str;
+use crate::{dirs, duration, env, forge_tokens, netrc};
+use base64::Engine;
 use eyre::Result;
 use heck::ToKebabCase;
 use reqwest::IntoUrl;
@@ -16,11 +19,13 @@ use xx::regex;
 pub struct GithubRelease {
     pub tag_name: String,
     // pub name: Option<String>,
-    // pub body: Option<String>,
+    pub body: Option<String>,
     pub draft: bool,
     pub prerelease: bool,
-    // pub created_at: String,
-    // pub published_at: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub published_at: Option<String>,
     pub assets: Vec<GithubAsset>,
 }
 
@@ -29,16 +34,44 @@ pub struct GithubTag {
     pub name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubRepo {
+    pub full_name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRepoSearch {
+    items: Vec<GithubRepo>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GithubAsset {
     pub name: String,
     // pub size: u64,
     pub browser_download_url: String,
+    /// not returned by forgejo, see `api_url`
+    #[serde(default)]
     pub url: String,
     /// SHA256 digest provided by GitHub API (format: "sha256:hash")
     /// Will be null for releases created before this feature was added
     #[serde(default)]
     pub digest: Option<String>,
+    /// MIME type, e.g.: "application/gzip", not returned by forgejo
+    #[serde(default)]
+    pub content_type: Option<String>,
+}
+
+impl GithubAsset {
+    /// The API url of the asset, forgejo only has the browser url
+    pub fn api_url(&self) -> &str {
+        match self.url.is_empty() {
+            true => &self.browser_download_url,
+            false => &self.url,
+        }
+    }
 }
 
 type CacheGroup<T> = HashMap<String, CacheManager<T>>;
@@ -97,11 +130,24 @@ pub async fn list_releases(repo: &str) -> Result<Vec<GithubRelease>> {
     Ok(cache
         .get_or_try_init_async(async || list_releases_(API_URL, repo).await)
         .await?
-        .to_vec())
+        .iter()
+        .filter(|r| !r.prerelease)
+        .cloned()
+        .collect())
 }
 
 pub async fn list_releases_from_url(api_url: &str, repo: &str) -> Result<Vec<GithubRelease>> {
-    let key = format!("{api_url}-{repo}").to_kebab_case();
+    let mut releases = list_releases_with_prereleases_from_url(api_url, repo).await?;
+    releases.retain(|r| !r.prerelease);
+    Ok(releases)
+}
+
+/// Like `list_releases_from_url` but keeps releases marked as prereleases
+pub async fn list_releases_with_prereleases_from_url(
+    api_url: &str,
+    repo: &str,
+) -> Result<Vec<GithubRelease>> {
+    let key = repo_key(api_url, repo);
     let cache = get_releases_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -113,21 +159,29 @@ pub async fn list_releases_from_url(api_url: &str, repo: &str) -> Result<Vec<Git
 async fn list_releases_(api_url: &str, repo: &str) -> Result<Vec<GithubRelease>> {
     let url = format!("{api_url}/repos/{repo}/releases");
     let headers = get_headers(&url);
+    let cache_file = response_cache_file(api_url, repo, &url);
     let (mut releases, mut headers) = crate::http::HTTP_FETCH
-        .json_headers_with_headers::<Vec<GithubRelease>, _>(url, &headers)
+        .json_headers_conditional::<Vec<GithubRelease>, _>(url, &headers, &cache_file)
         .await?;
 
     if *env::MISE_LIST_ALL_VERSIONS {
+        let mut pages = 1;
         while let Some(next) = next_page(&headers) {
+            if !env::more_pages_allowed(pages, repo) {
+                break;
+            }
+            pages += 1;
             headers = get_headers(&next);
+            let cache_file = response_cache_file(api_url, repo, &next);
             let (more, h) = crate::http::HTTP_FETCH
-                .json_headers_with_headers::<Vec<GithubRelease>, _>(next, &headers)
+                .json_headers_conditional::<Vec<GithubRelease>, _>(next, &headers, &cache_file)
                 .await?;
             releases.extend(more);
             headers = h;
         }
     }
-    releases.retain(|r| !r.draft && !r.prerelease);
+    // prereleases are cached too, see `list_releases_with_prereleases_from_url`
+    releases.retain(|r| !r.draft);
 
     Ok(releases)
 }
@@ -143,7 +197,7 @@ pub async fn list_tags(repo: &str) -> Result<Vec<String>> {
 }
 
 pub async fn list_tags_from_url(api_url: &str, repo: &str) -> Result<Vec<String>> {
-    let key = format!("{api_url}-{repo}").to_kebab_case();
+    let key = repo_key(api_url, repo);
     let cache = get_tags_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -155,15 +209,22 @@ pub async fn list_tags_from_url(api_url: &str, repo: &str) -> Result<Vec<String>
 async fn list_tags_(api_url: &str, repo: &str) -> Result<Vec<String>> {
     let url = format!("{api_url}/repos/{repo}/tags");
     let headers = get_headers(&url);
+    let cache_file = response_cache_file(api_url, repo, &url);
     let (mut tags, mut headers) = crate::http::HTTP_FETCH
-        .json_headers_with_headers::<Vec<GithubTag>, _>(url, &headers)
+        .json_headers_conditional::<Vec<GithubTag>, _>(url, &headers, &cache_file)
         .await?;
 
     if *env::MISE_LIST_ALL_VERSIONS {
+        let mut pages = 1;
         while let Some(next) = next_page(&headers) {
+            if !env::more_pages_allowed(pages, repo) {
+                break;
+            }
+            pages += 1;
             headers = get_headers(&next);
+            let cache_file = response_cache_file(api_url, repo, &next);
             let (more, h) = crate::http::HTTP_FETCH
-                .json_headers_with_headers::<Vec<GithubTag>, _>(next, &headers)
+                .json_headers_conditional::<Vec<GithubTag>, _>(next, &headers, &cache_file)
                 .await?;
             tags.extend(more);
             headers = h;
@@ -174,7 +235,7 @@ async fn list_tags_(api_url: &str, repo: &str) -> Result<Vec<String>> {
 }
 
 pub async fn get_release(repo: &str, tag: &str) -> Result<GithubRelease> {
-    let key = format!("{repo}-{tag}").to_kebab_case();
+    let key = format!("{}@{}", repo.to_kebab_case(), tag.to_kebab_case());
     let cache = get_release_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -184,7 +245,7 @@ pub async fn get_release(repo: &str, tag: &str) -> Result<GithubRelease> {
 }
 
 pub async fn get_release_for_url(api_url: &str, repo: &str, tag: &str) -> Result<GithubRelease> {
-    let key = format!("{api_url}-{repo}-{tag}").to_kebab_case();
+    let key = format!("{}@{}", repo_key(api_url, repo), tag.to_kebab_case());
     let cache = get_release_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -196,11 +257,65 @@ pub async fn get_release_for_url(api_url: &str, repo: &str, tag: &str) -> Result
 async fn get_release_(api_url: &str, repo: &str, tag: &str) -> Result<GithubRelease> {
     let url = format!("{api_url}/repos/{repo}/releases/tags/{tag}");
     let headers = get_headers(&url);
+    let cache_file = response_cache_file(api_url, repo, &url);
+    crate::http::HTTP_FETCH
+        .json_headers_conditional(url, &headers, &cache_file)
+        .await
+        .map(|(release, _)| release)
+}
+
+/// Fetches only the latest (non-draft, non-prerelease) release, bypassing the release caches.
+/// The request is still conditional, so an unchanged release doesn't count against the rate limit.
+pub async fn get_latest_release_for_url(api_url: &str, repo: &str) -> Result<GithubRelease> {
+    let url = format!("{api_url}/repos/{repo}/releases/latest");
+    let headers = get_headers(&url);
+    let cache_file = response_cache_file(api_url, repo, &url);
+    crate::http::HTTP_FETCH
+        .json_headers_conditional(url, &headers, &cache_file)
+        .await
+        .map(|(release, _)| release)
+}
+
+pub async fn get_repo_for_url(api_url: &str, repo: &str) -> Result<GithubRepo> {
+    let url = format!("{api_url}/repos/{repo}");
+    let headers = get_headers(&url);
     crate::http::HTTP_FETCH
         .json_with_headers(url, &headers)
         .await
 }
 
+/// Lists the most starred repositories with the given topic
+pub async fn search_repos_by_topic(api_url: &str, topic: &str) -> Result<Vec<GithubRepo>> {
+    let url = format!(
+        "{api_url}/search/repositories?q=topic:{}&sort=stars&per_page=30",
+        urlencoding::encode(topic)
+    );
+    let headers = get_headers(&url);
+    let search: GithubRepoSearch = crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await?;
+    Ok(search.items)
+}
+
+#[derive(Debug, Deserialize)]
+struct ForgejoRepoSearch {
+    data: Vec<GithubRepo>,
+}
+
+/// Same as `search_repos_by_topic` for forgejo hosts, e.g.: codeberg.org, which have their own
+/// search endpoint
+pub async fn search_forgejo_repos_by_topic(api_url: &str, topic: &str) -> Result<Vec<GithubRepo>> {
+    let url = format!(
+        "{api_url}/repos/search?q={}&topic=true&sort=stars&order=desc&limit=30",
+        urlencoding::encode(topic)
+    );
+    let headers = get_headers(&url);
+    let search: ForgejoRepoSearch = crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await?;
+    Ok(search.data)
+}
+
 fn next_page(headers: &HeaderMap) -> Option<String> {
     let link = headers
         .get("link")
@@ -211,17 +326,53 @@ fn next_page(headers: &HeaderMap) -> Option<String> {
         .map(|c| c.get(1).unwrap().as_str().to_string())
 }
 
+/// Removes cached release/tag data for repos which haven't been queried in a while
+pub fn evict_stale_cache() -> Result<()> {
+    crate::cache::evict_stale_msgpack(&cache_dir())
+}
+
 fn cache_dir() -> PathBuf {
     dirs::CACHE.join("github")
 }
 
+/// The cache key of `repo` on the API at `api_url`. Cache files of a single release or response
+/// are named `{key}@...` so `mise backends prune` can tell which repo they belong to.
+pub fn repo_key(api_url: &str, repo: &str) -> String {
+    format!("{api_url}-{repo}").to_kebab_case()
+}
+
+/// Where the last response of `url` is stored for revalidation, see `json_headers_conditional`
+fn response_cache_file(api_url: &str, repo: &str, url: &str) -> PathBuf {
+    cache_dir().join(format!(
+        "{}@{}-response.msgpack.z",
+        repo_key(api_url, repo),
+        hash_to_str(&url)
+    ))
+}
+
+tokio::task_local! {
+    /// the forge of the backend making the requests, see `with_forge`
+    static FORGE: BackendType;
+}
+
+/// Runs `f` with its requests authenticated with the tokens of `forge`, e.g.: so a Forgejo
+/// instance with a custom `api_url` gets MISE_CODEBERG_TOKEN and not the GitHub Enterprise token
+pub async fn with_forge<F: Future>(forge: BackendType, f: F) -> F::Output {
+    FORGE.scope(forge, f).await
+}
+
+/// The headers for a request to `url` made by a backend of `forge`, see `with_forge`
+pub fn get_headers_for_forge<U: IntoUrl>(forge: BackendType, url: U) -> HeaderMap {
+    FORGE.sync_scope(forge, || get_headers(url))
+}
+
 pub fn get_headers<U: IntoUrl>(url: U) -> HeaderMap {
     let mut headers = HeaderMap::new();
     let url = url.into_url().unwrap();
-    let mut set_headers = |token: &str| {
+    let mut set_headers = |authorization: String| {
         headers.insert(
             "authorization",
-            HeaderValue::from_str(format!("token {token}").as_str()).unwrap(),
+            HeaderValue::from_str(&authorization).unwrap(),
         );
         headers.insert(
             "x-github-api-version",
@@ -229,12 +380,32 @@ pub fn get_headers<U: IntoUrl>(url: U) -> HeaderMap {
         );
     };
 
-    if url.host_str() == Some("api.github.com") {
-        if let Some(token) = env::GITHUB_TOKEN.as_ref() {
-            set_headers(token);
-        }
-    } else if let Some(token) = env::MISE_GITHUB_ENTERPRISE_TOKEN.as_ref() {
-        set_headers(token);
+    let host = url.host_str().unwrap_or_default();
+    // requests outside of a backend, e.g.: from aqua or ubi, are made to github
+    let forge = FORGE.try_with(|forge| forge.clone()).ok();
+    let token = match (forge, host) {
+        // MISE_CODEBERG_TOKEN falls back to MISE_FORGEJO_TOKEN, other forgejo instances are
+        // logged in to as codeberg
+        (Some(BackendType::Codeberg), _) | (None, "codeberg.org") => env::MISE_CODEBERG_TOKEN
+            .as_deref()
+            .or_else(|| forge_tokens::get("codeberg", host)),
+        (_, "api.github.com") => env::GITHUB_TOKEN
+            .as_deref()
+            .or_else(|| forge_tokens::get("github", "github.com")),
+        _ => env::MISE_GITHUB_ENTERPRISE_TOKEN
+            .as_deref()
+            .or_else(|| forge_tokens::get("github", host)),
+    };
+    if let Some(token) = token {
+        set_headers(format!("token {token}"));
+    } else if let Some(creds) = netrc::get(host).or_else(|| match host {
+        // git credentials for github are usually stored under github.com
+        "api.github.com" => netrc::get("github.com"),
+        _ => None,
+    }) {
+        let auth = base64::engine::general_purpose::STANDARD
+            .encode(format!("{}:{}", creds.login, creds.password));
+        set_headers(format!("Basic {auth}"));
     }
 
     if url.path().contains("/releases/assets/") {
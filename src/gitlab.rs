@@ -10,12 +10,16 @@ use tokio::sync::{RwLock, RwLockReadGuard};
 use xx::regex;
 
 use crate::cache::{CacheManager, CacheManagerBuilder};
-use crate::{dirs, duration, env};
+use crate::{dirs, duration, env, forge_tokens};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitlabRelease {
     pub tag_name: String,
     pub description: Option<String>,
+    #[serde(default)]
+    pub created_at: String,
+    #[serde(default)]
+    pub released_at: Option<String>,
     pub assets: GitlabAssets,
 }
 
@@ -46,6 +50,27 @@ pub struct GitlabAssetLink {
     pub link_type: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabPackage {
+    pub id: i64,
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabProject {
+    pub path_with_namespace: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabPackageFile {
+    pub file_name: String,
+    pub file_sha256: Option<String>,
+}
+
 type CacheGroup<T> = HashMap<String, CacheManager<T>>;
 
 static RELEASES_CACHE: Lazy<RwLock<CacheGroup<Vec<GitlabRelease>>>> = Lazy::new(Default::default);
@@ -107,7 +132,7 @@ pub async fn list_releases(repo: &str) -> Result<Vec<GitlabRelease>> {
 }
 
 pub async fn list_releases_from_url(api_url: &str, repo: &str) -> Result<Vec<GitlabRelease>> {
-    let key = format!("{api_url}-{repo}").to_kebab_case();
+    let key = crate::github::repo_key(api_url, repo);
     let cache = get_releases_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -129,7 +154,12 @@ async fn list_releases_(api_url: &str, repo: &str) -> Result<Vec<GitlabRelease>>
         .await?;
 
     if *env::MISE_LIST_ALL_VERSIONS {
+        let mut pages = 1;
         while let Some(next) = next_page(&headers) {
+            if !env::more_pages_allowed(pages, repo) {
+                break;
+            }
+            pages += 1;
             let (more, h) = crate::http::HTTP_FETCH
                 .json_headers_with_headers::<Vec<GitlabRelease>, _>(next, &headers)
                 .await?;
@@ -141,6 +171,75 @@ async fn list_releases_(api_url: &str, repo: &str) -> Result<Vec<GitlabRelease>>
     Ok(releases)
 }
 
+/// Lists the versions of a package in the project's generic package registry
+pub async fn list_packages_from_url(
+    api_url: &str,
+    repo: &str,
+    package_name: &str,
+) -> Result<Vec<GitlabPackage>> {
+    let url = format!(
+        "{}/projects/{}/packages?package_type=generic&package_name={}&order_by=version&sort=asc&per_page=100",
+        api_url,
+        urlencoding::encode(repo),
+        urlencoding::encode(package_name)
+    );
+    let headers = get_headers(&url);
+    let (mut packages, mut next) = crate::http::HTTP_FETCH
+        .json_headers_with_headers::<Vec<GitlabPackage>, _>(url, &headers)
+        .await
+        .map(|(p, h)| (p, next_page(&h)))?;
+    let mut pages = 1;
+    while let Some(url) = next {
+        if !env::more_pages_allowed(pages, repo) {
+            break;
+        }
+        pages += 1;
+        let (more, h) = crate::http::HTTP_FETCH
+            .json_headers_with_headers::<Vec<GitlabPackage>, _>(url, &headers)
+            .await?;
+        packages.extend(more);
+        next = next_page(&h);
+    }
+    // the package_name filter is a fuzzy match
+    packages.retain(|p| p.name == package_name);
+    Ok(packages)
+}
+
+pub async fn list_package_files(
+    api_url: &str,
+    repo: &str,
+    package_id: i64,
+) -> Result<Vec<GitlabPackageFile>> {
+    let url = format!(
+        "{}/projects/{}/packages/{}/package_files?per_page=100",
+        api_url,
+        urlencoding::encode(repo),
+        package_id
+    );
+    let headers = get_headers(&url);
+    crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await
+}
+
+/// Download URL for a file in the project's generic package registry
+pub fn package_file_url(
+    api_url: &str,
+    repo: &str,
+    package_name: &str,
+    version: &str,
+    file_name: &str,
+) -> String {
+    format!(
+        "{}/projects/{}/packages/generic/{}/{}/{}",
+        api_url,
+        urlencoding::encode(repo),
+        urlencoding::encode(package_name),
+        urlencoding::encode(version),
+        urlencoding::encode(file_name)
+    )
+}
+
 #[allow(dead_code)]
 pub async fn list_tags(repo: &str) -> Result<Vec<String>> {
     let key = repo.to_kebab_case();
@@ -153,7 +252,7 @@ pub async fn list_tags(repo: &str) -> Result<Vec<String>> {
 }
 
 pub async fn list_tags_from_url(api_url: &str, repo: &str) -> Result<Vec<String>> {
-    let key = format!("{api_url}-{repo}").to_kebab_case();
+    let key = crate::github::repo_key(api_url, repo);
     let cache = get_tags_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -174,7 +273,12 @@ async fn list_tags_(api_url: &str, repo: &str) -> Result<Vec<String>> {
         .await?;
 
     if *env::MISE_LIST_ALL_VERSIONS {
+        let mut pages = 1;
         while let Some(next) = next_page(&headers) {
+            if !env::more_pages_allowed(pages, repo) {
+                break;
+            }
+            pages += 1;
             let (more, h) = crate::http::HTTP_FETCH
                 .json_headers_with_headers::<Vec<GitlabTag>, _>(next, &headers)
                 .await?;
@@ -188,7 +292,7 @@ async fn list_tags_(api_url: &str, repo: &str) -> Result<Vec<String>> {
 
 #[allow(dead_code)]
 pub async fn get_release(repo: &str, tag: &str) -> Result<GitlabRelease> {
-    let key = format!("{repo}-{tag}").to_kebab_case();
+    let key = format!("{}@{}", repo.to_kebab_case(), tag.to_kebab_case());
     let cache = get_release_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -198,7 +302,11 @@ pub async fn get_release(repo: &str, tag: &str) -> Result<GitlabRelease> {
 }
 
 pub async fn get_release_for_url(api_url: &str, repo: &str, tag: &str) -> Result<GitlabRelease> {
-    let key = format!("{api_url}-{repo}-{tag}").to_kebab_case();
+    let key = format!(
+        "{}@{}",
+        crate::github::repo_key(api_url, repo),
+        tag.to_kebab_case()
+    );
     let cache = get_release_cache(&key).await;
     let cache = cache.get(&key).unwrap();
     Ok(cache
@@ -220,6 +328,39 @@ async fn get_release_(api_url: &str, repo: &str, tag: &str) -> Result<GitlabRele
         .await
 }
 
+/// Fetches only the latest release, bypassing the release caches
+pub async fn get_latest_release_for_url(api_url: &str, repo: &str) -> Result<GitlabRelease> {
+    let url = format!(
+        "{}/projects/{}/releases/permalink/latest",
+        api_url,
+        urlencoding::encode(repo)
+    );
+    let headers = get_headers(&url);
+    crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await
+}
+
+pub async fn get_project_for_url(api_url: &str, repo: &str) -> Result<GitlabProject> {
+    let url = format!("{}/projects/{}", api_url, urlencoding::encode(repo));
+    let headers = get_headers(&url);
+    crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await
+}
+
+/// Lists the most starred projects with the given topic
+pub async fn search_projects_by_topic(api_url: &str, topic: &str) -> Result<Vec<GitlabProject>> {
+    let url = format!(
+        "{api_url}/projects?topic={}&order_by=star_count&per_page=30",
+        urlencoding::encode(topic)
+    );
+    let headers = get_headers(&url);
+    crate::http::HTTP_FETCH
+        .json_with_headers(url, &headers)
+        .await
+}
+
 fn next_page(headers: &HeaderMap) -> Option<String> {
     let link = headers
         .get("link")
@@ -230,6 +371,11 @@ fn next_page(headers: &HeaderMap) -> Option<String> {
         .map(|c| c.get(1).unwrap().as_str().to_string())
 }
 
+/// Removes cached release/tag data for repos which haven't been queried in a while
+pub fn evict_stale_cache() -> Result<()> {
+    crate::cache::evict_stale_msgpack(&cache_dir())
+}
+
 fn cache_dir() -> PathBuf {
     dirs::CACHE.join("gitlab")
 }
@@ -243,11 +389,18 @@ pub fn get_headers<U: IntoUrl>(url: U) -> HeaderMap {
             HeaderValue::from_str(format!("Bearer {token}").as_str()).unwrap(),
         );
     };
-    if url.host_str() == Some("gitlab.com") {
-        if let Some(token) = env::GITLAB_TOKEN.as_ref() {
+    let host = url.host_str().unwrap_or_default();
+    if host == "gitlab.com" {
+        if let Some(token) = env::GITLAB_TOKEN
+            .as_deref()
+            .or_else(|| forge_tokens::get("gitlab", host))
+        {
             set_headers(token);
         }
-    } else if let Some(token) = env::MISE_GITLAB_ENTERPRISE_TOKEN.as_ref() {
+    } else if let Some(token) = env::MISE_GITLAB_ENTERPRISE_TOKEN
+        .as_deref()
+        .or_else(|| forge_tokens::get("gitlab", host))
+    {
         set_headers(token);
     }
     headers
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::Result;
 use crate::cmd::CmdLineRunner;
 use crate::install_context::InstallContext;
@@ -10,6 +12,36 @@ pub fn add_keys_swift(ctx: &InstallContext) -> Result<()> {
     add_keys(ctx, include_str!("assets/gpg/swift.asc"))
 }
 
+/// Verifies a detached signature of `file` with `keys` only, using a temporary keyring
+/// so the keys aren't added to the user's keyring
+pub fn verify_detached(
+    ctx: &InstallContext,
+    keys: &str,
+    signature: &Path,
+    file: &Path,
+) -> Result<()> {
+    let home = tempfile::tempdir()?;
+    CmdLineRunner::new("gpg")
+        .arg("--homedir")
+        .arg(home.path())
+        .arg("--quiet")
+        .arg("--import")
+        .stdin_string(keys)
+        .with_pr(ctx.pr.as_ref())
+        .execute()?;
+    CmdLineRunner::new("gpg")
+        .arg("--homedir")
+        .arg(home.path())
+        .arg("--quiet")
+        .arg("--trust-model")
+        .arg("always")
+        .arg("--verify")
+        .arg(signature)
+        .arg(file)
+        .with_pr(ctx.pr.as_ref())
+        .execute()
+}
+
 fn add_keys(ctx: &InstallContext, keys: &str) -> Result<()> {
     CmdLineRunner::new("gpg")
         .arg("--quiet")
@@ -20,7 +20,7 @@ pub fn hash_to_str<T: Hash>(t: &T) -> String {
     format!("{:x}", s.finish())
 }
 
-pub fn hash_sha256_to_str(s: &str) -> String {
+pub fn hash_sha256_to_str(s: impl AsRef<[u8]>) -> String {
     let mut hasher = Sha256::new();
     hasher.update(s);
     format!("{:x}", hasher.finalize())
@@ -145,6 +145,40 @@ pub fn parse_shasums(text: &str) -> HashMap<String, String> {
         .collect()
 }
 
+/// Computes the sha256 and size of what is read through it, e.g.: of an asset that is extracted
+/// while it downloads
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+    size: u64,
+}
+
+impl<R: Read> HashingReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+            size: 0,
+        }
+    }
+
+    /// Reads the rest of the input, e.g.: the padding after the end of a tarball, and returns
+    /// its sha256 and size
+    pub fn finish(mut self) -> Result<(String, u64)> {
+        std::io::copy(&mut self, &mut std::io::sink())?;
+        Ok((format!("{:x}", self.hasher.finalize()), self.size))
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.size += n as u64;
+        Ok(n)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
@@ -167,4 +201,14 @@ mod tests {
         let hash = file_hash_prog::<Sha256>(path, None).unwrap();
         assert_snapshot!(hash);
     }
+
+    #[test]
+    fn test_hashing_reader() {
+        let mut reader = HashingReader::new(&b"hello world"[..]);
+        let mut buf = [0; 5];
+        reader.read_exact(&mut buf).unwrap();
+        let (hash, size) = reader.finish().unwrap();
+        assert_eq!(hash, hash_sha256_to_str("hello world"));
+        assert_eq!(size, 11);
+    }
 }
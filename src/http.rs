@@ -1,20 +1,37 @@
-use std::io::Write;
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
 use std::path::Path;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use eyre::{Report, Result, bail, ensure};
 use regex::Regex;
-use reqwest::header::{HeaderMap, HeaderValue};
-use reqwest::{ClientBuilder, IntoUrl, Method, Response};
+use reqwest::header::{
+    AUTHORIZATION, COOKIE, ETAG, HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE,
+    IF_NONE_MATCH, LAST_MODIFIED, LINK, LOCATION, PROXY_AUTHORIZATION, WWW_AUTHENTICATE,
+};
+use reqwest::{ClientBuilder, IntoUrl, Method, Response, StatusCode};
+#[cfg(tls_pinning)]
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+#[cfg(tls_pinning)]
+use rustls::crypto::CryptoProvider;
+#[cfg(tls_pinning)]
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+#[cfg(tls_pinning)]
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use serde_derive::{Deserialize, Serialize};
 use std::sync::LazyLock as Lazy;
 use tokio_retry::Retry;
 use tokio_retry::strategy::{ExponentialBackoff, jitter};
 use url::Url;
 
+use crate::cache::{CacheManager, CacheManagerBuilder};
 use crate::cli::version;
 use crate::config::Settings;
 use crate::file::display_path;
-use crate::ui::progress_report::SingleReport;
+#[cfg(tls_pinning)]
+use crate::hash;
+use crate::ui::progress_report::{DownloadStats, SingleReport};
 use crate::ui::time::format_duration;
 use crate::{env, file};
 
@@ -33,9 +50,38 @@ pub static HTTP_FETCH: Lazy<Client> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Blocking reader over the chunks of a download from `Client::stream_with_headers`
+pub struct ChunkReader {
+    rx: tokio::sync::mpsc::Receiver<std::io::Result<Vec<u8>>>,
+    chunk: Cursor<Vec<u8>>,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let n = self.chunk.read(buf)?;
+            if n > 0 || buf.is_empty() {
+                return Ok(n);
+            }
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = Cursor::new(chunk?),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+tokio::task_local! {
+    /// options for the requests to one host, see `with_host_options`
+    static HOST_OPTIONS: HostOptions;
+}
+
 #[derive(Debug)]
 pub struct Client {
     reqwest: reqwest::Client,
+    /// clients that only accept the pinned certificate of a host, keyed by (host, fingerprint)
+    #[cfg(tls_pinning)]
+    pinned: Mutex<HashMap<(String, String), reqwest::Client>>,
     timeout: Duration,
     kind: ClientKind,
 }
@@ -55,16 +101,73 @@ impl Client {
                 .read_timeout(timeout)
                 .connect_timeout(timeout)
                 .build()?,
+            #[cfg(tls_pinning)]
+            pinned: Default::default(),
             timeout,
             kind,
         })
     }
 
+    /// The client to send requests to `url` with, which only accepts the certificate pinned
+    /// with `tls_fingerprint` if one is set for its host
+    fn client_for(&self, url: &Url) -> Result<reqwest::Client> {
+        let Some(options) = host_options(url) else {
+            return Ok(self.reqwest.clone());
+        };
+        let Some(fingerprint) = options.tls_fingerprint else {
+            return Ok(self.reqwest.clone());
+        };
+        if url.scheme() != "https" {
+            bail!(
+                "tls_fingerprint is set for {} but {url} is not fetched over TLS",
+                options.host
+            );
+        }
+        self.pinned_client(options.host, fingerprint.replace(':', "").to_lowercase())
+    }
+
+    /// A client that only connects to `host` if it presents the certificate with the sha256
+    /// `fingerprint`. It doesn't follow redirects so the hosts they lead to are verified by the
+    /// regular client, see `pinned_redirect`.
+    #[cfg(tls_pinning)]
+    fn pinned_client(&self, host: String, fingerprint: String) -> Result<reqwest::Client> {
+        let key = (host, fingerprint);
+        let mut pinned = self.pinned.lock().unwrap();
+        if let Some(client) = pinned.get(&key) {
+            return Ok(client.clone());
+        }
+        let verifier = PinnedCertVerifier::new(key.0.clone(), key.1.clone());
+        let mut tls = rustls::ClientConfig::builder_with_provider(verifier.provider.clone())
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(verifier))
+            .with_no_client_auth();
+        tls.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        let client = Self::_new()
+            .use_preconfigured_tls(tls)
+            .redirect(reqwest::redirect::Policy::none())
+            .read_timeout(self.timeout)
+            .connect_timeout(self.timeout)
+            .build()?;
+        pinned.insert(key, client.clone());
+        Ok(client)
+    }
+
+    #[cfg(not(tls_pinning))]
+    fn pinned_client(&self, host: String, _fingerprint: String) -> Result<reqwest::Client> {
+        bail!(
+            "tls_fingerprint is set for {host} but mise was built without rustls, which certificate pinning requires"
+        )
+    }
+
     fn _new() -> ClientBuilder {
-        let v = &*version::VERSION;
-        let shell = env::MISE_SHELL.map(|s| s.to_string()).unwrap_or_default();
+        let user_agent = env::MISE_USER_AGENT.clone().unwrap_or_else(|| {
+            let v = &*version::VERSION;
+            let shell = env::MISE_SHELL.map(|s| s.to_string()).unwrap_or_default();
+            format!("mise/{v} {shell}").trim().to_string()
+        });
         ClientBuilder::new()
-            .user_agent(format!("mise/{v} {shell}").trim())
+            .user_agent(user_agent)
             .gzip(true)
             .zstd(true)
     }
@@ -81,7 +184,7 @@ impl Client {
         self.get_async_with_headers(url, &headers).await
     }
 
-    async fn get_async_with_headers<U: IntoUrl>(
+    pub async fn get_async_with_headers<U: IntoUrl>(
         &self,
         url: U,
         headers: &HeaderMap,
@@ -166,6 +269,70 @@ impl Client {
         Ok((json, headers))
     }
 
+    /// Like `json_headers_with_headers` but revalidates the last response for `url` stored in
+    /// `cache_file` with If-None-Match/If-Modified-Since, reusing its body if the server answers
+    /// 304 Not Modified. Conditional requests do not count against GitHub's rate limit.
+    pub async fn json_headers_conditional<T, U: IntoUrl>(
+        &self,
+        url: U,
+        headers: &HeaderMap,
+        cache_file: &Path,
+    ) -> Result<(T, HeaderMap)>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let url = url.into_url().unwrap();
+        let cache: CacheManager<CachedResponse> = CacheManagerBuilder::new(cache_file).build();
+        let cached = cache.get_cached();
+        let mut headers = headers.clone();
+        if let Some(cached) = &cached {
+            if let Some(etag) = cached
+                .etag
+                .as_deref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                headers.insert(IF_NONE_MATCH, etag);
+            } else if let Some(last_modified) = cached
+                .last_modified
+                .as_deref()
+                .and_then(|v| HeaderValue::from_str(v).ok())
+            {
+                headers.insert(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let resp = self.get_async_with_headers(url.clone(), &headers).await?;
+        let mut headers = resp.headers().clone();
+        if resp.status() == StatusCode::NOT_MODIFIED
+            && let Some(cached) = cached
+        {
+            debug!("{url} not modified, using cached response");
+            if let Some(link) = cached.link.and_then(|v| HeaderValue::from_str(&v).ok()) {
+                headers.entry(LINK).or_insert(link);
+            }
+            return Ok((serde_json::from_str(&cached.body)?, headers));
+        }
+        let body = resp.text().await?;
+        let json = serde_json::from_str(&body)?;
+        let header = |name: HeaderName| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let response = CachedResponse {
+            etag: header(ETAG),
+            last_modified: header(LAST_MODIFIED),
+            link: header(LINK),
+            body,
+        };
+        if (response.etag.is_some() || response.last_modified.is_some())
+            && let Err(err) = cache.write(&response)
+        {
+            warn!("failed to cache response for {url}: {err:#}");
+        }
+        Ok((json, headers))
+    }
+
     pub async fn json<T, U: IntoUrl>(&self, url: U) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
@@ -182,6 +349,25 @@ impl Client {
             .map(|(json, _)| json)
     }
 
+    /// POSTs a form and parses the JSON response. Error statuses are not treated as errors
+    /// since OAuth endpoints describe them in the body.
+    pub async fn post_form_json<T, U: IntoUrl>(&self, url: U, form: &[(&str, &str)]) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        ensure!(!*env::OFFLINE, "offline mode is enabled");
+        let url = url.into_url().unwrap();
+        debug!("POST {url}");
+        let resp = self
+            .reqwest
+            .post(url)
+            .header("accept", "application/json")
+            .form(form)
+            .send()
+            .await?;
+        Ok(resp.json().await?)
+    }
+
     pub async fn download_file<U: IntoUrl>(
         &self,
         url: U,
@@ -215,16 +401,82 @@ impl Client {
         let parent = path.parent().unwrap();
         file::create_dir_all(parent)?;
         let mut file = tempfile::NamedTempFile::with_prefix_in(path, parent)?;
+        let mut stats = DownloadStats::new(resp.content_length());
         while let Some(chunk) = resp.chunk().await? {
             file.write_all(&chunk)?;
             if let Some(pr) = pr {
                 pr.inc(chunk.len() as u64);
+                if stats.record(chunk.len() as u64) {
+                    pr.set_download_stats(&stats);
+                }
             }
         }
         file.persist(path)?;
         Ok(())
     }
 
+    /// Downloads `url` and hands the body to `f` as a blocking reader while it is still
+    /// being downloaded, so it never has to be written to disk.
+    /// `f` runs on a blocking thread, if it stops reading early the download is aborted.
+    pub async fn stream_with_headers<U, F, T>(
+        &self,
+        url: U,
+        headers: &HeaderMap,
+        pr: Option<&dyn SingleReport>,
+        f: F,
+    ) -> Result<T>
+    where
+        U: IntoUrl,
+        F: FnOnce(ChunkReader) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let url = url.into_url()?;
+        debug!("GET Streaming {}", &url);
+        let mut resp = self.get_async_with_headers(url.clone(), headers).await?;
+        if let Some(length) = resp.content_length()
+            && let Some(pr) = pr
+        {
+            pr.set_length(length);
+            pr.set_position(0);
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        let reader = tokio::task::spawn_blocking(move || {
+            f(ChunkReader {
+                rx,
+                chunk: Cursor::new(vec![]),
+            })
+        });
+        let mut stats = DownloadStats::new(resp.content_length());
+        let download: Result<()> = async {
+            while let Some(chunk) = resp.chunk().await? {
+                if let Some(pr) = pr {
+                    pr.inc(chunk.len() as u64);
+                    if stats.record(chunk.len() as u64) {
+                        pr.set_download_stats(&stats);
+                    }
+                }
+                if tx.send(Ok(chunk.to_vec())).await.is_err() {
+                    // the reader has already failed, its error is returned below
+                    break;
+                }
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(err) = &download {
+            let _ = tx
+                .send(Err(std::io::Error::other(format!(
+                    "download failed: {err}"
+                ))))
+                .await;
+        }
+        drop(tx);
+        let out = reader.await?;
+        download?;
+        out
+    }
+
     async fn send_with_https_fallback(
         &self,
         method: Method,
@@ -259,15 +511,62 @@ impl Client {
 
     async fn send_once(
         &self,
-        method: Method,
+        mut method: Method,
         mut url: Url,
         headers: &HeaderMap,
         verb_label: &str,
     ) -> Result<Response> {
         apply_url_replacements(&mut url);
+        let mut headers = headers.clone();
+        for _ in 0..MAX_PINNED_REDIRECTS {
+            let resp = self
+                .send_request(method.clone(), url.clone(), &headers, verb_label)
+                .await?;
+            let Some(next) = pinned_redirect(&url, &resp)? else {
+                return Ok(resp);
+            };
+            trace!("{url} redirects to {next}");
+            // like reqwest, don't send credentials to other hosts
+            if (next.host_str(), next.port_or_known_default())
+                != (url.host_str(), url.port_or_known_default())
+            {
+                for header in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, WWW_AUTHENTICATE] {
+                    headers.remove(header);
+                }
+            }
+            if resp.status() == StatusCode::SEE_OTHER
+                || (method == Method::POST
+                    && matches!(
+                        resp.status(),
+                        StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND
+                    ))
+            {
+                method = Method::GET;
+            }
+            url = next;
+        }
+        bail!("too many redirects, stopped at {url}")
+    }
+
+    async fn send_request(
+        &self,
+        method: Method,
+        url: Url,
+        headers: &HeaderMap,
+        verb_label: &str,
+    ) -> Result<Response> {
+        let options = host_options(&url).unwrap_or_default();
+        if let Some(pause) = options.rate_limit_pause {
+            RateLimitPause::for_host(&options.host)
+                .wait(pause, &url)
+                .await;
+        }
         debug!("{} {}", verb_label, &url);
-        let mut req = self.reqwest.request(method, url.clone());
+        let mut req = self.client_for(&url)?.request(method, url.clone());
         req = req.headers(headers.clone());
+        if let Some(user_agent) = options.user_agent {
+            req = req.header(reqwest::header::USER_AGENT, user_agent);
+        }
         let resp = match req.send().await {
             Ok(resp) => resp,
             Err(err) => {
@@ -310,6 +609,192 @@ impl Client {
     }
 }
 
+/// A response body stored with its validators, see `Client::json_headers_conditional`
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// the pagination header, which servers may leave out of 304 responses
+    link: Option<String>,
+    body: String,
+}
+
+/// Options for the requests made to `host` within `with_host_options`
+#[derive(Debug, Clone, Default)]
+pub struct HostOptions {
+    pub host: String,
+    /// sha256 fingerprint of the TLS certificate `host` must present, hex with or without colons
+    pub tls_fingerprint: Option<String>,
+    /// minimum time between requests to `host`, see `RateLimitPause`
+    pub rate_limit_pause: Option<Duration>,
+    /// sent instead of the default user agent, e.g.: for instances that block unknown user agents
+    pub user_agent: Option<String>,
+}
+
+/// Runs `f` with `options` applied to the requests it makes to `options.host`, e.g.: to only
+/// pin the certificate of a host for the requests of the tool that set `tls_fingerprint`
+pub async fn with_host_options<F: Future>(options: HostOptions, f: F) -> F::Output {
+    HOST_OPTIONS.scope(options, f).await
+}
+
+fn host_options(url: &Url) -> Option<HostOptions> {
+    let host = url.host_str()?;
+    HOST_OPTIONS
+        .try_with(|options| (options.host == host).then(|| options.clone()))
+        .ok()
+        .flatten()
+}
+
+/// reqwest's default limit, for the redirects followed by `pinned_redirect`
+const MAX_PINNED_REDIRECTS: usize = 10;
+
+/// Where a response from a host pinned with `tls_fingerprint` redirects to. The pinned client
+/// doesn't follow redirects itself so e.g.: a CDN serving the assets of a pinned forge is
+/// verified against the same root certificates as every other request.
+fn pinned_redirect(url: &Url, resp: &Response) -> Result<Option<Url>> {
+    if !matches!(
+        resp.status(),
+        StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT
+    ) || host_options(url).is_none_or(|o| o.tls_fingerprint.is_none())
+    {
+        return Ok(None);
+    }
+    let Some(location) = resp.headers().get(LOCATION) else {
+        return Ok(None);
+    };
+    Ok(Some(url.join(location.to_str()?)?))
+}
+
+/// Accepts the certificate of `host` if its sha256 fingerprint is pinned, instead of checking it
+/// against the root certificates, so a self-signed certificate can be pinned as well.
+/// The pinned client only connects to `host`, redirects to other hosts go through the regular
+/// client.
+#[cfg(tls_pinning)]
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    host: String,
+    fingerprint: String,
+    provider: Arc<CryptoProvider>,
+}
+
+#[cfg(tls_pinning)]
+impl PinnedCertVerifier {
+    fn new(host: String, fingerprint: String) -> Self {
+        Self {
+            host,
+            fingerprint,
+            provider: Arc::new(rustls::crypto::ring::default_provider()),
+        }
+    }
+}
+
+#[cfg(tls_pinning)]
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        if server_name.to_str() != self.host {
+            return Err(rustls::Error::General(format!(
+                "the certificate pinned for {} can't verify {}",
+                self.host,
+                server_name.to_str()
+            )));
+        }
+        let actual = hash::hash_sha256_to_str(end_entity);
+        if actual != self.fingerprint {
+            return Err(rustls::Error::General(format!(
+                "TLS certificate fingerprint mismatch for {}:\nExpected: {}\nActual:   {actual}",
+                self.host, self.fingerprint
+            )));
+        }
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> std::result::Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+static RATE_LIMIT_PAUSES: Lazy<Mutex<HashMap<String, Arc<RateLimitPause>>>> =
+    Lazy::new(Default::default);
+
+/// Spaces out the requests to an API host, e.g.: to not get rate limited by an API shared by
+/// many CI jobs. There is one per host so the tools using the same API share it.
+#[derive(Debug, Default)]
+struct RateLimitPause {
+    /// when the next request may be made
+    next: Mutex<Option<Instant>>,
+}
+
+impl RateLimitPause {
+    fn for_host(host: &str) -> Arc<Self> {
+        RATE_LIMIT_PAUSES
+            .lock()
+            .unwrap()
+            .entry(host.to_string())
+            .or_default()
+            .clone()
+    }
+
+    /// Waits for the next slot and keeps the host free for `pause` after it
+    async fn wait(&self, pause: Duration, url: &Url) {
+        // reserve the next slot while holding the lock so concurrent requests are spaced out too
+        let wait = {
+            let mut next = self.next.lock().unwrap();
+            let now = Instant::now();
+            let at = next.map_or(now, |next| next.max(now));
+            *next = Some(at + pause);
+            at - now
+        };
+        if !wait.is_zero() {
+            trace!(
+                "rate_limit_pause: waiting {}ms before {url}",
+                wait.as_millis()
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
 pub fn error_code(e: &Report) -> Option<u16> {
     if e.to_string().contains("404") {
         // TODO: not this when I can figure out how to use eyre properly
@@ -322,6 +807,25 @@ pub fn error_code(e: &Report) -> Option<u16> {
     }
 }
 
+/// Whether a failed request is worth retrying, i.e.: timeouts, connection errors, 429 and 5xx
+pub fn is_transient_error(e: &Report) -> bool {
+    match error_code(e) {
+        Some(code) => matches!(code, 408 | 429) || code >= 500,
+        None => e
+            .chain()
+            .filter_map(|e| e.downcast_ref::<reqwest::Error>())
+            .any(|e| e.is_timeout() || e.is_connect() || e.is_request()),
+    }
+}
+
+/// Whether a request failed without getting a response, i.e.: the host could not be reached
+pub fn is_connection_error(e: &Report) -> bool {
+    error_code(e).is_none()
+        && e.chain()
+            .filter_map(|e| e.downcast_ref::<reqwest::Error>())
+            .any(|e| e.is_connect() || e.is_timeout())
+}
+
 fn github_headers(url: &Url) -> HeaderMap {
     let mut headers = HeaderMap::new();
     if url.host_str() == Some("api.github.com")
@@ -717,4 +1221,164 @@ mod tests {
             );
         });
     }
+
+    #[cfg(tls_pinning)]
+    #[test]
+    fn test_pinned_cert_verifier() {
+        let cert = CertificateDer::from(b"not a real certificate".to_vec());
+        let fingerprint = hash::hash_sha256_to_str(&cert);
+        let verify = |verifier: &PinnedCertVerifier, host: &str| {
+            let server_name = ServerName::try_from(host.to_string()).unwrap();
+            verifier.verify_server_cert(&cert, &[], &server_name, &[], UnixTime::now())
+        };
+        let verifier = PinnedCertVerifier::new("pinned.example.com".into(), fingerprint);
+        assert!(verify(&verifier, "pinned.example.com").is_ok());
+        // other hosts are only reached through the regular client
+        assert!(verify(&verifier, "other.example.com").is_err());
+
+        let verifier = PinnedCertVerifier::new("pinned.example.com".into(), "00".repeat(32));
+        let err = verify(&verifier, "pinned.example.com").unwrap_err();
+        assert!(err.to_string().contains("fingerprint mismatch"));
+    }
+
+    /// Redirects every request to `location` over TLS with a self-signed certificate for
+    /// localhost, returns the port and the fingerprint of the certificate
+    #[cfg(tls_pinning)]
+    fn serve_tls_redirect(location: String) -> (u16, String) {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let fingerprint = hash::hash_sha256_to_str(cert.cert.der());
+        let key = rustls::pki_types::PrivateKeyDer::Pkcs8(cert.key_pair.serialize_der().into());
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert.cert.der().clone()], key)
+            .unwrap();
+        let config = Arc::new(config);
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let conn = rustls::ServerConnection::new(config.clone()).unwrap();
+                let mut tls = rustls::StreamOwned::new(conn, stream.unwrap());
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    match tls.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = write!(
+                    tls,
+                    "HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                );
+                tls.conn.send_close_notify();
+                let _ = tls.flush();
+            }
+        });
+        (port, fingerprint)
+    }
+
+    #[cfg(tls_pinning)]
+    #[tokio::test]
+    async fn test_pinned_host_redirect() {
+        let mut server = mockito::Server::new_async().await;
+        let asset = server
+            .mock("GET", "/tool.tar.gz")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_body("tool")
+            .create_async()
+            .await;
+        let (port, fingerprint) = serve_tls_redirect(format!("{}/tool.tar.gz", server.url()));
+        let options = HostOptions {
+            host: "localhost".into(),
+            tls_fingerprint: Some(fingerprint),
+            ..Default::default()
+        };
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("token secret"));
+        let url = format!("https://localhost:{port}/releases/download/v1.0.0/tool.tar.gz");
+        let resp = with_host_options(options, HTTP.get_async_with_headers(url, &headers))
+            .await
+            .unwrap();
+        // the other host is fetched with the regular client and without the token
+        assert_eq!(resp.text().await.unwrap(), "tool");
+        asset.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_host_options() {
+        let url = Url::parse("https://pinned.example.com/api/v1").unwrap();
+        assert!(host_options(&url).is_none());
+        let options = HostOptions {
+            host: "pinned.example.com".into(),
+            tls_fingerprint: Some("00".repeat(32)),
+            ..Default::default()
+        };
+        with_host_options(options, async {
+            assert!(host_options(&url).is_some());
+            let other = Url::parse("https://other.example.com/api/v1").unwrap();
+            assert!(host_options(&other).is_none());
+            let http = Url::parse("http://pinned.example.com/api/v1").unwrap();
+            assert!(HTTP.client_for(&http).is_err());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_host_options_user_agent() {
+        let url = Url::parse("https://user-agent.example.com/api/v1/repos").unwrap();
+        let options = HostOptions {
+            host: "user-agent.example.com".into(),
+            user_agent: Some("acme-ci/1.0".into()),
+            ..Default::default()
+        };
+        with_host_options(options, async {
+            let user_agent = host_options(&url).unwrap().user_agent;
+            assert_eq!(user_agent, Some("acme-ci/1.0".to_string()));
+            let other = Url::parse("https://other.example.com/api/v1/repos").unwrap();
+            assert!(host_options(&other).is_none());
+        })
+        .await;
+        // other tools using the same host send the default user agent
+        assert!(host_options(&url).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_pause() {
+        let url = Url::parse("https://rate-limit-pause.example.com/releases").unwrap();
+        let pause = Duration::from_millis(50);
+        let start = Instant::now();
+        RateLimitPause::for_host("rate-limit-pause.example.com")
+            .wait(pause, &url)
+            .await;
+        assert!(start.elapsed() < pause);
+        // the tools using the same host share the pause
+        RateLimitPause::for_host("rate-limit-pause.example.com")
+            .wait(pause, &url)
+            .await;
+        RateLimitPause::for_host("rate-limit-pause.example.com")
+            .wait(pause, &url)
+            .await;
+        assert!(start.elapsed() >= pause * 2);
+        let other = Instant::now();
+        RateLimitPause::for_host("other.example.com")
+            .wait(pause, &url)
+            .await;
+        assert!(other.elapsed() < pause);
+
+        // only requests within the scope of the tool that set it are paused
+        let options = HostOptions {
+            host: "rate-limit-pause.example.com".into(),
+            rate_limit_pause: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        with_host_options(options, async {
+            assert!(host_options(&url).unwrap().rate_limit_pause.is_some());
+        })
+        .await;
+        assert!(host_options(&url).is_none());
+    }
 }
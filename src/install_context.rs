@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::ui::progress_report::SingleReport;
@@ -9,4 +10,9 @@ pub struct InstallContext {
     pub pr: Box<dyn SingleReport>,
     pub force: bool,
     pub dry_run: bool,
+    /// install from this file instead of downloading, see `mise install --from-file`
+    pub from_file: Option<PathBuf>,
+    /// installing for another os/arch, see `mise install --platform`, so nothing that
+    /// runs or prepares the installed binaries on this system is done
+    pub foreign_platform: bool,
 }
@@ -1,12 +1,14 @@
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use eyre::Result;
+use eyre::{Result, bail};
 
 use crate::dirs;
 use crate::file::{create_dir_all, display_path};
 use crate::hash::hash_to_str;
+use crate::ui::time::format_duration;
 
-pub type OnLockedFn = Box<dyn Fn(&Path)>;
+pub type OnLockedFn = Box<dyn Fn(&Path) + Send + Sync>;
 
 pub struct LockFile {
     path: PathBuf,
@@ -24,7 +26,7 @@ impl LockFile {
 
     pub fn with_callback<F>(mut self, cb: F) -> Self
     where
-        F: Fn(&Path) + 'static,
+        F: Fn(&Path) + Send + Sync + 'static,
     {
         self.on_locked = Some(Box::new(cb));
         self
@@ -43,6 +45,32 @@ impl LockFile {
         }
         Ok(lock)
     }
+
+    /// Like `lock` but gives up after `timeout`, e.g.: if another process holds the lock.
+    /// Waits with `tokio::time::sleep` so it doesn't block the runtime.
+    pub async fn lock_with_timeout(self, timeout: Duration) -> Result<fslock::LockFile> {
+        if let Some(parent) = self.path.parent() {
+            create_dir_all(parent)?;
+        }
+        let mut lock = fslock::LockFile::open(&self.path)?;
+        let start = Instant::now();
+        if !lock.try_lock()? {
+            if let Some(f) = &self.on_locked {
+                f(&self.path)
+            }
+            while !lock.try_lock()? {
+                if start.elapsed() >= timeout {
+                    bail!(
+                        "timed out after {} waiting for lock {}",
+                        format_duration(timeout),
+                        display_path(&self.path)
+                    );
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+        Ok(lock)
+    }
 }
 
 pub(crate) fn get(path: &Path, force: bool) -> eyre::Result<Option<fslock::LockFile>> {
@@ -58,3 +86,30 @@ pub(crate) fn get(path: &Path, force: bool) -> eyre::Result<Option<fslock::LockF
     };
     Ok(lock)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lock_with_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mise.lock");
+        let lock = LockFile::new(&path)
+            .lock_with_timeout(Duration::from_secs(1))
+            .await
+            .unwrap();
+        let err = LockFile::new(&path)
+            .lock_with_timeout(Duration::from_millis(200))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"));
+        drop(lock);
+        assert!(
+            LockFile::new(&path)
+                .lock_with_timeout(Duration::from_millis(200))
+                .await
+                .is_ok()
+        );
+    }
+}
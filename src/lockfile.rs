@@ -1,21 +1,26 @@
+use crate::cli::args::BackendArg;
 use crate::config::{Config, Settings};
 use crate::file;
 use crate::file::display_path;
 use crate::path::PathExt;
 use crate::registry::{REGISTRY, tool_enabled};
 use crate::toolset::{ToolSource, ToolVersion, ToolVersionList, Toolset};
-use eyre::{Report, Result, bail};
+use eyre::{Report, Result, WrapErr, bail};
 use itertools::Itertools;
 use serde_derive::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock as Lazy;
 use std::sync::Mutex;
+use std::time::Duration;
 use std::{
     collections::{BTreeMap, HashMap, HashSet},
     sync::Arc,
 };
 use toml_edit::DocumentMut;
 
+/// how long to wait for another mise process to finish writing a lockfile
+const LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Lockfile {
@@ -121,6 +126,21 @@ impl From<PlatformInfo> for toml::Value {
 }
 
 impl Lockfile {
+    /// Takes an exclusive lock on the lockfile at `path` so concurrent mise processes
+    /// don't overwrite each other's changes. Hold it across the whole read-modify-save.
+    pub async fn lock(path: &Path) -> Result<fslock::LockFile> {
+        crate::lock_file::LockFile::new(path)
+            .with_callback(|l| debug!("waiting for lock on {}", display_path(l)))
+            .lock_with_timeout(LOCK_TIMEOUT)
+            .await
+            .wrap_err_with(|| {
+                format!(
+                    "failed to lock {}, another mise process may be writing to it",
+                    display_path(path)
+                )
+            })
+    }
+
     pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         if !path.exists() {
@@ -128,7 +148,12 @@ impl Lockfile {
         }
         trace!("reading lockfile {}", path.display_user());
         let content = file::read_to_string(path)?;
-        let mut table: toml::Table = toml::from_str(&content)?;
+        Self::parse(&content)
+            .wrap_err_with(|| format!("failed to parse lockfile {}", display_path(path)))
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut table: toml::Table = toml::from_str(content)?;
 
         let tools: toml::Table = table
             .remove("tools")
@@ -155,16 +180,13 @@ impl Lockfile {
         }
 
         if has_single_version_format {
-            debug!(
-                "Auto-migrated lockfile from single-version to multi-version format: {}",
-                path.display()
-            );
+            debug!("Auto-migrated lockfile from single-version to multi-version format");
         }
 
         Ok(lockfile)
     }
 
-    fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
         if self.is_empty() {
             let _ = file::remove_file(path);
         } else {
@@ -196,9 +218,25 @@ impl Lockfile {
     pub fn tools(&self) -> &BTreeMap<String, Vec<LockfileTool>> {
         &self.tools
     }
+
+    /// Moves the locked versions of `old` to `new`, returns false if `old` isn't locked
+    pub fn rename_tool(&mut self, old: &str, new: &BackendArg) -> bool {
+        let Some(mut versions) = self.tools.remove(old) else {
+            return false;
+        };
+        for version in &mut versions {
+            version.backend = Some(new.full());
+        }
+        self.tools.insert(new.short.clone(), versions);
+        true
+    }
 }
 
-pub fn update_lockfiles(config: &Config, ts: &Toolset, new_versions: &[ToolVersion]) -> Result<()> {
+pub async fn update_lockfiles(
+    config: &Config,
+    ts: &Toolset,
+    new_versions: &[ToolVersion],
+) -> Result<()> {
     if !Settings::get().lockfile || !Settings::get().experimental {
         return Ok(());
     }
@@ -270,6 +308,7 @@ pub fn update_lockfiles(config: &Config, ts: &Toolset, new_versions: &[ToolVersi
             tools.len(),
             display_path(&lockfile_path)
         );
+        let _lock = Lockfile::lock(&lockfile_path).await?;
         let mut existing_lockfile = Lockfile::read(&lockfile_path)
             .unwrap_or_else(|err| handle_missing_lockfile(err, &lockfile_path));
 
@@ -46,6 +46,8 @@ mod exit;
 #[cfg_attr(windows, path = "fake_asdf_windows.rs")]
 mod fake_asdf;
 mod file;
+mod forge_tokens;
+pub(crate) mod forgejo;
 mod git;
 pub(crate) mod github;
 pub(crate) mod gitlab;
@@ -61,6 +63,7 @@ pub(crate) mod logger;
 pub(crate) mod maplit;
 mod migrate;
 mod minisign;
+mod netrc;
 pub(crate) mod parallel;
 mod path;
 mod path_env;
@@ -0,0 +1,108 @@
+//! Credentials from `~/.netrc` (or the file in `NETRC`), keyed by hostname.
+//!
+//! These are used when no token is set in the environment or saved by `mise backends login`.
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::LazyLock as Lazy;
+
+use eyre::{Result, eyre};
+use itertools::Itertools;
+
+use crate::{env, file};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub login: String,
+    pub password: String,
+}
+
+static NETRC: Lazy<HashMap<String, Credentials>> = Lazy::new(load);
+
+/// Returns the credentials for `host`, e.g.: "codeberg.org"
+pub fn get(host: &str) -> Option<&'static Credentials> {
+    NETRC.get(host)
+}
+
+fn path() -> PathBuf {
+    env::var_path("NETRC").unwrap_or_else(|| env::HOME.join(".netrc"))
+}
+
+fn load() -> HashMap<String, Credentials> {
+    let path = path();
+    if !path.exists() {
+        return Default::default();
+    }
+    match file::read_to_string(&path).and_then(|s| parse(&s)) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!("failed to read {}: {err:#}", file::display_path(&path));
+            Default::default()
+        }
+    }
+}
+
+fn parse(s: &str) -> Result<HashMap<String, Credentials>> {
+    // the netrc crate doesn't skip comments
+    let s = s
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .join("\n");
+    let netrc = ::netrc::Netrc::parse(Cursor::new(s)).map_err(|err| match err {
+        ::netrc::Error::Io(err) => eyre!(err),
+        ::netrc::Error::Parse(msg, line) => eyre!("{msg} on line {line}"),
+    })?;
+    let mut entries = HashMap::new();
+    // `default` matches every host, don't send those credentials anywhere we're asked to
+    for (machine, entry) in netrc.hosts {
+        let Some(password) = entry.password else {
+            continue;
+        };
+        // the first entry for a host wins
+        entries.entry(machine).or_insert(Credentials {
+            login: entry.login,
+            password,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let entries = parse(
+            r#"
+# github
+machine github.com login octocat password gho_abc
+machine codeberg.org
+    login alice
+    password secret
+macdef init
+machine evil.example.com login mallory password nope
+
+machine github.com login other password gho_def
+default login anonymous password guest
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            entries.get("github.com"),
+            Some(&Credentials {
+                login: "octocat".into(),
+                password: "gho_abc".into(),
+            })
+        );
+        assert_eq!(
+            entries.get("codeberg.org"),
+            Some(&Credentials {
+                login: "alice".into(),
+                password: "secret".into(),
+            })
+        );
+        assert_eq!(entries.len(), 2);
+        assert!(parse("login nobody").is_err());
+    }
+}
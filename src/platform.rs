@@ -33,6 +33,20 @@ impl Platform {
         }
     }
 
+    /// Normalize common os/arch aliases, e.g.: "darwin-aarch64" -> "macos-arm64"
+    pub fn normalize(mut self) -> Self {
+        self.os = match self.os.as_str() {
+            "darwin" | "macos" => "macos".to_string(),
+            other => other.to_string(),
+        };
+        self.arch = match self.arch.as_str() {
+            "x86_64" | "amd64" => "x64".to_string(),
+            "aarch64" | "arm64" => "arm64".to_string(),
+            other => other.to_string(),
+        };
+        self
+    }
+
     /// Get the current platform from system information
     pub fn current() -> Self {
         let settings = Settings::get();
@@ -47,9 +61,9 @@ impl Platform {
     pub fn validate(&self) -> Result<()> {
         // Validate OS
         match self.os.as_str() {
-            "linux" | "macos" | "windows" => {}
+            "linux" | "macos" | "windows" | "freebsd" | "openbsd" | "netbsd" => {}
             _ => bail!(
-                "Unsupported OS '{}'. Supported: linux, macos, windows",
+                "Unsupported OS '{}'. Supported: linux, macos, windows, freebsd, openbsd, netbsd",
                 self.os
             ),
         }
@@ -79,8 +93,12 @@ impl Platform {
 
     /// Check if this platform is compatible with the current system
     pub fn is_compatible_with_current(&self) -> bool {
-        let current = Self::current();
-        self.os == current.os && self.arch == current.arch
+        self.is_compatible_with(&Self::current())
+    }
+
+    /// Check if this platform has the same os and arch as `other`
+    pub fn is_compatible_with(&self, other: &Platform) -> bool {
+        self.os == other.os && self.arch == other.arch
     }
 
     /// Convert to platform key format used in lockfiles
@@ -202,6 +220,7 @@ mod tests {
         assert!(Platform::parse("linux-x64").unwrap().validate().is_ok());
         assert!(Platform::parse("macos-arm64").unwrap().validate().is_ok());
         assert!(Platform::parse("windows-x64").unwrap().validate().is_ok());
+        assert!(Platform::parse("freebsd-x64").unwrap().validate().is_ok());
         assert!(Platform::parse("linux-x64-gnu").unwrap().validate().is_ok());
 
         // Invalid OS
@@ -224,6 +243,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_platform_normalize() {
+        let platform = Platform::parse("darwin-aarch64").unwrap().normalize();
+        assert_eq!(platform.to_key(), "macos-arm64");
+
+        let platform = Platform::parse("linux-x86_64").unwrap().normalize();
+        assert_eq!(platform.to_key(), "linux-x64");
+        assert!(platform.validate().is_ok());
+
+        let platform = Platform::parse("windows-x64").unwrap().normalize();
+        assert_eq!(platform.to_key(), "windows-x64");
+    }
+
     #[test]
     fn test_platform_to_key() {
         let platform1 = Platform::parse("linux-x64").unwrap();
@@ -21,6 +21,7 @@ pub enum ShellType {
     Nu,
     Xonsh,
     Zsh,
+    #[value(alias = "powershell")]
     Pwsh,
 }
 
@@ -65,7 +66,7 @@ impl FromStr for ShellType {
             "nu" => Ok(Self::Nu),
             "xonsh" => Ok(Self::Xonsh),
             "zsh" => Ok(Self::Zsh),
-            "pwsh" => Ok(Self::Pwsh),
+            "pwsh" | "powershell" => Ok(Self::Pwsh),
             _ => Err(format!("unsupported shell type: {s}")),
         }
     }
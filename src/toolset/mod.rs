@@ -45,7 +45,9 @@ mod tool_version;
 mod tool_version_list;
 mod tool_version_options;
 
-pub use tool_version_options::{ToolVersionOptions, parse_tool_options};
+pub use tool_version_options::{
+    ENV_EXPANDED_OPTIONS, SCRIPT_OPTIONS, ToolVersionOptions, expand_env_vars, parse_tool_options,
+};
 
 // Cache Toolset::list_paths results across identical toolsets within a process.
 // Keyed by project_root plus sorted list of backend@version pairs currently installed.
@@ -64,6 +66,8 @@ pub struct InstallOptions {
     pub auto_install_disable_tools: Option<Vec<String>>,
     pub resolve_options: ResolveOptions,
     pub dry_run: bool,
+    /// install from this file instead of downloading, see `mise install --from-file`
+    pub from_file: Option<PathBuf>,
 }
 
 impl Default for InstallOptions {
@@ -78,6 +82,7 @@ impl Default for InstallOptions {
             auto_install_disable_tools: Settings::get().auto_install_disable_tools.clone(),
             resolve_options: Default::default(),
             dry_run: false,
+            from_file: None,
         }
     }
 }
@@ -454,6 +459,7 @@ impl Toolset {
                 let mut results = vec![];
 
                 for tr in filtered_trs {
+                    mpr.footer_start();
                     let result = async {
                         let tv = tr.resolve(&config, &opts.resolve_options).await?;
                         let ctx = InstallContext {
@@ -462,6 +468,8 @@ impl Toolset {
                             pr: mpr.add_with_options(&tv.style(), opts.dry_run),
                             force: opts.force,
                             dry_run: opts.dry_run,
+                            from_file: opts.from_file.clone(),
+                            foreign_platform: false,
                         };
                         // Avoid wrapping the backend error here so the error location
                         // points to the backend implementation (more helpful for debugging).
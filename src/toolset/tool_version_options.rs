@@ -1,3 +1,4 @@
+use eyre::{Result, bail};
 use indexmap::IndexMap;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -160,6 +161,57 @@ impl ToolVersionOptions {
     }
 }
 
+/// Tool options holding shell commands that mise runs while installing a tool
+pub const SCRIPT_OPTIONS: &[&str] = &[
+    "postinstall",
+    "post_download_hook",
+    "pre_extract_script",
+    "post_install_script",
+    "verify_binary",
+];
+
+/// Tool options holding paths or URLs, the only ones where `${VAR}` is expanded since
+/// patterns and filters use `$` and braces themselves
+pub const ENV_EXPANDED_OPTIONS: &[&str] = &[
+    "api_url",
+    "url",
+    "download_mirror",
+    "checksum_url",
+    "asset_hash_url",
+    "fallback_repos",
+    "signing_key_url",
+    "bin_path",
+    "content_path",
+    "pkg_payload_path",
+];
+
+/// Substitutes `${VAR}` and `${VAR:-default}` with values from `lookup`.
+/// Fails if a variable is unset and has no default.
+pub fn expand_env_vars(value: &str, lookup: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            bail!("unterminated variable reference in {value:?}");
+        };
+        let expr = &rest[start + 2..start + end];
+        let (name, default) = match expr.split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (expr, None),
+        };
+        match lookup(name).or_else(|| default.map(str::to_string)) {
+            Some(v) => out.push_str(&v),
+            None => bail!(
+                "environment variable {name} is not set, use ${{{name}:-default}} to provide a default"
+            ),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
 pub fn parse_tool_options(s: &str) -> ToolVersionOptions {
     let mut tvo = ToolVersionOptions::default();
     for opt in s.split(',') {
@@ -211,6 +263,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expand_env_vars() {
+        let lookup = |name: &str| (name == "GITEA_URL").then(|| "https://git.example.com".into());
+        assert_eq!(
+            expand_env_vars("${GITEA_URL}/api/v1", lookup).unwrap(),
+            "https://git.example.com/api/v1"
+        );
+        assert_eq!(
+            expand_env_vars("${UNSET:-https://gitea.com}/api/v1", lookup).unwrap(),
+            "https://gitea.com/api/v1"
+        );
+        assert_eq!(
+            expand_env_vars("${GITEA_URL:-x}${UNSET:-}", lookup).unwrap(),
+            "https://git.example.com"
+        );
+        assert_eq!(
+            expand_env_vars("$HOME/{version}", lookup).unwrap(),
+            "$HOME/{version}"
+        );
+        let err = expand_env_vars("${UNSET}/api", lookup).unwrap_err();
+        assert!(err.to_string().contains("UNSET is not set"));
+        assert!(expand_env_vars("${GITEA_URL", lookup).is_err());
+    }
+
     #[test]
     fn test_nested_option_with_os_arch_dash() {
         let mut opts = IndexMap::new();
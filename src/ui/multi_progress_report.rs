@@ -20,11 +20,17 @@ pub struct MultiProgressReport {
     total_count: Mutex<usize>,
     report_progress: Mutex<HashMap<usize, (u64, u64)>>, // report_id -> (position, length)
     next_report_id: Mutex<usize>,
+    started_count: Mutex<usize>,  // Tools that have started installing
+    finished_count: Mutex<usize>, // Tools that have finished installing (success or failure)
     last_osc_percentage: Mutex<Option<u8>>, // Last OSC percentage sent, to avoid duplicate updates
 }
 
 static INSTANCE: Mutex<Option<Arc<MultiProgressReport>>> = Mutex::new(None);
 
+/// Batches with at least this many tools show a summary in the footer since the
+/// individual progress bars scroll out of view
+const FOOTER_SUMMARY_MIN_TOOLS: usize = 10;
+
 impl MultiProgressReport {
     pub fn try_get() -> Option<Arc<Self>> {
         INSTANCE.lock().unwrap().as_ref().cloned()
@@ -72,6 +78,8 @@ impl MultiProgressReport {
             total_count: Mutex::new(0),
             report_progress: Mutex::new(HashMap::new()),
             next_report_id: Mutex::new(0),
+            started_count: Mutex::new(0),
+            finished_count: Mutex::new(0),
             last_osc_percentage: Mutex::new(None),
         }
     }
@@ -175,9 +183,27 @@ impl MultiProgressReport {
         if n == 0 {
             return;
         }
+        *self.finished_count.lock().unwrap() += n;
         if let Some(f) = &*self.footer.lock().unwrap() {
             f.inc(n as u64);
         }
+        self.update_footer_summary();
+    }
+    /// Mark a tool in the batch as started so it is counted as in progress
+    pub fn footer_start(&self) {
+        *self.started_count.lock().unwrap() += 1;
+        self.update_footer_summary();
+    }
+    fn update_footer_summary(&self) {
+        let total = *self.total_count.lock().unwrap();
+        if total < FOOTER_SUMMARY_MIN_TOOLS {
+            return;
+        }
+        let started = *self.started_count.lock().unwrap();
+        let finished = *self.finished_count.lock().unwrap();
+        if let Some(f) = &*self.footer.lock().unwrap() {
+            f.set_footer_summary(footer_summary(total, started, finished));
+        }
     }
     pub fn footer_finish(&self) {
         if let Some(f) = &*self.footer.lock().unwrap() {
@@ -306,6 +332,16 @@ impl MultiProgressReport {
     }
 }
 
+fn footer_summary(total: usize, started: usize, finished: usize) -> String {
+    let done = finished.min(total);
+    let in_progress = started.saturating_sub(finished).min(total - done);
+    let queued = total - done - in_progress;
+    format!(
+        "Installing {}/{total} tools... ({done} done, {in_progress} in progress, {queued} queued)",
+        done + in_progress
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -318,4 +354,20 @@ mod tests {
         pr.println("".into());
         pr.set_message("test".into());
     }
+
+    #[test]
+    fn test_footer_summary() {
+        assert_eq!(
+            footer_summary(10, 3, 2),
+            "Installing 3/10 tools... (2 done, 1 in progress, 7 queued)"
+        );
+        assert_eq!(
+            footer_summary(10, 0, 0),
+            "Installing 0/10 tools... (0 done, 0 in progress, 10 queued)"
+        );
+        assert_eq!(
+            footer_summary(10, 10, 12),
+            "Installing 10/10 tools... (10 done, 0 in progress, 0 queued)"
+        );
+    }
 }
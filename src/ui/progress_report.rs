@@ -1,13 +1,14 @@
 #![allow(unknown_lints)]
 #![allow(clippy::literal_string_with_formatting_args)]
 
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use std::{
     fmt::{Display, Formatter},
     sync::Mutex,
 };
 
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{HumanBytes, HumanDuration, ProgressBar, ProgressStyle};
 use std::sync::LazyLock as Lazy;
 
 use crate::progress_trace;
@@ -55,6 +56,109 @@ pub trait SingleReport: Send + Sync + std::fmt::Debug {
     ///
     /// Then each set_length() call will allocate 33.33% of the total progress
     fn start_operations(&self, _count: usize) {}
+
+    /// Report transfer statistics (speed/ETA) for an in-progress download
+    fn set_download_stats(&self, _stats: &DownloadStats) {}
+
+    /// Show a summary of the batch (e.g.: "Installing 3/10 tools...") next to the footer text
+    fn set_footer_summary(&self, _summary: String) {}
+}
+
+/// How far back to look when computing the rolling download speed
+const DOWNLOAD_SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// Tracks bytes received during a download to compute a rolling average speed and ETA
+#[derive(Debug)]
+pub struct DownloadStats {
+    downloaded: u64,
+    total: Option<u64>,
+    samples: VecDeque<(Instant, u64)>,
+    last_report: Option<Instant>,
+}
+
+impl DownloadStats {
+    pub fn new(total: Option<u64>) -> Self {
+        Self::new_at(total, Instant::now())
+    }
+
+    fn new_at(total: Option<u64>, now: Instant) -> Self {
+        Self {
+            downloaded: 0,
+            total,
+            samples: VecDeque::from([(now, 0)]),
+            last_report: None,
+        }
+    }
+
+    /// Records `delta` more bytes received, returns true if enough time has passed that the
+    /// stats should be reported again
+    pub fn record(&mut self, delta: u64) -> bool {
+        self.record_at(delta, Instant::now())
+    }
+
+    fn record_at(&mut self, delta: u64, now: Instant) -> bool {
+        self.downloaded += delta;
+        self.samples.push_back((now, self.downloaded));
+        while self.samples.len() > 2
+            && now.duration_since(self.samples[0].0) > DOWNLOAD_SPEED_WINDOW
+        {
+            self.samples.pop_front();
+        }
+        if self
+            .last_report
+            .is_some_and(|last| now.duration_since(last) < TICK_INTERVAL)
+        {
+            return false;
+        }
+        self.last_report = Some(now);
+        true
+    }
+
+    pub fn downloaded(&self) -> u64 {
+        self.downloaded
+    }
+
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Average bytes/second over the rolling window
+    pub fn bytes_per_sec(&self) -> f64 {
+        let (Some((start, start_bytes)), Some((end, end_bytes))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+        let elapsed = end.duration_since(*start).as_secs_f64();
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        (end_bytes - start_bytes) as f64 / elapsed
+    }
+
+    /// Estimated time remaining, if the total size is known and data is flowing
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total?.saturating_sub(self.downloaded);
+        let speed = self.bytes_per_sec();
+        if speed <= 0.0 {
+            return None;
+        }
+        Some(Duration::from_secs_f64(remaining as f64 / speed))
+    }
+}
+
+impl Display for DownloadStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", HumanBytes(self.downloaded))?;
+        if let Some(total) = self.total {
+            write!(f, "/{}", HumanBytes(total))?;
+        }
+        write!(f, " @ {}/s", HumanBytes(self.bytes_per_sec() as u64))?;
+        if let Some(eta) = self.eta() {
+            write!(f, ", {} left", HumanDuration(eta))?;
+        }
+        Ok(())
+    }
 }
 
 static SPIN_TEMPLATE: Lazy<ProgressStyle> = Lazy::new(|| {
@@ -76,6 +180,17 @@ static PROG_TEMPLATE: Lazy<ProgressStyle> = Lazy::new(|| {
     ProgressStyle::with_template(tmpl).unwrap()
 });
 
+/// Like PROG_TEMPLATE without bytes and ETA, which `set_download_stats` adds to the message
+static DOWNLOAD_TEMPLATE: Lazy<ProgressStyle> = Lazy::new(|| {
+    let tmpl = match *env::TERM_WIDTH {
+        0..=89 => "{prefix} {wide_msg} {bar:10.cyan/blue} {percent:>2}%",
+        90..=99 => "{prefix} {wide_msg} {bar:15.cyan/blue} {percent:>2}%",
+        100..=114 => "{prefix} {wide_msg} {bar:10.cyan/blue} {percent:>2}%",
+        _ => "{prefix} {wide_msg} {bar:20.cyan/blue} {elapsed:>3.dim.italic}",
+    };
+    ProgressStyle::with_template(tmpl).unwrap()
+});
+
 /// Renders a progress bar with text overlaid on top
 /// The text background alternates based on progress:
 /// - Filled portion: black text on cyan background
@@ -147,6 +262,8 @@ pub struct ProgressReport {
     operation_base: Mutex<u64>, // Base progress for current operation (0, 333333, 666666...)
     operation_length: Mutex<u64>, // Allocated length for current operation
     footer_text: Option<String>, // If set, this is a footer bar with text overlay
+    footer_summary: Mutex<Option<String>>, // Batch summary shown after the footer text
+    message: Mutex<String>,     // Last message set, download stats are appended to it
 }
 
 static LONGEST_PLUGIN_NAME: Lazy<usize> = Lazy::new(|| {
@@ -188,6 +305,8 @@ impl ProgressReport {
             operation_base: Mutex::new(0),
             operation_length: Mutex::new(1_000_000), // Full range initially
             footer_text: None,
+            footer_summary: Mutex::new(None),
+            message: Mutex::new(String::new()),
         }
     }
 
@@ -209,6 +328,8 @@ impl ProgressReport {
             operation_base: Mutex::new(0),
             operation_length: Mutex::new(length),
             footer_text: Some(footer_text),
+            footer_summary: Mutex::new(None),
+            message: Mutex::new(String::new()),
         }
     }
 
@@ -223,7 +344,11 @@ impl ProgressReport {
                 0.0
             };
             let width = *env::TERM_WIDTH;
-            let rendered = render_progress_bar_with_overlay(footer_text, progress, width);
+            let text = match &*self.footer_summary.lock().unwrap() {
+                Some(summary) => format!("{footer_text} · {summary}"),
+                None => footer_text.clone(),
+            };
+            let rendered = render_progress_bar_with_overlay(&text, progress, width);
             self.pb.set_message(rendered);
         }
     }
@@ -286,7 +411,9 @@ impl SingleReport for ProgressReport {
         });
     }
     fn set_message(&self, message: String) {
-        self.pb.set_message(message.replace('\r', ""));
+        let message = message.replace('\r', "");
+        self.pb.set_message(message.clone());
+        *self.message.lock().unwrap() = message;
     }
     fn inc(&self, delta: u64) {
         self.pb.inc(delta);
@@ -408,6 +535,18 @@ impl SingleReport for ProgressReport {
         );
         *self.total_operations.lock().unwrap() = Some(count.max(1));
     }
+
+    fn set_download_stats(&self, stats: &DownloadStats) {
+        let message = self.message.lock().unwrap();
+        self.pb.set_style(DOWNLOAD_TEMPLATE.clone());
+        self.pb
+            .set_message(format!("{message} {}", style::edim(stats.to_string())));
+    }
+
+    fn set_footer_summary(&self, summary: String) {
+        *self.footer_summary.lock().unwrap() = Some(summary);
+        self.update_footer_display();
+    }
 }
 
 #[derive(Debug)]
@@ -489,4 +628,30 @@ mod tests {
         pr.set_message("message".into());
         pr.finish_with_message("message".into());
     }
+
+    #[test]
+    fn test_download_stats() {
+        let start = Instant::now();
+        let mut stats = DownloadStats::new_at(Some(3000), start);
+        assert!(stats.record_at(500, start + Duration::from_secs(1)));
+        // reports are throttled to the tick interval
+        assert!(!stats.record_at(500, start + Duration::from_millis(1100)));
+        assert_eq!(stats.downloaded(), 1000);
+        assert_eq!(stats.bytes_per_sec() as u64, 909);
+        assert!(stats.record_at(0, start + Duration::from_secs(2)));
+        assert_eq!(stats.bytes_per_sec() as u64, 500);
+        assert_eq!(stats.eta(), Some(Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn test_download_stats_rolling_window() {
+        let start = Instant::now();
+        let mut stats = DownloadStats::new_at(None, start);
+        stats.record_at(10_000, start + Duration::from_secs(1));
+        stats.record_at(100, start + Duration::from_secs(10));
+        stats.record_at(100, start + Duration::from_secs(11));
+        // the initial burst falls outside the window
+        assert_eq!(stats.bytes_per_sec() as u64, 100);
+        assert_eq!(stats.eta(), None);
+    }
 }